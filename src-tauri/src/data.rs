@@ -3,7 +3,10 @@ use crate::util::channel::Sender;
 use crate::util::error::Error;
 use crate::util::{db, dialog, process};
 use rocket::serde::json::Json;
-use serde::Deserialize;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::fs::File as FilesystemFile;
+use std::io::BufWriter;
 use std::sync::Mutex;
 use tauri::ipc::JavaScriptChannelId;
 use tauri::{AppHandle, Emitter, Manager, Webview};
@@ -14,10 +17,13 @@ mod column_type;
 mod datasource;
 mod export;
 mod file;
+mod import;
 mod report;
 mod row;
 mod schema;
 mod table;
+#[cfg(test)]
+pub(crate) mod testing;
 mod view;
 
 fn reset(app: &AppHandle) -> Result<(), Error> {
@@ -35,9 +41,12 @@ fn reset(app: &AppHandle) -> Result<(), Error> {
 
 #[tauri::command]
 /// Create a new DungeonDB database file.
-pub fn init_new(app: AppHandle) -> Result<(), Error> {
+/// The journal mode defaults to WAL, but DELETE is needed for files kept on network shares.
+pub fn init_new(app: AppHandle, journal_mode: Option<db::JournalMode>) -> Result<(), Error> {
     // Create a new DungeonDB database file
-    db::init_new()?;
+    db::init_new(journal_mode.unwrap_or_default())?;
+    column::set_validation_enabled(true);
+    view::clear_bulk_mode();
 
     // Reset the window
     reset(&app)?;
@@ -46,9 +55,17 @@ pub fn init_new(app: AppHandle) -> Result<(), Error> {
 
 #[tauri::command]
 /// Initialize a connection to an existing DungeonDB database file.
-pub fn init_existing(app: AppHandle, path: String) -> Result<(), Error> {
+pub fn init_existing(app: AppHandle, path: String, journal_mode: Option<db::JournalMode>) -> Result<(), Error> {
     // Initialize a connection to an existing DungeonDB database file.
-    db::init_existing(path)?;
+    db::init_existing(path, journal_mode.unwrap_or_default())?;
+    column::set_validation_enabled(true);
+    view::clear_bulk_mode();
+
+    // Clear out rows that have been in the trash for longer than the retention period
+    let purged: Vec<row::PurgedRows> = purge_expired_trash()?;
+    if purged.len() > 0 {
+        app.emit(TRASH_PURGED_SIGNAL, purged)?;
+    }
 
     // Reset the app
     reset(&app)?;
@@ -68,6 +85,7 @@ pub fn save_shortcut(app: &AppHandle) -> Result<(), Error> {
         // Record that there are no changes since the last save
         let mut has_unsaved_changes = HAS_UNSAVED_CHANGES.lock().unwrap();
         *has_unsaved_changes = false;
+        *ACTIONS_SINCE_SAVE.lock().unwrap() = 0;
     }
     Ok(())
 }
@@ -80,6 +98,7 @@ pub fn save_as(app: AppHandle) -> Result<(), Error> {
         // Record that there are no changes since the last save
         let mut has_unsaved_changes = HAS_UNSAVED_CHANGES.lock().unwrap();
         *has_unsaved_changes = false;
+        *ACTIONS_SINCE_SAVE.lock().unwrap() = 0;
     }
     Ok(())
 }
@@ -93,7 +112,7 @@ pub fn load(app: AppHandle) -> Result<(), Error> {
         .add_filter("DungeonDB File (*.dndb)", &["dndb"])
         .pick_file(|path| {
             if let Some(path) = path {
-                match init_existing(app, path.to_string()) {
+                match init_existing(app, path.to_string(), None) {
                     Ok(_) => {},
                     Err(e) => {
                         todo!("Do something with the error.")
@@ -116,6 +135,9 @@ pub enum QueryStream {
     Tables {
         channel: JavaScriptChannelId,
     },
+    TableGroups {
+        channel: JavaScriptChannelId,
+    },
     Reports {
         channel: JavaScriptChannelId,
     },
@@ -133,6 +155,10 @@ pub enum QueryStream {
         schema_oid: i64,
         channel: JavaScriptChannelId,
     },
+    EffectiveColumns {
+        table_oid: i64,
+        channel: JavaScriptChannelId,
+    },
     RootDatasources {
         channel: JavaScriptChannelId,
     },
@@ -154,13 +180,17 @@ pub enum QueryStream {
         schema_oid: i64,
         filters: Vec<(String, i64)>,
         limit: cell::RetrievalLimit,
+        #[serde(default)]
+        visible_column_oids: Option<Vec<i64>>,
         column_channel: JavaScriptChannelId,
         cell_channel: JavaScriptChannelId,
     },
 
     TableRowLabels {
         table_oid: i64,
-        processid: i64
+        processid: i64,
+        #[serde(default)]
+        column_oid: Option<i64>
     }
 }
 
@@ -171,6 +201,9 @@ impl QueryStream {
             Self::Tables { channel } => schema::HierarchicalListItemMetadata::query_tables(
                 Sender::Channel(channel.channel_on(webview)),
             ),
+            Self::TableGroups { channel } => table::Group::query_all(
+                Sender::Channel(channel.channel_on(webview)),
+            ),
             Self::Reports { channel } => schema::HierarchicalListItemMetadata::query_reports(
                 Sender::Channel(channel.channel_on(webview)),
             ),
@@ -203,6 +236,14 @@ impl QueryStream {
                 schema_oid,
             ),
 
+            Self::EffectiveColumns {
+                table_oid,
+                channel,
+            } => column::FullMetadata::query_effective(
+                Sender::Channel(channel.channel_on(webview)),
+                table_oid,
+            ),
+
             Self::RootDatasources { channel } => {
                 datasource::Datasource::query_roots(Sender::Channel(channel.channel_on(webview)))
             }
@@ -230,6 +271,7 @@ impl QueryStream {
                 schema_oid,
                 filters,
                 limit,
+                visible_column_oids,
                 column_channel,
                 cell_channel,
             } => cell::SchemaCellStream::query_by_schema(
@@ -238,17 +280,20 @@ impl QueryStream {
                 schema_oid,
                 filters,
                 limit,
+                visible_column_oids,
             ),
 
             Self::TableRowLabels { 
                 table_oid, 
-                processid 
+                processid,
+                column_oid
             } => {
                 tauri::async_runtime::spawn_blocking(move || {
                     table::DropdownValue::emit_table_row_labels(
                         app, 
                         processid, 
-                        table_oid
+                        table_oid,
+                        column_oid
                     )
                 });
                 Ok(())
@@ -269,6 +314,43 @@ pub fn get_table_metadata(table_oid: i64) -> Result<table::FullMetadata, Error>
     table::FullMetadata::get(table_oid)
 }
 
+#[tauri::command]
+/// Gets the metadata for every table, along with the number of columns displayed in each.
+pub fn get_all_table_metadata() -> Result<Vec<table::Summary>, Error> {
+    table::FullMetadata::get_all()
+}
+
+#[tauri::command]
+/// Finds the reports that draw data from a table, so that the user can be warned before the table is changed.
+pub fn find_reports_using_table(table_oid: i64) -> Result<Vec<report::DependentReport>, Error> {
+    report::FullMetadata::find_using_table(table_oid)
+}
+
+#[tauri::command]
+/// Sets the number of rows displayed per page when a table is opened.
+pub fn set_table_page_size(table_oid: i64, page_size: Option<i64>) -> Result<(), Error> {
+    table::FullMetadata::set_page_size(table_oid, page_size)
+}
+
+#[tauri::command]
+/// Gets the space used by a table and each of its columns.
+pub fn get_table_storage_stats(table_oid: i64) -> Result<table::StorageStats, Error> {
+    table::FullMetadata::get_storage_stats(table_oid)
+}
+
+#[tauri::command]
+/// Finds the columns of a table in which every row has the same value, or no value at all.
+pub fn find_degenerate_columns(table_oid: i64) -> Result<Vec<table::DegenerateColumn>, Error> {
+    table::FullMetadata::find_degenerate_columns(table_oid)
+}
+
+#[tauri::command]
+/// Computes a checksum over the values of a table's rows, which changes whenever the data of the table changes.
+/// The checksum depends on the order of the rows, and ignores trashed rows.
+pub fn get_table_checksum(table_oid: i64) -> Result<String, Error> {
+    table::FullMetadata::get_checksum(table_oid)
+}
+
 #[tauri::command]
 /// Gets the metadata for a report.
 pub fn get_report_metadata(report_oid: i64) -> Result<report::FullMetadata, Error> {
@@ -287,16 +369,188 @@ pub fn get_column(column_oid: i64) -> Result<column::FullMetadata, Error> {
     column::FullMetadata::get(column_oid)
 }
 
+#[tauri::command]
+/// Gets whether each column of a schema is defined on the schema itself or inherited from a master table.
+pub fn get_column_sources(schema_oid: i64) -> Result<Vec<column::ColumnSource>, Error> {
+    column::FullMetadata::query_sources(schema_oid)
+}
+
+#[tauri::command]
+/// Gets the CSS style of each column displayed in a table or report.
+pub fn get_column_styles(schema_oid: i64) -> Result<Vec<column::ColumnStyle>, Error> {
+    column::FullMetadata::query_styles(schema_oid)
+}
+
+#[tauri::command]
+/// Renumbers the orderings of the columns displayed in a table, removing gaps and duplicates while keeping their order.
+/// Only the columns of the table and its master tables are renumbered.
+/// Returns the number of columns whose ordering changed.
+pub fn normalize_column_ordering(app: AppHandle, table_oid: i64) -> Result<usize, Error> {
+    let (changed_count, affected_schema_oids) = column::FullMetadata::normalize_orderings(table_oid)?;
+    if changed_count > 0 {
+        schema::FullMetadata::emit_affected_schema(&app, affected_schema_oids)?;
+    }
+    Ok(changed_count)
+}
+
+#[tauri::command]
+/// Gets the conditional formatting rules of a column.
+pub fn get_column_format_rules(column_oid: i64) -> Result<Vec<column::FormatRule>, Error> {
+    column::FullMetadata::get_format_rules(column_oid)
+}
+
+#[tauri::command]
+/// Adds a conditional formatting rule to a column, which applies a CSS style to each cell that meets a condition.
+/// Returns the OID of the new rule.
+pub async fn add_column_format_rule(app: AppHandle, column_oid: i64, condition: column::FormatCondition, style: String) -> Result<i64, Error> {
+    // The OID is chosen up front, so that undoing and redoing the action keeps the rule under the same OID
+    let rule_oid: i64 = column::FullMetadata::get_next_format_rule_oid()?;
+    execute(
+        app,
+        Action::AddColumnFormatRule(column::FormatRule {
+            oid: rule_oid,
+            column_oid,
+            condition,
+            style,
+        }),
+    )
+    .await?;
+    Ok(rule_oid)
+}
+
+#[tauri::command]
+/// Removes a conditional formatting rule from its column.
+pub async fn remove_column_format_rule(app: AppHandle, rule_oid: i64) -> Result<(), Error> {
+    execute(app, Action::RemoveColumnFormatRule(rule_oid)).await
+}
+
+#[tauri::command]
+/// Gets the table currently referenced by an Object, Select, or Multiselect column.
+pub fn get_column_reference_target(column_oid: i64) -> Result<Option<column::ReferenceTarget>, Error> {
+    column::FullMetadata::get_reference_target(column_oid)
+}
+
+#[tauri::command]
+/// Finds every column in the database whose type has the given mode.
+pub fn find_columns_of_type(mode: String) -> Result<Vec<column::ColumnOfType>, Error> {
+    column::FullMetadata::find_by_type_mode(mode)
+}
+
+#[tauri::command]
+/// Counts the rows that select a particular dropdown value in a Select or Multiselect column.
+pub fn count_dropdown_value_usage(column_oid: i64, value_oid: i64) -> Result<i64, Error> {
+    column::FullMetadata::count_reference_usage(column_oid, value_oid)
+}
+
+#[tauri::command]
+/// Gets the current SQL definitions of the views associated with a schema.
+pub fn get_schema_view_sql(schema_oid: i64) -> Result<view::SchemaViewSql, Error> {
+    view::get_schema_view_sql(schema_oid)
+}
+
+#[tauri::command]
+/// Checks the column references of a formula, returning each reference to a column that does not exist.
+pub fn validate_formula_columns(formula: String) -> Result<Vec<String>, Error> {
+    column::FullMetadata::find_unknown_formula_columns(&formula)
+}
+
+#[tauri::command]
+/// Recreates the views of every schema, repairing any that have fallen out of sync with the metadata.
+pub fn rebuild_all_schema_views(app: AppHandle) -> Result<(), Error> {
+    view::rebuild_all_schema_views()?;
+    schema::FullMetadata::emit_all_schema(&app)
+}
+
+#[tauri::command]
+/// Recreates the views of a single schema, reporting any columns that prevent them from being recreated.
+pub fn repair_schema_views(app: AppHandle, schema_oid: i64) -> Result<view::SchemaViewRepair, Error> {
+    let repair: view::SchemaViewRepair = view::repair_schema_views(schema_oid)?;
+    if repair.repaired {
+        schema::FullMetadata::emit_affected_schema(&app, vec![schema_oid])?;
+    }
+    Ok(repair)
+}
+
+#[tauri::command]
+/// Measures how deeply Object columns are nested inside a table, and whether any chain of them loops back on itself.
+pub fn get_object_nesting(table_oid: i64) -> Result<table::ObjectNesting, Error> {
+    table::FullMetadata::get_object_nesting(table_oid)
+}
+
+#[tauri::command]
+/// Creates a new folder for grouping tables in the sidebar, returning its OID.
+pub fn create_table_group(name: String) -> Result<i64, Error> {
+    table::Group::create(name)
+}
+
+#[tauri::command]
+/// Renames a folder of tables.
+pub fn rename_table_group(app: AppHandle, group_oid: i64, name: String) -> Result<(), Error> {
+    table::Group::rename(group_oid, name)?;
+    schema::FullMetadata::emit_affected_schema(&app, table::Group::get_table_oids(group_oid)?)
+}
+
+#[tauri::command]
+/// Deletes a folder of tables. The tables in the folder become ungrouped.
+pub fn delete_table_group(app: AppHandle, group_oid: i64) -> Result<(), Error> {
+    let table_oids: Vec<i64> = table::Group::delete(group_oid)?;
+    schema::FullMetadata::emit_affected_schema(&app, table_oids)
+}
+
+#[tauri::command]
+/// Moves a table into a folder, or out of any folder if no folder is given.
+pub fn assign_table_group(app: AppHandle, table_oid: i64, group_oid: Option<i64>) -> Result<(), Error> {
+    table::Group::assign_table(table_oid, group_oid)?;
+    schema::FullMetadata::emit_affected_schema(&app, vec![table_oid])
+}
+
+#[tauri::command]
+/// Gets every constraint that values written to a column are validated against.
+pub fn get_column_validations(column_oid: i64) -> Result<column::ColumnValidations, Error> {
+    column::FullMetadata::get_validations(column_oid)
+}
+
+#[tauri::command]
+/// Parses a value entered into an Integer column, according to the column's display format.
+pub fn parse_integer_cell_value(column_oid: i64, text: String) -> Result<Option<i64>, Error> {
+    let column = column::FullMetadata::get(column_oid)?;
+    column.integer_format.parse(&text)
+}
+
 #[tauri::command]
 pub fn get_cell(cell_identifier: cell::CellIdentifier) -> cell::Cell {
     cell::Cell::get(cell_identifier)
 }
 
+#[tauri::command]
+/// Gets the underlying value of a cell, such as the OID of the row referenced by a Select cell.
+pub fn get_cell_true_value(table_oid: i64, column_oid: i64, row_oid: i64) -> Result<Option<String>, Error> {
+    cell::Cell::get_true_value(table_oid, column_oid, row_oid)
+}
+
+#[tauri::command]
+/// Gets the length of the longest text displayed in a column, so that the column can be sized to fit it.
+pub fn get_column_max_display_length(table_oid: i64, column_oid: i64) -> Result<i64, Error> {
+    column::FullMetadata::get_max_display_length(table_oid, column_oid)
+}
+
+#[tauri::command]
+/// Gets the default value of a column, formatted for display.
+pub fn get_column_default(column_oid: i64) -> Result<Option<String>, Error> {
+    column::FullMetadata::get_default_display(column_oid)
+}
+
 #[tauri::command]
 pub fn get_image_src(file: file::File) -> Result<String, Error> {
     file.get_image_src()
 }
 
+#[tauri::command]
+/// Gets the size of a file, in bytes.
+pub fn get_file_size(file: file::File) -> Result<i64, Error> {
+    file.get_size()
+}
+
 #[tauri::command]
 pub fn download_file(file_oid: i64, download_to_path: String) -> Result<(), Error> {
     let file: file::File = file::File::get(file_oid)?;
@@ -311,6 +565,137 @@ pub fn upload_file(mut file: file::File, upload_from_path: String) -> Result<i64
     })
 }
 
+#[tauri::command]
+/// Gets the creation and modification timestamps of a row.
+pub fn get_row_timestamps(table_oid: i64, row_oid: i64) -> Result<Option<row::RowTimestamps>, Error> {
+    row::get_timestamps(table_oid, row_oid)
+}
+
+#[tauri::command]
+/// Gets the rows of a table that changed after the given version.
+pub fn get_changed_rows(table_oid: i64, since_version: i64) -> Result<row::ChangedRows, Error> {
+    row::get_changed_rows(table_oid, since_version)
+}
+
+#[tauri::command]
+/// Counts the rows of a table by the subtype that each row currently is.
+pub fn get_subtype_counts(base_table_oid: i64) -> Result<Vec<row::SubtypeCount>, Error> {
+    row::get_subtype_counts(base_table_oid)
+}
+
+#[tauri::command]
+/// Lists the subtypes that a row could be changed to, with the number of columns each change would hide or add.
+pub fn get_retype_options(base_table_oid: i64, base_row_oid: i64) -> Result<Vec<row::RetypeOption>, Error> {
+    row::get_retype_options(base_table_oid, base_row_oid)
+}
+
+#[tauri::command]
+/// Finds groups of rows in a table with matching values in each of the given columns.
+pub fn find_duplicate_rows(table_oid: i64, column_oids: Vec<i64>) -> Result<Vec<Vec<i64>>, Error> {
+    row::find_duplicates(table_oid, column_oids)
+}
+
+#[tauri::command]
+/// Gets the label displayed for a row referenced by another table.
+pub fn get_reference_display(referenced_table_oid: i64, referenced_row_oid: i64) -> Result<Option<String>, Error> {
+    row::get_label(referenced_table_oid, referenced_row_oid)
+}
+
+#[tauri::command]
+/// Gets the display value of each primary key column of a row referenced by another table, so that they can be displayed separately.
+pub fn get_reference_key_parts(referenced_table_oid: i64, referenced_row_oid: i64) -> Result<Vec<row::ReferenceKeyPart>, Error> {
+    row::get_key_parts(referenced_table_oid, referenced_row_oid)
+}
+
+#[tauri::command]
+/// Gets the OID that the next row inserted into a table would receive.
+pub fn get_next_oid(table_oid: i64) -> Result<i64, Error> {
+    row::get_next_oid(table_oid)
+}
+
+#[tauri::command]
+/// Gets the lowest and highest OIDs of the rows in a table, or null if the table has no rows.
+pub fn get_oid_range(table_oid: i64) -> Result<Option<(i64, i64)>, Error> {
+    row::get_oid_range(table_oid)
+}
+
+#[tauri::command]
+/// Exports a single row of a table as text, so that it can be copied to the clipboard.
+pub fn export_row(table_oid: i64, row_oid: i64, format: export::RowExportFormat) -> Result<String, Error> {
+    export::export_row(table_oid, row_oid, format)
+}
+
+#[tauri::command]
+/// Exports the rows of a report to a CSV or JSON file.
+pub fn export_report(app: AppHandle, report_oid: i64, format: export::ExportFormat, path: String) -> Result<(), Error> {
+    export::export_report(
+        report_oid,
+        format,
+        path,
+        Sender::Event(&app, export::EXPORT_PROGRESS_SIGNAL),
+    )
+}
+
+#[tauri::command]
+/// Exports the rows of a table to a newline-delimited JSON file.
+pub fn export_table_ndjson(app: AppHandle, table_oid: i64, path: String) -> Result<(), Error> {
+    export::export_table_ndjson(
+        table_oid,
+        path,
+        Sender::Event(&app, export::EXPORT_PROGRESS_SIGNAL),
+    )
+}
+
+#[tauri::command]
+/// Detects the delimiter, quote character, header, and column names of a CSV file before it is imported.
+pub fn sniff_csv(path: String) -> Result<import::csv::CsvFormat, Error> {
+    import::csv::sniff(path)
+}
+
+#[tauri::command]
+/// Imports tables from a JSON file, either into new tables or into existing tables with the same names.
+/// The import can be stopped with cancel_import, using the same processid.
+pub async fn import_table_json(app: AppHandle, processid: i64, path: String, create_new: bool, preserve_oids: bool) -> Result<import::json::ImportReport, Error> {
+    // Import on a blocking thread, so that cancel_import can be handled while the import is in progress
+    let import_app: AppHandle = app.clone();
+    let import_result: Result<import::json::ImportReport, Error> = tauri::async_runtime::spawn_blocking(move || {
+        import::json::import(
+            path,
+            create_new,
+            preserve_oids,
+            processid,
+            Sender::Event(&import_app, import::IMPORT_PROGRESS_SIGNAL),
+        )
+    })
+    .await?;
+    process::end_process(processid);
+    let report: import::json::ImportReport = match import_result {
+        Ok(report) => report,
+        Err(Error::ProcessCancelled { processid }) => {
+            // Nothing was imported, so there are no changes to report
+            app.emit(import::IMPORT_CANCELLED_SIGNAL, processid)?;
+            return Err(Error::ProcessCancelled { processid });
+        }
+        Err(e) => {
+            return Err(e);
+        }
+    };
+    {
+        let mut has_unsaved_changes = HAS_UNSAVED_CHANGES.lock().unwrap();
+        *has_unsaved_changes = true;
+    }
+
+    // Send signal to update the affected tables
+    schema::FullMetadata::emit_affected_schema(&app, report.table_oids.clone())?;
+    Ok(report)
+}
+
+#[tauri::command]
+/// Cancels an import that is in progress, rolling back any rows it has already imported.
+pub fn cancel_import(processid: i64) {
+    process::cancel_process(processid);
+}
+
 #[tauri::command] 
 pub fn get_processid() -> i64 {
     process::get_processid()
@@ -323,18 +708,44 @@ pub fn get_table_row_labels(app: AppHandle, processid: i64, table_oid: i64) {
 
 
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum Action {
     CreateTable(table::FullMetadata),
     EditTable(table::FullMetadata),
+    CloneTableSchema {
+        source_table_oid: i64,
+        new_name: String,
+    },
     CreateReport(report::FullMetadata),
     EditReport(report::FullMetadata),
     TrashSchema(i64),
+    TrashSchemaCascade(i64),
     UntrashSchema(i64),
+    UntrashSchemas(Vec<i64>),
+    EditSchemaDisplayOrder {
+        schema_oid: i64,
+        display_order: Option<i64>,
+    },
+    ReorderSchema {
+        schema_oid: i64,
+        new_position: i64,
+    },
 
     CreateColumn(column::FullMetadata),
+    CreateColumnRelative {
+        metadata: column::FullMetadata,
+        reference_column_oid: i64,
+        position: column::ColumnPosition,
+    },
     EditColumn(column::FullMetadata),
+    EditColumnNotNull {
+        column_oid: i64,
+        backfill_value: Option<String>,
+    },
+    PromoteObjectTable {
+        table_oid: i64,
+    },
     EditColumnStyle {
         metadata: column::FullMetadata,
         new_column_style: String,
@@ -343,6 +754,11 @@ pub enum Action {
         metadata: column::FullMetadata,
         new_column_ordering: Option<i64>,
     },
+    SwapColumnOrdering {
+        schema_oid: i64,
+        column_oid_a: i64,
+        column_oid_b: i64,
+    },
     TrashColumn {
         schema_oid: i64,
         column_oid: i64,
@@ -356,10 +772,14 @@ pub enum Action {
         trash_column_oid: i64,
         untrash_column_oid: i64,
     },
+    AddColumnFormatRule(column::FormatRule),
+    RemoveColumnFormatRule(i64),
 
     CreateRow {
         table_oid: i64,
         row_oid: Option<i64>,
+        #[serde(default)]
+        insert_mode: row::InsertMode,
         fixed_parent_datasource: Option<(i64, i64, column::FullMetadata)>,
     },
     EditRowOid {
@@ -374,19 +794,57 @@ pub enum Action {
     UntrashRow {
         table_oid: i64,
         row_oid: i64,
+        #[serde(default)]
+        cascaded_rows: Vec<(i64, i64)>,
     },
     EditRowSubtype {
         table_oid: i64,
         row_oid: i64,
         inheritor_table_oid: i64,
     },
+    EditRowMaster {
+        table_oid: i64,
+        row_oid: i64,
+        master_table_oid: i64,
+        master_row_oid: i64,
+    },
+    ReassignReferences {
+        table_oid: i64,
+        from_row_oid: i64,
+        to_row_oid: i64,
+        #[serde(default)]
+        only_references: Option<Vec<row::ReassignedReference>>,
+    },
+    RestoreReferences {
+        table_oid: i64,
+        from_row_oid: i64,
+        to_row_oid: i64,
+        references: Vec<row::ReassignedReference>,
+    },
 
     EditCellContents(cell::DataCellEntry),
+    ClearCellContents {
+        table_oid: i64,
+        column_oid: i64,
+        row_oid: i64,
+    },
+    EditRowContents {
+        table_oid: i64,
+        row_oid: i64,
+        values: Vec<(i64, cell::DataCellValue)>,
+    },
 }
 
 static REVERSE_STACK: Mutex<Vec<Action>> = Mutex::new(Vec::new());
 static FORWARD_STACK: Mutex<Vec<Action>> = Mutex::new(Vec::new());
 static HAS_UNSAVED_CHANGES: Mutex<bool> = Mutex::new(false);
+/// The number of actions after which the database is saved to its main file automatically, if any.
+static AUTOSAVE_ACTION_INTERVAL: Mutex<Option<usize>> = Mutex::new(None);
+/// The number of actions executed, undone, or redone since the database was last saved.
+static ACTIONS_SINCE_SAVE: Mutex<usize> = Mutex::new(0);
+
+/// The name of the event emitted when the database is saved automatically.
+pub const SAVED_SIGNAL: &'static str = "saved";
 
 /// Records the opposite action to the one that was just performed, for undo/redo purposes.
 fn record_action(action: Action, is_forward: bool) {
@@ -405,7 +863,67 @@ fn record_action(action: Action, is_forward: bool) {
 }
 
 impl Action {
+    /// Describes the action in a human-readable way.
+    fn describe(&self) -> String {
+        match self {
+            Self::CreateTable(metadata) => format!("Create table \"{}\"", metadata.schema.name),
+            Self::EditTable(metadata) => format!("Edit table \"{}\"", metadata.schema.name),
+            Self::CloneTableSchema { source_table_oid, new_name } => format!("Copy the structure of table {source_table_oid} to table \"{new_name}\""),
+            Self::CreateReport(metadata) => format!("Create report \"{}\"", metadata.schema.name),
+            Self::EditReport(metadata) => format!("Edit report \"{}\"", metadata.schema.name),
+            Self::TrashSchema(schema_oid) => format!("Delete schema {schema_oid}"),
+            Self::TrashSchemaCascade(schema_oid) => format!("Delete schema {schema_oid} and its dependents"),
+            Self::UntrashSchema(schema_oid) => format!("Restore schema {schema_oid}"),
+            Self::UntrashSchemas(schema_oids) => format!("Restore {} schemas", schema_oids.len()),
+            Self::EditSchemaDisplayOrder { schema_oid, .. } => format!("Change display order of schema {schema_oid}"),
+            Self::ReorderSchema { schema_oid, new_position } => format!("Move schema {schema_oid} to position {new_position}"),
+
+            Self::CreateColumn(metadata) | Self::CreateColumnRelative { metadata, .. } => format!("Create column \"{}\" in schema {}", metadata.name, metadata.schema.oid),
+            Self::EditColumn(metadata) => format!("Edit column \"{}\" in schema {}", metadata.name, metadata.schema.oid),
+            Self::EditColumnNotNull { column_oid, .. } => format!("Make column {column_oid} required"),
+            Self::PromoteObjectTable { table_oid } => format!("Convert the Object column embedding table {table_oid} into a Select column"),
+            Self::EditColumnStyle { metadata, .. } => format!("Edit style of column \"{}\"", metadata.name),
+            Self::EditColumnOrdering { metadata, .. } => format!("Move column \"{}\"", metadata.name),
+            Self::SwapColumnOrdering { schema_oid, column_oid_a, column_oid_b } => format!("Swap columns {column_oid_a} and {column_oid_b} in schema {schema_oid}"),
+            Self::TrashColumn { schema_oid, column_oid } => format!("Delete column {column_oid} from schema {schema_oid}"),
+            Self::UntrashColumn { schema_oid, column_oid } => format!("Restore column {column_oid} to schema {schema_oid}"),
+            Self::RestoreColumn { schema_oid, untrash_column_oid, .. } => format!("Revert column {untrash_column_oid} in schema {schema_oid}"),
+            Self::AddColumnFormatRule(rule) => format!("Add formatting rule {} to column {}", rule.oid, rule.column_oid),
+            Self::RemoveColumnFormatRule(rule_oid) => format!("Remove formatting rule {rule_oid}"),
+
+            Self::CreateRow { table_oid, row_oid: Some(row_oid), .. } => format!("Insert row {row_oid} into table {table_oid}"),
+            Self::CreateRow { table_oid, row_oid: None, .. } => format!("Add row to table {table_oid}"),
+            Self::EditRowOid { table_oid, row_oid, new_row_oid: Some(new_row_oid) } => format!("Move row {row_oid} of table {table_oid} to {new_row_oid}"),
+            Self::EditRowOid { table_oid, row_oid, new_row_oid: None } => format!("Move row {row_oid} of table {table_oid} to the end"),
+            Self::TrashRow { table_oid, row_oid } => format!("Delete row {row_oid} from table {table_oid}"),
+            Self::UntrashRow { table_oid, row_oid, .. } => format!("Restore row {row_oid} to table {table_oid}"),
+            Self::EditRowSubtype { table_oid, row_oid, inheritor_table_oid } => format!("Change subtype of row {row_oid} of table {table_oid} to table {inheritor_table_oid}"),
+            Self::EditRowMaster { table_oid, row_oid, master_table_oid, master_row_oid } => format!("Change master of row {row_oid} of table {table_oid} to row {master_row_oid} of table {master_table_oid}"),
+            Self::ReassignReferences { table_oid, from_row_oid, to_row_oid, .. } => format!("Reassign references from row {from_row_oid} to row {to_row_oid} of table {table_oid}"),
+            Self::RestoreReferences { table_oid, from_row_oid, to_row_oid, references } => format!("Restore {} references from row {from_row_oid} to row {to_row_oid} of table {table_oid}", references.len()),
+
+            Self::EditCellContents(cell) => format!("Edit column {} of row {} in table {}", cell.column_oid, cell.row_oid, cell.table_oid),
+            Self::ClearCellContents { table_oid, column_oid, row_oid } => format!("Clear column {column_oid} of row {row_oid} in table {table_oid}"),
+            Self::EditRowContents { table_oid, row_oid, values } => format!("Edit {} cells of row {row_oid} in table {table_oid}", values.len()),
+        }
+    }
+
+    /// Performs the action, then appends it to the action log stored in the database.
+    /// Undoing and redoing are logged as the actions they execute, so the log is a history of the undo stack rather than of every change.
+    /// Changes made by commands that do not execute an action are not logged, such as
+    /// table groups, table page sizes, normalized column orderings, uploaded files, imported tables, and purges of the trash.
     async fn execute(self, app: &AppHandle, is_forward: bool) -> Result<(), Error> {
+        let Ok(logged_action) = serde_json::to_string(&self) else {
+            return Err(Error::AdhocError("Unable to record the action in the action log."));
+        };
+        self.apply(app, is_forward).await?;
+
+        let conn = db::open()?;
+        conn.execute("INSERT INTO METADATA_ACTION_LOG (ACTION) VALUES (?1)", params![logged_action])?;
+        Ok(())
+    }
+
+    async fn apply(self, app: &AppHandle, is_forward: bool) -> Result<(), Error> {
         match self {
             Self::CreateTable(mut metadata) => {
                 // Create the table
@@ -435,6 +953,17 @@ impl Action {
                 // Send signal to update table
                 schema::FullMetadata::emit_affected_schema(app, vec![metadata.schema.oid])?;
             }
+            Self::CloneTableSchema {
+                source_table_oid,
+                new_name,
+            } => {
+                // Create a table with the same structure as the source table
+                let table_oid: i64 = table::FullMetadata::clone_structure(source_table_oid, new_name)?;
+                record_action(Self::TrashSchema(table_oid), is_forward);
+
+                // Send signal to update the new table
+                schema::FullMetadata::emit_affected_schema(app, vec![table_oid])?;
+            }
             Self::CreateReport(mut metadata) => {
                 // Create the report
                 metadata.create()?;
@@ -465,12 +994,20 @@ impl Action {
             }
             Self::TrashSchema(schema_oid) => {
                 // Flag the schema for garbage collection
-                schema::FullMetadata::trash(schema_oid.clone())?;
+                schema::FullMetadata::trash(schema_oid.clone(), false)?;
                 record_action(Self::UntrashSchema(schema_oid), is_forward);
 
                 // Send signal to update schema
                 schema::FullMetadata::emit_affected_schema(app, vec![schema_oid])?;
             }
+            Self::TrashSchemaCascade(schema_oid) => {
+                // Flag the schema and every table that inherits from it for garbage collection
+                let trashed_schema_oids: Vec<i64> = schema::FullMetadata::trash(schema_oid, true)?;
+                record_action(Self::UntrashSchemas(trashed_schema_oids.clone()), is_forward);
+
+                // Send signal to update schema
+                schema::FullMetadata::emit_affected_schema(app, trashed_schema_oids)?;
+            }
             Self::UntrashSchema(schema_oid) => {
                 // Unflag the schema for garbage collection
                 schema::FullMetadata::untrash(schema_oid.clone())?;
@@ -479,6 +1016,61 @@ impl Action {
                 // Send signal to update schema
                 schema::FullMetadata::emit_affected_schema(app, vec![schema_oid])?;
             }
+            Self::UntrashSchemas(schema_oids) => {
+                // Unflag every schema for garbage collection, starting with the one that was originally trashed
+                for schema_oid in schema_oids.iter() {
+                    schema::FullMetadata::untrash(schema_oid.clone())?;
+                }
+                if let Some(schema_oid) = schema_oids.first() {
+                    record_action(Self::TrashSchemaCascade(schema_oid.clone()), is_forward);
+                }
+
+                // Send signal to update schema
+                schema::FullMetadata::emit_affected_schema(app, schema_oids)?;
+            }
+            Self::EditSchemaDisplayOrder {
+                schema_oid,
+                display_order,
+            } => {
+                // Update the display order of the schema
+                let old_display_order: Option<i64> =
+                    schema::FullMetadata::get_display_order(schema_oid.clone())?;
+                schema::FullMetadata::set_display_order(schema_oid.clone(), display_order)?;
+                record_action(
+                    Self::EditSchemaDisplayOrder {
+                        schema_oid: schema_oid.clone(),
+                        display_order: old_display_order,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update schema
+                schema::FullMetadata::emit_affected_schema(app, vec![schema_oid])?;
+            }
+            Self::ReorderSchema {
+                schema_oid,
+                new_position,
+            } => {
+                // Move the schema, shifting the schemas after it
+                let old_position: Option<i64> = schema::FullMetadata::reorder(schema_oid.clone(), new_position)?;
+                record_action(
+                    match old_position {
+                        Some(old_position) => Self::ReorderSchema {
+                            schema_oid: schema_oid.clone(),
+                            new_position: old_position,
+                        },
+                        // A schema without a position goes back to being ordered by name
+                        None => Self::EditSchemaDisplayOrder {
+                            schema_oid: schema_oid.clone(),
+                            display_order: None,
+                        },
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update schema
+                schema::FullMetadata::emit_affected_schema(app, vec![schema_oid])?;
+            }
 
             Self::CreateColumn(mut metadata) => {
                 // Create the column
@@ -494,6 +1086,24 @@ impl Action {
                 // Send signal to update schema
                 schema::FullMetadata::emit_affected_schema(app, vec![metadata.schema.oid])?;
             }
+            Self::CreateColumnRelative {
+                mut metadata,
+                reference_column_oid,
+                position,
+            } => {
+                // Create the column next to the reference column
+                metadata.create_relative(reference_column_oid, position)?;
+                record_action(
+                    Self::TrashColumn {
+                        schema_oid: metadata.schema.oid.clone(),
+                        column_oid: metadata.oid,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update schema
+                schema::FullMetadata::emit_affected_schema(app, vec![metadata.schema.oid])?;
+            }
             Self::EditColumn(mut metadata) => {
                 // Update the column
                 let old_column_oid: i64 = metadata.oid.clone();
@@ -510,6 +1120,48 @@ impl Action {
                 // Send signal to update schema
                 app.emit("column", (old_column_oid, metadata))?;
             }
+            Self::EditColumnNotNull {
+                column_oid,
+                backfill_value,
+            } => {
+                // Replace the column with a non-nullable copy, filling in its blank cells
+                let mut metadata: column::FullMetadata = column::FullMetadata::get(column_oid)?;
+                metadata.set_not_null(backfill_value.as_deref())?;
+                record_action(
+                    Self::RestoreColumn {
+                        schema_oid: metadata.schema.oid.clone(),
+                        trash_column_oid: metadata.oid,
+                        untrash_column_oid: column_oid,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update schema
+                app.emit("column", (column_oid, metadata))?;
+            }
+            Self::PromoteObjectTable { table_oid } => {
+                // Find the Object column that embeds rows of the table
+                let Some(mut metadata) = column::FullMetadata::get_object_owner(table_oid)? else {
+                    return Err(Error::AdhocError("No Object column embeds rows of this table."));
+                };
+
+                // Convert it into a Select column, so that the table can be referenced from other columns as well
+                let old_column_oid: i64 = metadata.oid.clone();
+                metadata.column_type = column_type::ColumnType::Select { oid: 0, table_oid };
+                metadata.is_nullable = true;
+                metadata.set()?;
+                record_action(
+                    Self::RestoreColumn {
+                        schema_oid: metadata.schema.oid.clone(),
+                        trash_column_oid: metadata.oid,
+                        untrash_column_oid: old_column_oid,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update schema
+                app.emit("column", (old_column_oid, metadata))?;
+            }
             Self::EditColumnStyle {
                 mut metadata,
                 new_column_style,
@@ -546,6 +1198,25 @@ impl Action {
                 // Send signal to update schema
                 schema::FullMetadata::emit_affected_schema(app, vec![metadata.schema.oid])?;
             }
+            Self::SwapColumnOrdering {
+                schema_oid,
+                column_oid_a,
+                column_oid_b,
+            } => {
+                // Exchange the orderings of the columns, which is its own inverse
+                column::FullMetadata::swap_ordering(schema_oid, column_oid_a, column_oid_b)?;
+                record_action(
+                    Self::SwapColumnOrdering {
+                        schema_oid,
+                        column_oid_a,
+                        column_oid_b,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update schema
+                schema::FullMetadata::emit_affected_schema(app, vec![schema_oid])?;
+            }
             Self::TrashColumn {
                 schema_oid,
                 column_oid,
@@ -602,14 +1273,31 @@ impl Action {
                 // Send signal to update schema
                 schema::FullMetadata::emit_affected_schema(app, vec![schema_oid])?;
             }
+            Self::AddColumnFormatRule(rule) => {
+                column::FullMetadata::add_format_rule(&rule)?;
+                record_action(Self::RemoveColumnFormatRule(rule.oid), is_forward);
+
+                // Send signal to update the cells of the column
+                let column: column::FullMetadata = column::FullMetadata::get(rule.column_oid)?;
+                schema::FullMetadata::emit_affected_schema(app, vec![column.schema.oid])?;
+            }
+            Self::RemoveColumnFormatRule(rule_oid) => {
+                let rule: column::FormatRule = column::FullMetadata::remove_format_rule(rule_oid)?;
+                let column: column::FullMetadata = column::FullMetadata::get(rule.column_oid)?;
+                record_action(Self::AddColumnFormatRule(rule), is_forward);
+
+                // Send signal to update the cells of the column
+                schema::FullMetadata::emit_affected_schema(app, vec![column.schema.oid])?;
+            }
 
             Self::CreateRow {
                 table_oid,
                 row_oid,
+                insert_mode,
                 fixed_parent_datasource,
             } => {
                 // Create the row
-                let row_oid: i64 = row::insert(table_oid, row_oid, fixed_parent_datasource)?;
+                let row_oid: i64 = row::insert(table_oid, row_oid, insert_mode, fixed_parent_datasource)?;
                 record_action(Self::TrashRow { table_oid, row_oid }, is_forward);
 
                 // Send signal to update table
@@ -634,19 +1322,23 @@ impl Action {
                 schema::FullMetadata::emit_affected_schema(app, vec![table_oid])?;
             }
             Self::TrashRow { table_oid, row_oid } => {
-                if let Some((table_oid, row_oid)) = row::trash(table_oid, row_oid)? {
-                    record_action(Self::UntrashRow { table_oid, row_oid }, is_forward);
+                if let (Some((table_oid, row_oid)), cascaded_rows) = row::trash(table_oid, row_oid)? {
+                    let mut affected_table_oids: Vec<i64> = vec![table_oid];
+                    affected_table_oids.extend(cascaded_rows.iter().map(|(cascaded_table_oid, _)| *cascaded_table_oid));
+                    record_action(Self::UntrashRow { table_oid, row_oid, cascaded_rows }, is_forward);
 
                     // Send signal to update table
-                    schema::FullMetadata::emit_affected_schema(app, vec![table_oid])?;
+                    schema::FullMetadata::emit_affected_schema(app, affected_table_oids)?;
                 }
             }
-            Self::UntrashRow { table_oid, row_oid } => {
-                row::untrash(table_oid, row_oid)?;
+            Self::UntrashRow { table_oid, row_oid, cascaded_rows } => {
+                row::untrash(table_oid, row_oid, &cascaded_rows)?;
+                let mut affected_table_oids: Vec<i64> = vec![table_oid];
+                affected_table_oids.extend(cascaded_rows.iter().map(|(cascaded_table_oid, _)| *cascaded_table_oid));
                 record_action(Self::TrashRow { table_oid, row_oid }, is_forward);
 
                 // Send signal to update table
-                schema::FullMetadata::emit_affected_schema(app, vec![table_oid])?;
+                schema::FullMetadata::emit_affected_schema(app, affected_table_oids)?;
             }
             Self::EditRowSubtype {
                 table_oid,
@@ -668,6 +1360,75 @@ impl Action {
                 schema::FullMetadata::emit_affected_schema(app, vec![table_oid])?;
             }
 
+            Self::EditRowMaster {
+                table_oid,
+                row_oid,
+                master_table_oid,
+                master_row_oid,
+            } => {
+                let old_master_row_oid: i64 =
+                    row::reparent(table_oid, row_oid, master_table_oid, master_row_oid)?;
+                record_action(
+                    Self::EditRowMaster {
+                        table_oid,
+                        row_oid,
+                        master_table_oid,
+                        master_row_oid: old_master_row_oid,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update table
+                schema::FullMetadata::emit_affected_schema(app, vec![table_oid, master_table_oid])?;
+            }
+            Self::ReassignReferences {
+                table_oid,
+                from_row_oid,
+                to_row_oid,
+                only_references,
+            } => {
+                let references: Vec<row::ReassignedReference> =
+                    row::reassign_references(table_oid, from_row_oid, to_row_oid, only_references.as_ref())?;
+                let mut affected_table_oids: Vec<i64> = references.iter().map(|r| r.table_oid).collect();
+                affected_table_oids.sort();
+                affected_table_oids.dedup();
+                record_action(
+                    Self::RestoreReferences {
+                        table_oid,
+                        from_row_oid: to_row_oid,
+                        to_row_oid: from_row_oid,
+                        references,
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update the referencing tables
+                schema::FullMetadata::emit_affected_schema(app, affected_table_oids)?;
+            }
+            Self::RestoreReferences {
+                table_oid,
+                from_row_oid,
+                to_row_oid,
+                references,
+            } => {
+                row::restore_references(table_oid, from_row_oid, to_row_oid, &references)?;
+                let mut affected_table_oids: Vec<i64> = references.iter().map(|r| r.table_oid).collect();
+                affected_table_oids.sort();
+                affected_table_oids.dedup();
+                record_action(
+                    Self::ReassignReferences {
+                        table_oid,
+                        from_row_oid: to_row_oid,
+                        to_row_oid: from_row_oid,
+                        only_references: Some(references),
+                    },
+                    is_forward,
+                );
+
+                // Send signal to update the referencing tables
+                schema::FullMetadata::emit_affected_schema(app, affected_table_oids)?;
+            }
+
             Self::EditCellContents(cell) => {
                 let execution_result: Result<(), Error> = {
                     // Update the contents of the cell
@@ -688,6 +1449,44 @@ impl Action {
                     return Err(e);
                 }
             }
+            Self::ClearCellContents {
+                table_oid,
+                column_oid,
+                row_oid,
+            } => {
+                // Set the cell to the blank value for its column, which is undone by restoring the previous contents
+                let column: column::FullMetadata = column::FullMetadata::get(column_oid)?;
+                let cell: cell::DataCellEntry = cell::DataCellEntry::new(
+                    table_oid,
+                    column_oid,
+                    row_oid,
+                    cell::DataCellValue::null_for(&column)?,
+                );
+                let old_cell: cell::DataCellEntry = cell.set()?;
+                record_action(Self::EditCellContents(old_cell), is_forward);
+
+                // Send signal to update that cell + any dependent cells
+                cell::Cell::emit_affected_cells(app, table_oid, column_oid, row_oid)?;
+            }
+            Self::EditRowContents {
+                table_oid,
+                row_oid,
+                values,
+            } => {
+                // Update the contents of every cell in the row at once
+                let old_values: Vec<(i64, cell::DataCellValue)> = cell::DataCellEntry::set_row(table_oid, row_oid, &values)?;
+                record_action(
+                    Self::EditRowContents {
+                        table_oid,
+                        row_oid,
+                        values: old_values,
+                    },
+                    is_forward,
+                );
+
+                // Send a single signal to update the cells of the row + any dependent cells
+                cell::Cell::emit_affected_row(app, table_oid, row_oid, values.into_iter().map(|(column_oid, _)| column_oid).collect())?;
+            }
         }
         Ok(())
     }
@@ -700,9 +1499,595 @@ pub async fn execute(app: AppHandle, action: Action) -> Result<(), Error> {
     action.execute(&app, true).await?;
 
     // Clear the stack of undone actions
-    let mut forward_stack = FORWARD_STACK.lock().unwrap();
-    *forward_stack = Vec::new();
-    return Ok(());
+    {
+        let mut forward_stack = FORWARD_STACK.lock().unwrap();
+        *forward_stack = Vec::new();
+    }
+
+    // Save automatically once enough actions have accumulated
+    autosave_after_action(&app)
+}
+
+/// Counts an executed, undone, or redone action toward automatic saving, saving the database once enough actions have accumulated.
+fn autosave_after_action(app: &AppHandle) -> Result<(), Error> {
+    let autosave_action_interval: Option<usize> = AUTOSAVE_ACTION_INTERVAL.lock().unwrap().clone();
+    if let Some(autosave_action_interval) = autosave_action_interval {
+        let actions_since_save: usize = {
+            let mut actions_since_save = ACTIONS_SINCE_SAVE.lock().unwrap();
+            *actions_since_save += 1;
+            *actions_since_save
+        };
+        if actions_since_save >= autosave_action_interval && db::save_to_existing_file(app)? {
+            *ACTIONS_SINCE_SAVE.lock().unwrap() = 0;
+            *HAS_UNSAVED_CHANGES.lock().unwrap() = false;
+            app.emit(SAVED_SIGNAL, ())?;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+/// Sets the number of actions after which the database is saved to its main file automatically.
+/// Automatic saving is disabled if no number is given, or while the database has never been saved to a file.
+pub fn set_autosave_action_interval(action_count: Option<usize>) -> Result<(), Error> {
+    if action_count == Some(0) {
+        return Err(Error::AdhocError("The number of actions between saves must be at least 1."));
+    }
+    *AUTOSAVE_ACTION_INTERVAL.lock().unwrap() = action_count;
+    *ACTIONS_SINCE_SAVE.lock().unwrap() = 0;
+    Ok(())
+}
+
+/// The number of days that trashed rows are kept before being permanently deleted, or None to keep them indefinitely.
+static TRASH_RETENTION_DAYS: Mutex<Option<f64>> = Mutex::new(None);
+
+/// The name of the event emitted when expired trash is purged as a file is opened.
+pub const TRASH_PURGED_SIGNAL: &'static str = "trash-purged";
+
+#[tauri::command]
+/// Sets the number of days that trashed rows are kept before being permanently deleted.
+/// Expired rows are deleted whenever a file is opened, or when purge_expired_trash is called.
+pub fn set_trash_retention_days(days: Option<f64>) -> Result<(), Error> {
+    if days.is_some_and(|days| !(days >= 0.0)) {
+        return Err(Error::AdhocError("The number of days that trash is kept for cannot be negative."));
+    }
+    *TRASH_RETENTION_DAYS.lock().unwrap() = days;
+    Ok(())
+}
+
+#[tauri::command]
+/// Permanently deletes the rows that have been in the trash for longer than the retention period.
+/// Returns the number of rows deleted from each table.
+/// The undo history is cleared if any row is deleted.
+pub fn purge_expired_trash() -> Result<Vec<row::PurgedRows>, Error> {
+    let retention_days: Option<f64> = TRASH_RETENTION_DAYS.lock().unwrap().clone();
+    let Some(retention_days) = retention_days else {
+        return Ok(Vec::new());
+    };
+    let purged: Vec<row::PurgedRows> = row::purge_expired_trash(retention_days)?;
+    if purged.len() > 0 {
+        // Actions in the undo history may refer to the deleted rows, so the history can no longer be relied upon
+        let mut reverse_stack = REVERSE_STACK.lock().unwrap();
+        *reverse_stack = Vec::new();
+
+        let mut forward_stack = FORWARD_STACK.lock().unwrap();
+        *forward_stack = Vec::new();
+
+        let mut has_unsaved_changes = HAS_UNSAVED_CHANGES.lock().unwrap();
+        *has_unsaved_changes = true;
+    }
+    Ok(purged)
+}
+
+/// A description of what an action would change, if it were performed.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunResult {
+    /// The action that would undo the change.
+    /// This identifies anything the action would create, such as the OID of a new row or column.
+    pub undo_action: Option<Action>,
+}
+
+#[tauri::command]
+/// Performs an action, then restores the database and undo history to their state beforehand.
+/// Returns a description of what the action changed, without keeping the change.
+pub async fn execute_dry_run(app: AppHandle, action: Action) -> Result<DryRunResult, Error> {
+    // Take a snapshot of the database and undo history, so that both can be restored afterwards
+    let snapshot = db::create_snapshot()?;
+    let action_count: usize = REVERSE_STACK.lock().unwrap().len();
+    let old_forward_stack: Vec<Action> = FORWARD_STACK.lock().unwrap().clone();
+    let old_has_unsaved_changes: bool = *HAS_UNSAVED_CHANGES.lock().unwrap();
+
+    // Perform the action, without adding it to the action log
+    let result: Result<(), Error> = action.apply(&app, true).await;
+
+    // Restore the snapshot, keeping the action that would undo the change
+    db::restore_snapshot(&snapshot)?;
+    let undo_action: Option<Action> = {
+        let mut reverse_stack = REVERSE_STACK.lock().unwrap();
+        let undo_action: Option<Action> = if reverse_stack.len() > action_count {
+            reverse_stack.pop()
+        } else {
+            None
+        };
+        reverse_stack.truncate(action_count);
+        undo_action
+    };
+    *FORWARD_STACK.lock().unwrap() = old_forward_stack;
+    *HAS_UNSAVED_CHANGES.lock().unwrap() = old_has_unsaved_changes;
+
+    // Windows may have refreshed to show the change, so refresh them again
+    schema::FullMetadata::emit_all_schema(&app)?;
+    result?;
+    Ok(DryRunResult { undo_action })
+}
+
+#[tauri::command]
+/// Writes every action in the action log of the database to a file, as a JSON array.
+/// The log records the actions executed, undone, and redone through the undo history, for reviewing how the database was changed.
+/// It cannot be executed to rebuild the database, since changes made outside of actions are missing; see Action::execute for those changes.
+pub fn export_action_log(path: String) -> Result<(), Error> {
+    let conn = db::open()?;
+    let mut action_log: Vec<serde_json::Value> = Vec::new();
+    for row_result in conn.prepare("SELECT ACTION FROM METADATA_ACTION_LOG ORDER BY OID")?.query_map([], |row| row.get::<_, String>("ACTION"))? {
+        let Ok(action) = serde_json::from_str::<serde_json::Value>(&row_result?) else {
+            return Err(Error::AdhocError("The action log of the database is corrupted."));
+        };
+        action_log.push(action);
+    }
+
+    let file = match FilesystemFile::create(&path) {
+        Ok(f) => f,
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "create file",
+                path: Some(path),
+                error: e,
+            });
+        }
+    };
+
+    if serde_json::to_writer_pretty(BufWriter::new(file), &action_log).is_err() {
+        return Err(Error::AdhocError("Unable to write the action log."));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+/// Copies the dropdown values of one Select or Multiselect column to another of the same kind.
+/// The table of values selected from by the source column is copied, and the target column is edited to select from the copy,
+/// so that later changes to the values of either column do not affect the other.
+/// Undoing the edit restores the target column's previous values, but leaves the copied table in place.
+pub async fn copy_dropdown_values(
+    app: AppHandle,
+    source_column_oid: i64,
+    target_column_oid: i64,
+) -> Result<(), Error> {
+    let source: column::FullMetadata = column::FullMetadata::get(source_column_oid)?;
+    let mut target: column::FullMetadata = column::FullMetadata::get(target_column_oid)?;
+
+    // Check that the columns are dropdowns of the same kind
+    let source_table_oid: i64 = match (&source.column_type, &target.column_type) {
+        (column_type::ColumnType::Select { table_oid, .. }, column_type::ColumnType::Select { .. })
+        | (column_type::ColumnType::Multiselect { table_oid, .. }, column_type::ColumnType::Multiselect { .. }) => *table_oid,
+        _ => {
+            return Err(Error::AdhocError(
+                "Dropdown values can only be copied between two Select columns or two Multiselect columns.",
+            ));
+        }
+    };
+
+    // Copy the source column's table of values
+    let source_table: table::FullMetadata = table::FullMetadata::get(source_table_oid)?;
+    let copied_table_oid: i64 = table::FullMetadata::clone_with_rows(source_table_oid, format!("{} (Copy)", source_table.schema.name))?;
+    schema::FullMetadata::emit_affected_schema(&app, vec![copied_table_oid])?;
+
+    // Point the target column at the copied table of values
+    target.column_type = match target.column_type {
+        column_type::ColumnType::Select { oid, .. } => column_type::ColumnType::Select { oid, table_oid: copied_table_oid },
+        column_type::ColumnType::Multiselect { oid, .. } => column_type::ColumnType::Multiselect { oid, table_oid: copied_table_oid },
+        column_type => column_type,
+    };
+
+    // Edit the column as an undoable action
+    execute(app, Action::EditColumn(target)).await
+}
+
+#[tauri::command]
+/// Makes a column non-nullable as an undoable action, first filling its blank cells with a value.
+/// Returns the number of cells that were filled.
+pub async fn set_column_not_null(
+    app: AppHandle,
+    table_oid: i64,
+    column_oid: i64,
+    backfill_value: Option<String>,
+) -> Result<i64, Error> {
+    let column: column::FullMetadata = column::FullMetadata::get(column_oid)?;
+    if column.schema.oid != table_oid {
+        return Err(Error::AdhocError("The column does not belong to the table."));
+    }
+
+    // Blank cells can only be left as they are if there are none
+    if column.count_nulls()? > 0 && backfill_value.is_none() {
+        return Err(Error::AdhocError("The column has blank cells, so a value to fill them with is required."));
+    }
+
+    // Edit the column as an undoable action
+    execute(
+        app,
+        Action::EditColumnNotNull {
+            column_oid,
+            backfill_value,
+        },
+    )
+    .await?;
+
+    // The action that restores the column names the new column, whose filled cells were blank in the old column
+    let new_column_oid: i64 = {
+        let reverse_stack = REVERSE_STACK.lock().unwrap();
+        match reverse_stack.last() {
+            Some(Action::RestoreColumn { trash_column_oid, .. }) => *trash_column_oid,
+            _ => return Err(Error::AdhocError("Unable to find the non-nullable column.")),
+        }
+    };
+    let conn = db::open()?;
+    let sql_count: String = format!("SELECT COUNT(*) FROM TABLE{table_oid} WHERE NOT TRASH AND COLUMN{column_oid} IS NULL AND COLUMN{new_column_oid} IS NOT NULL");
+    Ok(conn.query_one(&sql_count, [], |row| row.get(0))?)
+}
+
+#[tauri::command]
+/// Repoints every reference to a row so that it references another row of the same table instead, without trashing either row.
+/// Returns the number of references that were repointed.
+pub async fn reassign_references(
+    app: AppHandle,
+    table_oid: i64,
+    from_row_oid: i64,
+    to_row_oid: i64,
+) -> Result<usize, Error> {
+    execute(
+        app,
+        Action::ReassignReferences {
+            table_oid,
+            from_row_oid,
+            to_row_oid,
+            only_references: None,
+        },
+    )
+    .await?;
+
+    // The action that restores the references lists every reference that was repointed
+    let reverse_stack = REVERSE_STACK.lock().unwrap();
+    match reverse_stack.last() {
+        Some(Action::RestoreReferences { references, .. }) => Ok(references.len()),
+        _ => Ok(0),
+    }
+}
+
+#[tauri::command]
+/// Sets a Select cell to the row of the referenced table whose label matches a display value.
+/// Returns the OID of the matched row.
+pub async fn set_reference_by_display(
+    app: AppHandle,
+    table_oid: i64,
+    row_oid: i64,
+    column_oid: i64,
+    display_value: String,
+) -> Result<i64, Error> {
+    let column: column::FullMetadata = column::FullMetadata::get(column_oid)?;
+    let column_type::ColumnType::Select {
+        table_oid: referenced_table_oid,
+        ..
+    } = column.column_type
+    else {
+        return Err(Error::AdhocError("Only Select columns can be set by display value."));
+    };
+    let referenced_row_oid: i64 = row::find_by_label(referenced_table_oid, &display_value)?;
+
+    // Edit the cell as an undoable action
+    execute(
+        app,
+        Action::EditCellContents(cell::DataCellEntry::new(
+            table_oid,
+            column_oid,
+            row_oid,
+            cell::DataCellValue::Select {
+                linked_row_oid: Some(referenced_row_oid),
+            },
+        )),
+    )
+    .await?;
+    Ok(referenced_row_oid)
+}
+
+#[tauri::command]
+/// Renames one of the values that can be selected in a Select or Multiselect column, as an undoable action.
+/// The value is renamed in place, so every row that selects it displays the new text.
+pub async fn rename_dropdown_value(
+    app: AppHandle,
+    column_oid: i64,
+    value_oid: i64,
+    new_text: String,
+) -> Result<(), Error> {
+    let column: column::FullMetadata = column::FullMetadata::get(column_oid)?;
+    let value_table_oid: i64 = match column.column_type {
+        column_type::ColumnType::Select { table_oid, .. }
+        | column_type::ColumnType::Multiselect { table_oid, .. } => table_oid,
+        _ => {
+            return Err(Error::AdhocError("Only the values of Select and Multiselect columns can be renamed."));
+        }
+    };
+
+    // The text of a value is stored in the column that labels the rows of the table of values
+    let label_column: column::FullMetadata = column::FullMetadata::get_label_column(value_table_oid)?;
+    row::get_label(value_table_oid, value_oid)?; // Check that the value exists
+    let label_table_oid: i64 = label_column.schema.oid;
+    let label_row_oid: i64 = if label_table_oid == value_table_oid {
+        value_oid
+    } else {
+        // The label column is inherited, so the text is stored in the matching row of the master table
+        let Some(label_row_oid) = row::get_master_row_oid(value_table_oid, value_oid, label_table_oid)? else {
+            return Err(Error::AdhocError("The value does not have a row in the table that stores its label."));
+        };
+        label_row_oid
+    };
+
+    // Edit the label as an undoable action
+    execute(
+        app,
+        Action::EditCellContents(cell::DataCellEntry::new(
+            label_table_oid,
+            label_column.oid,
+            label_row_oid,
+            cell::DataCellValue::Text(Some(new_text)),
+        )),
+    )
+    .await
+}
+
+#[tauri::command]
+/// Moves one of the rows selected in a cell of a Multiselect column to a different position in the selection.
+pub async fn reorder_multiselect_selection(
+    app: AppHandle,
+    table_oid: i64,
+    row_oid: i64,
+    column_oid: i64,
+    value_oid: i64,
+    new_index: usize,
+) -> Result<(), Error> {
+    let mut linked_row_oids: Vec<i64> = {
+        let conn = db::open()?;
+        cell::DataCellEntry::query_multiselect_transact(&conn, table_oid, column_oid, row_oid)?
+    };
+    let Some(old_index) = linked_row_oids.iter().position(|linked_row_oid| *linked_row_oid == value_oid) else {
+        return Err(Error::AdhocError("The row is not selected in the cell."));
+    };
+    let moved_row_oid: i64 = linked_row_oids.remove(old_index);
+    linked_row_oids.insert(new_index.min(linked_row_oids.len()), moved_row_oid);
+
+    // Edit the cell as an undoable action
+    execute(
+        app,
+        Action::EditCellContents(cell::DataCellEntry::new(
+            table_oid,
+            column_oid,
+            row_oid,
+            cell::DataCellValue::Multiselect { linked_row_oid: linked_row_oids },
+        )),
+    )
+    .await
+}
+
+#[tauri::command]
+/// Lists the files attached to a cell of a File List column.
+pub fn list_cell_files(table_oid: i64, column_oid: i64, row_oid: i64) -> Result<Vec<cell::AttachedFile>, Error> {
+    cell::DataCellEntry::list_files(table_oid, column_oid, row_oid)
+}
+
+#[tauri::command]
+/// Attaches a file to the end of the list of files in a cell of a File List column.
+pub async fn add_file_to_cell(
+    app: AppHandle,
+    table_oid: i64,
+    column_oid: i64,
+    row_oid: i64,
+    file_oid: i64,
+) -> Result<(), Error> {
+    let conn = db::open()?;
+    let mut file_oids: Vec<i64> = cell::DataCellEntry::query_file_list_transact(&conn, table_oid, column_oid, row_oid)?;
+    if file_oids.contains(&file_oid) {
+        return Ok(());
+    }
+    file_oids.push(file_oid);
+
+    // Edit the cell as an undoable action
+    execute(
+        app,
+        Action::EditCellContents(cell::DataCellEntry::new(
+            table_oid,
+            column_oid,
+            row_oid,
+            cell::DataCellValue::FileList { file_oid: file_oids },
+        )),
+    )
+    .await
+}
+
+#[tauri::command]
+/// Detaches a file from a cell of a File List column.
+pub async fn remove_file_from_cell(
+    app: AppHandle,
+    table_oid: i64,
+    column_oid: i64,
+    row_oid: i64,
+    file_oid: i64,
+) -> Result<(), Error> {
+    let conn = db::open()?;
+    let mut file_oids: Vec<i64> = cell::DataCellEntry::query_file_list_transact(&conn, table_oid, column_oid, row_oid)?;
+    file_oids.retain(|attached_file_oid| *attached_file_oid != file_oid);
+
+    // Edit the cell as an undoable action
+    execute(
+        app,
+        Action::EditCellContents(cell::DataCellEntry::new(
+            table_oid,
+            column_oid,
+            row_oid,
+            cell::DataCellValue::FileList { file_oid: file_oids },
+        )),
+    )
+    .await
+}
+
+#[tauri::command]
+/// Creates a new table with the same structure as an existing table, but no rows.
+/// Returns the OID of the new table.
+pub async fn clone_table_schema(app: AppHandle, source_table_oid: i64, new_name: String) -> Result<i64, Error> {
+    execute(
+        app,
+        Action::CloneTableSchema {
+            source_table_oid,
+            new_name,
+        },
+    )
+    .await?;
+
+    // The action that undoes the clone trashes the new table
+    let reverse_stack = REVERSE_STACK.lock().unwrap();
+    match reverse_stack.last() {
+        Some(Action::TrashSchema(table_oid)) => Ok(*table_oid),
+        _ => Err(Error::AdhocError("Unable to find the cloned table.")),
+    }
+}
+
+#[tauri::command]
+/// Checks that a file is a DungeonDB database without opening it as the current file.
+pub fn probe_database(path: String) -> Result<db::DatabaseProbe, Error> {
+    db::probe_database(path)
+}
+
+#[tauri::command]
+/// Moves all changes from the write-ahead log into the database file, shrinking the log.
+pub fn checkpoint_wal() -> Result<db::WalCheckpoint, Error> {
+    db::checkpoint_wal()
+}
+
+#[tauri::command]
+/// Creates a named checkpoint that the database can later be rolled back to.
+pub fn create_checkpoint(name: String) -> Result<(), Error> {
+    let reverse_stack = REVERSE_STACK.lock().unwrap();
+    db::create_checkpoint(name, (*reverse_stack).len())
+}
+
+#[tauri::command]
+/// Rolls back the database to a named checkpoint.
+/// Any actions performed after the checkpoint are removed from the undo history.
+pub fn rollback_to_checkpoint(app: AppHandle, name: String) -> Result<(), Error> {
+    {
+        let mut reverse_stack = REVERSE_STACK.lock().unwrap();
+        let action_count: usize = db::rollback_to_checkpoint(name)?;
+        (*reverse_stack).truncate(action_count);
+
+        let mut forward_stack = FORWARD_STACK.lock().unwrap();
+        *forward_stack = Vec::new();
+
+        let mut has_unsaved_changes = HAS_UNSAVED_CHANGES.lock().unwrap();
+        *has_unsaved_changes = true;
+    }
+
+    // Reset the window
+    reset(&app)
+}
+
+#[tauri::command]
+/// Discards every change made since the database was last saved, along with the undo history.
+pub fn discard_session(app: AppHandle) -> Result<(), Error> {
+    db::discard_session()?;
+    column::set_validation_enabled(true);
+    view::clear_bulk_mode();
+    {
+        let mut reverse_stack = REVERSE_STACK.lock().unwrap();
+        *reverse_stack = Vec::new();
+
+        let mut forward_stack = FORWARD_STACK.lock().unwrap();
+        *forward_stack = Vec::new();
+
+        let mut has_unsaved_changes = HAS_UNSAVED_CHANGES.lock().unwrap();
+        *has_unsaved_changes = false;
+    }
+
+    // Reset the window
+    reset(&app)
+}
+
+#[tauri::command]
+/// Sets the largest file, in bytes, that will be displayed as an image or sent to the frontend as base64.
+/// Passing None restores the default limit.
+pub fn set_max_base64_file_size(max_size: Option<i64>) -> Result<(), Error> {
+    file::set_max_base64_file_size(max_size.unwrap_or(file::DEFAULT_MAX_BASE64_FILE_SIZE))
+}
+
+#[tauri::command]
+/// Sets the maximum number of Object columns that can be nested inside one another.
+/// Passing None restores the default maximum.
+pub fn set_max_object_nesting_depth(max_depth: Option<i64>) -> Result<(), Error> {
+    table::set_max_object_nesting_depth(max_depth.unwrap_or(table::DEFAULT_MAX_OBJECT_NESTING_DEPTH))
+}
+
+#[tauri::command]
+/// Enables or disables validation of values written to cells for the rest of the session.
+/// Disabling validation speeds up bulk operations, but can leave cells with invalid values until they are fixed by hand.
+/// When validation is enabled again, returns every cell that does not meet the constraints of its column.
+pub fn set_validation_enabled(enabled: bool) -> Result<Vec<column::InvalidCell>, Error> {
+    let was_enabled: bool = column::is_validation_enabled();
+    column::set_validation_enabled(enabled);
+    if enabled && !was_enabled {
+        column::find_invalid_cells(None)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+#[tauri::command]
+/// Puts a table into bulk mode, deferring the regeneration of its views, signals to update it, and checks for case-insensitive duplicates until bulk mode is ended.
+/// While in bulk mode, the displayed values and references of the table may be out of date.
+pub fn begin_bulk_mode(table_oid: i64) {
+    view::begin_bulk_mode(table_oid);
+}
+
+#[tauri::command]
+/// Takes a table out of bulk mode, regenerating its views and updating the table once.
+/// Returns every cell of the table that does not meet the constraints of its column, such as duplicates written while in bulk mode.
+pub fn end_bulk_mode(app: AppHandle, table_oid: i64) -> Result<Vec<column::InvalidCell>, Error> {
+    if view::end_bulk_mode(table_oid)? {
+        schema::FullMetadata::emit_affected_schema(&app, vec![table_oid])?;
+    }
+    column::find_invalid_cells(Some(table_oid))
+}
+
+#[tauri::command]
+/// Lists the names of all checkpoints, from oldest to newest.
+pub fn list_checkpoints() -> Vec<String> {
+    db::list_checkpoints()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoHistory {
+    /// Descriptions of the actions that would be performed by undo, most recent first.
+    pub undo: Vec<String>,
+    /// Descriptions of the actions that would be performed by redo, most recent first.
+    pub redo: Vec<String>,
+}
+
+#[tauri::command]
+/// Describes the actions on the undo and redo stacks, without changing either.
+pub fn get_undo_history() -> UndoHistory {
+    let reverse_stack = REVERSE_STACK.lock().unwrap();
+    let forward_stack = FORWARD_STACK.lock().unwrap();
+    UndoHistory {
+        undo: reverse_stack.iter().rev().map(|action| action.describe()).collect(),
+        redo: forward_stack.iter().rev().map(|action| action.describe()).collect(),
+    }
 }
 
 #[tauri::command]
@@ -715,6 +2100,7 @@ pub async fn undo(app: AppHandle) -> Result<(), Error> {
     } {
         Some(reverse_action) => {
             reverse_action.execute(&app, false).await?;
+            autosave_after_action(&app)?;
         }
         None => {}
     }
@@ -731,8 +2117,31 @@ pub async fn redo(app: AppHandle) -> Result<(), Error> {
     } {
         Some(forward_action) => {
             forward_action.execute(&app, true).await?;
+            autosave_after_action(&app)?;
         }
         None => {}
     }
     return Ok(());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purging_trash_clears_the_undo_history() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let row_oid: i64 = testing::insert_row(table_oid);
+        row::trash(table_oid, row_oid).unwrap();
+        *REVERSE_STACK.lock().unwrap() = vec![Action::UntrashRow { table_oid, row_oid, cascaded_rows: Vec::new() }];
+        *FORWARD_STACK.lock().unwrap() = vec![Action::TrashRow { table_oid, row_oid }];
+
+        set_trash_retention_days(Some(0.0)).unwrap();
+        let purged: Result<Vec<row::PurgedRows>, Error> = purge_expired_trash();
+        set_trash_retention_days(None).unwrap();
+        assert_eq!(purged.unwrap().len(), 1);
+        assert!(REVERSE_STACK.lock().unwrap().is_empty());
+        assert!(FORWARD_STACK.lock().unwrap().is_empty());
+    }
+}
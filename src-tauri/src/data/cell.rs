@@ -27,16 +27,28 @@ pub struct FailedValidation {
 pub enum RetrievalLimit {
     Page { num: i64, size: i64 },
     SingleRow,
+    /// The rows of a table whose OIDs are within a radius of the OID of a target row.
+    AroundRow { row_oid: i64, radius: i64 },
     None,
 }
 
 impl RetrievalLimit {
     /// Retrieves the LIMIT of the query.
-    pub fn get_size(&self) -> i64 {
+    /// Fails if the radius around a row is negative, or too large for the number of rows to be counted.
+    pub fn get_size(&self) -> Result<i64, Error> {
         match self {
-            Self::Page { size, .. } => size.clone(),
-            Self::SingleRow => 1,
-            Self::None => i64::MAX,
+            Self::Page { size, .. } => Ok(size.clone()),
+            Self::SingleRow => Ok(1),
+            Self::AroundRow { radius, .. } => {
+                if *radius < 0 {
+                    return Err(Error::AdhocError("The radius around a row cannot be negative."));
+                }
+                match radius.checked_mul(2).and_then(|r| r.checked_add(1)) {
+                    Some(size) => Ok(size),
+                    None => Err(Error::AdhocError("The radius around a row is too large.")),
+                }
+            }
+            Self::None => Ok(i64::MAX),
         }
     }
 }
@@ -78,7 +90,9 @@ pub enum CellTextFormat {
     Plain,
     Markdown,
     Json,
-    Xml
+    Xml,
+    Long,
+    Point
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -129,6 +143,7 @@ pub enum Cell {
         data_column_oid: i64,
         data_row_oid: i64,
         value: Option<i64>,
+        label: Option<String>,
 
         /// The list of dependencies that always have a 1-to-1 relationship with this cell.
         /// Whenever one of these dependencies is updated, only this cell needs to be updated.
@@ -162,6 +177,29 @@ pub enum Cell {
         validation_failures: Vec<FailedValidation>,
     },
 
+    /// Data cell. Represented by a text entry field, where the entered value is restricted to a percentage.
+    PercentageEntry {
+        cell_identifier: CellIdentifier,
+        data_table_oid: i64,
+        data_column_oid: i64,
+        data_row_oid: i64,
+        /// The stored fraction (e.g. 0.75).
+        value: Option<f64>,
+        /// The displayed percentage (e.g. "75%").
+        label: Option<String>,
+
+        /// The list of dependencies that always have a 1-to-1 relationship with this cell.
+        /// Whenever one of these dependencies is updated, only this cell needs to be updated.
+        isolated_cell_dependencies: Vec<CellDependency>,
+
+        /// The list of dependencies that have a *-to-1 relationship with this cell.
+        /// Whenever one of these dependencies is updated, the entire schema needs to be reloaded.
+        full_reload_cell_dependencies: Vec<CellDependency>,
+
+        /// The list of validation errors.
+        validation_failures: Vec<FailedValidation>,
+    },
+
     /// Data cell. Represented by a text entry field, where the entered value is restricted to a date.
     DateEntry {
         cell_identifier: CellIdentifier,
@@ -353,8 +391,38 @@ pub enum Cell {
 }
 
 pub const UPDATE_CELL_SIGNAL: &'static str = "cell";
+pub const UPDATE_ROW_SIGNAL: &'static str = "update-table-row";
+
+/// Identifies the cells of a table row that were updated together.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RowUpdate {
+    pub table_oid: i64,
+    pub row_oid: i64,
+    pub column_oids: Vec<i64>,
+}
 
 impl Cell {
+    /// Gets the number of validation errors on the cell.
+    pub fn count_validation_failures(&self) -> i64 {
+        match self {
+            Self::Readonly { validation_failures, .. }
+            | Self::TextEntry { validation_failures, .. }
+            | Self::IntegerEntry { validation_failures, .. }
+            | Self::NumberEntry { validation_failures, .. }
+            | Self::PercentageEntry { validation_failures, .. }
+            | Self::DateEntry { validation_failures, .. }
+            | Self::DatetimeEntry { validation_failures, .. }
+            | Self::CheckboxEntry { validation_failures, .. }
+            | Self::FileEntry { validation_failures, .. }
+            | Self::ImageEntry { validation_failures, .. }
+            | Self::SchemaLink { validation_failures, .. }
+            | Self::ObjectLink { validation_failures, .. }
+            | Self::SingleSelectDropdown { validation_failures, .. }
+            | Self::MultiSelectDropdown { validation_failures, .. } => validation_failures.len() as i64,
+        }
+    }
+
     /// Retrieve a particular cell.
     pub fn get(cell_identifier: CellIdentifier) -> Self {
         let conn = match db::open() {
@@ -666,6 +734,32 @@ impl Cell {
         let full_reload_cell_dependencies: Vec<CellDependency> = Vec::new();
 
         match prim {
+            column_type::Primitive::FileList
+            | column_type::Primitive::RowNumber => {
+                let (label, label_e) = match row.get::<&str, Option<String>>(&label_ord) {
+                    Ok(label) => (label, None),
+                    Err(e) => (None, Some(e))
+                };
+
+                // The attached files are managed outside of the cell, so only the number of files is displayed
+                // Row numbers are computed from the position of the row, so they cannot be edited either
+                Cell::Readonly {
+                    label,
+                    format: CellTextFormat::Plain,
+                    cell_identifier,
+                    isolated_cell_dependencies,
+                    full_reload_cell_dependencies,
+                    validation_failures: {
+                        if let Some(label_e) = label_e {
+                            vec![FailedValidation {
+                                message: format!("{label_e}")
+                            }]
+                        } else {
+                            Vec::new()
+                        }
+                    }
+                }
+            }
             column_type::Primitive::Boolean => {
                 let (is_checked, is_checked_e) = match row.get::<&str, Option<bool>>(&value_ord) {
                     Ok(is_checked) => (is_checked, None),
@@ -696,12 +790,17 @@ impl Cell {
                     Ok(value) => (value, None),
                     Err(e) => (None, Some(e))
                 };
+                let (label, label_e) = match row.get::<&str, Option<String>>(&label_ord) {
+                    Ok(label) => (label, None),
+                    Err(e) => (None, Some(e))
+                };
 
                 Cell::IntegerEntry {
                     data_table_oid: table_oid,
                     data_column_oid: column_oid,
                     data_row_oid: row_oid,
                     value,
+                    label,
                     cell_identifier,
                     isolated_cell_dependencies,
                     full_reload_cell_dependencies,
@@ -713,6 +812,11 @@ impl Cell {
                         } else {
                             Vec::new()
                         };
+                        if let Some(label_e) = label_e {
+                            failures.push(FailedValidation {
+                                message: format!("{label_e}")
+                            })
+                        }
                         failures
                     }
                 }
@@ -743,10 +847,48 @@ impl Cell {
                     }
                 }
             }
+            column_type::Primitive::Percentage => {
+                let (value, value_e) = match row.get::<&str, Option<f64>>(&value_ord) {
+                    Ok(value) => (value, None),
+                    Err(e) => (None, Some(e))
+                };
+                let (label, label_e) = match row.get::<&str, Option<String>>(&label_ord) {
+                    Ok(label) => (label, None),
+                    Err(e) => (None, Some(e))
+                };
+
+                Cell::PercentageEntry {
+                    data_table_oid: table_oid,
+                    data_column_oid: column_oid,
+                    data_row_oid: row_oid,
+                    value,
+                    label,
+                    cell_identifier,
+                    isolated_cell_dependencies,
+                    full_reload_cell_dependencies,
+                    validation_failures: {
+                        let mut failures: Vec<FailedValidation> = if let Some(value_e) = value_e {
+                            vec![FailedValidation {
+                                message: format!("{value_e}")
+                            }]
+                        } else {
+                            Vec::new()
+                        };
+                        if let Some(label_e) = label_e {
+                            failures.push(FailedValidation {
+                                message: format!("{label_e}")
+                            })
+                        }
+                        failures
+                    }
+                }
+            }
             column_type::Primitive::PlainText
             | column_type::Primitive::MarkdownText
+            | column_type::Primitive::LongText
             | column_type::Primitive::JsonText
-            | column_type::Primitive::XmlText => {
+            | column_type::Primitive::XmlText
+            | column_type::Primitive::Point => {
                 let (label, label_e) = match row.get::<&str, Option<String>>(&label_ord) {
                     Ok(label) => (label, None),
                     Err(e) => (None, Some(e))
@@ -759,6 +901,8 @@ impl Cell {
                     label,
                     format: match prim {
                         column_type::Primitive::JsonText => CellTextFormat::Json,
+                        column_type::Primitive::LongText => CellTextFormat::Long,
+                        column_type::Primitive::Point => CellTextFormat::Point,
                         _ => CellTextFormat::Plain
                     },
                     cell_identifier,
@@ -1326,6 +1470,7 @@ impl Cell {
                     | Self::IntegerEntry { cell_identifier: ci, .. }
                     | Self::MultiSelectDropdown { cell_identifier: ci, .. }
                     | Self::NumberEntry { cell_identifier: ci, .. }
+                    | Self::PercentageEntry { cell_identifier: ci, .. }
                     | Self::ObjectLink { cell_identifier: ci, .. }
                     | Self::Readonly { cell_identifier: ci, .. }
                     | Self::SchemaLink { cell_identifier: ci, .. }
@@ -1345,6 +1490,10 @@ impl Cell {
                         CellTextFormat::Xml
                     } else if param.starts_with(column_type::Primitive::MarkdownText.to_str()) {
                         CellTextFormat::Markdown  
+                    } else if param.starts_with(column_type::Primitive::LongText.to_str()) {
+                        CellTextFormat::Long
+                    } else if param.starts_with(column_type::Primitive::Point.to_str()) {
+                        CellTextFormat::Point
                     } else {
                         CellTextFormat::Plain
                     },
@@ -1368,6 +1517,22 @@ impl Cell {
     }
 
 
+    /// Retrieve the underlying value of a cell, rather than the text that is displayed for it.
+    /// For Select and Object columns, this is the OID of the referenced row.
+    pub fn get_true_value(table_oid: i64, column_oid: i64, row_oid: i64) -> Result<Option<String>, Error> {
+        let conn: Connection = db::open()?;
+        let sql_select: String = format!(
+            "SELECT CAST(COLUMN{column_oid}_VALUE AS TEXT) FROM SCHEMA{table_oid}_VIEW WHERE OID = ?1"
+        );
+        match conn
+            .query_one(&sql_select, params![row_oid], |row| row.get::<_, Option<String>>(0))
+            .optional()?
+        {
+            Some(value) => Ok(value),
+            None => Err(Error::AdhocError("Row does not exist in table.")),
+        }
+    }
+
     /// Emit signal to update cells related to the indicated cell.
     pub fn emit_affected_cells(app: &AppHandle, table_oid: i64, column_oid: i64, row_oid: i64) -> Result<(), Error> {
         app.emit(UPDATE_CELL_SIGNAL, CellIdentifier::DataCell { 
@@ -1377,6 +1542,16 @@ impl Cell {
         })?;
         Ok(())
     }
+
+    /// Sends a single signal to update several cells of the same row, plus any dependent cells.
+    pub fn emit_affected_row(app: &AppHandle, table_oid: i64, row_oid: i64, column_oids: Vec<i64>) -> Result<(), Error> {
+        app.emit(UPDATE_ROW_SIGNAL, RowUpdate {
+            table_oid,
+            row_oid,
+            column_oids
+        })?;
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -1397,12 +1572,24 @@ pub enum SchemaCellStream {
     /// Indicates the total number of rows in the schema, for purposes of pagination.
     MaxIndex(i64),
 
+    /// Indicates the index of the row that the retrieved rows are centered on, if it passes the filters.
+    TargetIndex(Option<i64>),
+
     /// Indicates the start of a new row in the schema.
     Row {
         index: i64,
         row_identifier: RowIdentifier,
         fixed_parent_datasource: Option<(i64, i64, column::FullMetadata)>,
         validation_failures: Vec<FailedValidation>,
+
+        /// True if any cell in the row has a validation error.
+        has_errors: bool,
+
+        /// The total number of validation errors across all cells in the row.
+        error_count: i64,
+
+        /// The creation and modification timestamps of the row, if the table tracks them.
+        timestamps: Option<row::RowTimestamps>,
     },
 
     /// A button to navigate to the previous page.
@@ -1423,16 +1610,24 @@ pub enum SchemaCellStream {
 
     /// A cell in the schema.
     Cell(Cell),
+
+    /// The styles of the conditional formatting rules met by a cell in the row that was last sent.
+    ConditionalStyle {
+        column_oid: i64,
+        styles: Vec<String>,
+    },
 }
 
 impl SchemaCellStream {
     /// Sends all cells on a page in a schema.
+    /// If visible column OIDs are given, only the cells of those columns are queried and sent.
     pub fn query_by_schema(
         mut column_sender: Sender<column::FullMetadata>,
         mut cell_sender: Sender<Self>,
         schema_oid: i64,
         filters: Vec<(String, i64)>,
         limit: RetrievalLimit,
+        visible_column_oids: Option<Vec<i64>>,
     ) -> Result<(), Error> {
         let conn: Connection = db::open()?;
 
@@ -1443,6 +1638,7 @@ impl SchemaCellStream {
             None => None,
         };
         let mut cols: Vec<(column::FullMetadata, String)> = Vec::new();
+        let mut format_rules: Vec<Vec<column::FormatRule>> = Vec::new();
         for row_result in conn.prepare("SELECT COLUMN_OID, DATASOURCE_PATH FROM METADATA_SCHEMA_COLUMN_VIEW WHERE IS_REQUIRED AND SCHEMA_OID = ?1 ORDER BY ORDERING")?.query_map(params![schema_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, String>("DATASOURCE_PATH")?)))? {
             let (column_oid, datasource_path) = row_result?;
             let column_metadata: column::FullMetadata = column::FullMetadata::get_transact(&conn, column_oid)?;
@@ -1450,16 +1646,29 @@ impl SchemaCellStream {
             // Send the column
             column_sender.send(column_metadata.clone())?;
 
-            // Add to the list of columns
+            // Add to the list of columns, unless it is hidden
+            if let Some(visible_column_oids) = &visible_column_oids {
+                if !visible_column_oids.contains(&column_oid) {
+                    continue;
+                }
+            }
+            format_rules.push(column::FullMetadata::get_format_rules_transact(&conn, column_oid)?);
             cols.push((column_metadata, datasource_path));
         }
 
-        // Page-level filter
+        // Page-level filter and selected columns
+        let mut select_exprs: Vec<String> = Vec::new();
         let where_expr: String = {
             let mut where_clauses: Vec<String> = Vec::new();
             let pragma_sql: String = format!("PRAGMA table_info(SCHEMA{schema_oid}_VIEW)");
             for column_result in conn.prepare(&pragma_sql)?.query_map([], |row| row.get("NAME"))? {
                 let column_name: String = column_result?;
+
+                // Only select the cell columns of visible columns, so that the subqueries of hidden columns are skipped
+                if !column_name.starts_with("COLUMN") || cols.iter().any(|(c, _)| column_name.starts_with(&format!("COLUMN{}_", c.oid))) {
+                    select_exprs.push(column_name.clone());
+                }
+
                 match filters.iter().find(|(filter_column_name, _)| *filter_column_name == column_name) {
                     Some((filter_column_name, filter_value)) => {
                         where_clauses.push(format!("{filter_column_name} = {filter_value}"));
@@ -1480,13 +1689,33 @@ impl SchemaCellStream {
             }
         };
 
+        // Select the timestamps of each row along with its cells, if the table tracks them
+        let track_timestamps: bool = match root_datasource_alias {
+            Some(_) => conn
+                .query_one(
+                    "SELECT TRACK_TIMESTAMPS FROM METADATA_TABLE WHERE OID = ?1",
+                    params![schema_oid],
+                    |row| row.get("TRACK_TIMESTAMPS"),
+                )
+                .optional()?
+                .unwrap_or(false),
+            None => false,
+        };
+        if track_timestamps {
+            for timestamp_column_name in ["CREATED_AT", "UPDATED_AT"] {
+                select_exprs.push(format!(
+                    "(SELECT STRFTIME('%FT%H:%M:%fZ', t.{timestamp_column_name}, 'julianday') FROM TABLE{schema_oid} t WHERE t.OID = SCHEMA{schema_oid}_VIEW.OID) AS {timestamp_column_name}"
+                ));
+            }
+        }
+
         // Row limits
         let limit_expr: String = match limit {
             RetrievalLimit::SingleRow => String::from("LIMIT 1"),
             RetrievalLimit::Page { num, size } => {
                 format!("LIMIT {size} OFFSET {}", size * (num - 1))
             }
-            RetrievalLimit::None => String::from(""),
+            RetrievalLimit::AroundRow { .. } | RetrievalLimit::None => String::from(""),
         };
 
         // Send over the MAX index, for purposes of determining page count
@@ -1501,9 +1730,30 @@ impl SchemaCellStream {
         cell_sender.send(Self::MaxIndex(max_index.clone()))?;
 
         // Query the cells of the schema
-        let cell_sql: String = format!(
-            "SELECT ROW_NUMBER() OVER (ORDER BY ROW_INDEX) AS QUERY_ROW_INDEX, * FROM SCHEMA{schema_oid}_VIEW {where_expr} ORDER BY ROW_INDEX {limit_expr}"            
+        let mut cell_sql: String = format!(
+            "SELECT ROW_NUMBER() OVER (ORDER BY ROW_INDEX) AS QUERY_ROW_INDEX, {} FROM SCHEMA{schema_oid}_VIEW {where_expr} ORDER BY ROW_INDEX {limit_expr}",
+            select_exprs.join(", ")
         );
+        if let RetrievalLimit::AroundRow { row_oid, radius } = limit {
+            if root_datasource_alias.is_none() {
+                return Err(Error::AdhocError("Only the rows of a table can be retrieved around a row."));
+            }
+
+            // Reject a radius that does not describe a range of rows
+            limit.get_size()?;
+
+            // Send over the index of the target row, so that it can be scrolled to
+            let target_sql: String = format!("SELECT QUERY_ROW_INDEX FROM ({cell_sql}) WHERE OID = ?1");
+            let target_index: Option<i64> = conn.query_one(&target_sql, params![row_oid], |row| row.get("QUERY_ROW_INDEX")).optional()?;
+            cell_sender.send(Self::TargetIndex(target_index))?;
+
+            // Only retrieve the rows with OIDs near the target row, keeping the index of each row within the entire table
+            cell_sql = format!(
+                "SELECT * FROM ({cell_sql}) WHERE OID BETWEEN {} AND {} ORDER BY QUERY_ROW_INDEX",
+                row_oid.saturating_sub(radius),
+                row_oid.saturating_add(radius)
+            );
+        }
         let mut stmt_query = conn.prepare(&cell_sql)?;
         let mut rows_query = stmt_query.query([])?;
         loop {
@@ -1521,20 +1771,15 @@ impl SchemaCellStream {
                 }
             };
 
-            // Send indicator that a new row has started
-            cell_sender.send(Self::Row {
-                index: index.clone(),
-                row_identifier: row_identifier.clone(),
-                fixed_parent_datasource: None, // TODO get fixed parent datasources
-                validation_failures: Vec::new(),
-            })?;
+            // Buffer the cells of the row, so that errors can be counted before the row is sent
+            let mut row_cells: Vec<Cell> = Vec::new();
 
             // Iterate over columns of schema
             for (c, datasource_path) in cols.iter() {
                 let value_ord: String = format!("COLUMN{}_VALUE", c.oid);
                 let label_ord: String = format!("COLUMN{}_LABEL", c.oid);
 
-                cell_sender.send(Self::Cell(match &c.column_type {
+                row_cells.push(match &c.column_type {
                     column_type::ColumnType::Primitive(prim) => {
                         let data_table_oid: i64 = c.schema.oid.clone();
                         let data_column_oid: i64 = c.oid.clone();
@@ -1649,7 +1894,52 @@ impl SchemaCellStream {
 
                         Cell::new_subreport(row, cell_identifier, value_ord, label_ord, link_schema_oid)
                     }
-                }))?;
+                });
+            }
+
+            // Send indicator that a new row has started
+            let error_count: i64 = row_cells.iter().map(|cell| cell.count_validation_failures()).sum();
+            cell_sender.send(Self::Row {
+                index: index.clone(),
+                row_identifier: row_identifier.clone(),
+                fixed_parent_datasource: None, // TODO get fixed parent datasources
+                validation_failures: Vec::new(),
+                has_errors: error_count > 0,
+                error_count,
+                timestamps: if track_timestamps {
+                    Some(row::RowTimestamps {
+                        created_at: row.get("CREATED_AT")?,
+                        updated_at: row.get("UPDATED_AT")?,
+                    })
+                } else {
+                    None
+                },
+            })?;
+
+            // Send the cells of the row
+            for cell in row_cells {
+                cell_sender.send(Self::Cell(cell))?;
+            }
+
+            // Send the styles of the conditional formatting rules met by each cell
+            for ((c, _), rules) in cols.iter().zip(format_rules.iter()) {
+                if rules.len() == 0 {
+                    continue;
+                }
+                let value_ord: String = format!("COLUMN{}_VALUE", c.oid);
+                let label_ord: String = format!("COLUMN{}_LABEL", c.oid);
+                let Ok(value) = row.get::<&str, rusqlite::types::Value>(&value_ord) else {
+                    continue;
+                };
+                let label: Option<String> = row.get::<&str, Option<String>>(&label_ord).unwrap_or(None);
+                let styles: Vec<String> = rules
+                    .iter()
+                    .filter(|rule| rule.condition.matches(&value, label.as_deref()))
+                    .map(|rule| rule.style.clone())
+                    .collect();
+                if styles.len() > 0 {
+                    cell_sender.send(Self::ConditionalStyle { column_oid: c.oid, styles })?;
+                }
             }
         }
 
@@ -1756,16 +2046,28 @@ pub enum DataCellValue {
     Text(Option<String>),
     Integer(Option<i64>),
     Number(Option<f64>),
+    /// A percentage, stored as the fraction that it represents and displayed with a percent sign.
+    /// A label ending in "%" is divided by 100, so "75%" is stored as 0.75.
+    /// A bare number is taken as the fraction itself, so "0.75" is also stored as 0.75, while "75" is stored as 75 and displayed as "7500%".
+    Percentage {
+        label: Option<String>,
+    },
     Date {
         label: Option<String>,
     },
     Datetime {
         label: Option<String>,
     },
+    Point {
+        label: Option<String>,
+    },
     Boolean(Option<bool>),
     File {
         file_oid: Option<i64>,
     },
+    FileList {
+        file_oid: Vec<i64>,
+    },
     Object {
         linked_row_oid: DataCellObjectBehavior,
     },
@@ -1777,6 +2079,137 @@ pub enum DataCellValue {
     },
 }
 
+impl DataCellValue {
+    /// Gets the value that leaves a cell of a column blank.
+    /// Object cells unlink the linked row and move it to the trash, while Multiselect and File List cells remove every selection or attached file.
+    pub fn null_for(column: &column::FullMetadata) -> Result<Self, Error> {
+        Ok(match &column.column_type {
+            column_type::ColumnType::Primitive(prim) => match prim {
+                column_type::Primitive::PlainText
+                | column_type::Primitive::MarkdownText
+                | column_type::Primitive::LongText
+                | column_type::Primitive::JsonText
+                | column_type::Primitive::XmlText => Self::Text(None),
+                column_type::Primitive::Integer => Self::Integer(None),
+                column_type::Primitive::Number => Self::Number(None),
+                column_type::Primitive::Percentage => Self::Percentage { label: None },
+                column_type::Primitive::Date => Self::Date { label: None },
+                column_type::Primitive::Datetime => Self::Datetime { label: None },
+                column_type::Primitive::Point => Self::Point { label: None },
+                column_type::Primitive::Boolean => Self::Boolean(None),
+                column_type::Primitive::File
+                | column_type::Primitive::Image => Self::File { file_oid: None },
+                column_type::Primitive::FileList => Self::FileList { file_oid: Vec::new() },
+                column_type::Primitive::RowNumber => {
+                    return Err(Error::AdhocError("Row numbers cannot be edited."));
+                }
+            },
+            column_type::ColumnType::Object { .. } => Self::Object {
+                linked_row_oid: DataCellObjectBehavior::Delete,
+            },
+            column_type::ColumnType::Select { .. } => Self::Select { linked_row_oid: None },
+            column_type::ColumnType::Multiselect { .. } => Self::Multiselect { linked_row_oid: Vec::new() },
+            column_type::ColumnType::Formula { .. } | column_type::ColumnType::Subreport { .. } => {
+                return Err(Error::AdhocError("The values of Formula and Subreport columns are computed, so they cannot be cleared."));
+            }
+        })
+    }
+
+    /// Parses a value for a cell of a column from its label, as it would be entered in the cell.
+    pub fn from_label(column: &column::FullMetadata, label: &str) -> Result<Self, Error> {
+        Ok(match &column.column_type {
+            column_type::ColumnType::Primitive(prim) => match prim {
+                column_type::Primitive::PlainText
+                | column_type::Primitive::MarkdownText
+                | column_type::Primitive::LongText
+                | column_type::Primitive::JsonText
+                | column_type::Primitive::XmlText => Self::Text(Some(String::from(label))),
+                column_type::Primitive::Integer => Self::Integer(column.integer_format.parse(label)?),
+                column_type::Primitive::Number => match f64::from_str(label.trim()) {
+                    Ok(value) => Self::Number(Some(value)),
+                    Err(_) => {
+                        return Err(Error::AdhocError("Unable to parse number."));
+                    }
+                },
+                column_type::Primitive::Percentage => Self::Percentage { label: Some(String::from(label)) },
+                column_type::Primitive::Date => Self::Date { label: Some(String::from(label)) },
+                column_type::Primitive::Datetime => Self::Datetime { label: Some(String::from(label)) },
+                column_type::Primitive::Point => Self::Point { label: Some(String::from(label)) },
+                column_type::Primitive::Boolean => match label.trim().to_lowercase().as_str() {
+                    "true" | "1" => Self::Boolean(Some(true)),
+                    "false" | "0" => Self::Boolean(Some(false)),
+                    _ => {
+                        return Err(Error::AdhocError("Unable to parse checkbox value."));
+                    }
+                },
+                column_type::Primitive::File
+                | column_type::Primitive::Image
+                | column_type::Primitive::FileList => {
+                    return Err(Error::AdhocError("Files cannot be entered as text."));
+                }
+                column_type::Primitive::RowNumber => {
+                    return Err(Error::AdhocError("Row numbers cannot be edited."));
+                }
+            },
+            column_type::ColumnType::Select { table_oid, .. } => Self::Select {
+                linked_row_oid: Some(row::find_by_label(*table_oid, label)?),
+            },
+            _ => {
+                return Err(Error::AdhocError("Values of this column cannot be entered as text."));
+            }
+        })
+    }
+}
+
+/// Parses a percentage into the fraction that it represents.
+/// Values ending in "%" are divided by 100 (e.g. "75%" becomes 0.75),
+/// while bare values are already the fraction that is stored (e.g. "0.75" stays 0.75, and "2" stays 2).
+fn parse_percentage(label: &str) -> Result<f64, Error> {
+    let label: &str = label.trim();
+    match label.strip_suffix('%') {
+        Some(percent) => match f64::from_str(percent.trim()) {
+            Ok(percent) => Ok(percent / 100.0),
+            Err(_) => Err(Error::AdhocError("Unable to parse percentage.")),
+        },
+        None => match f64::from_str(label) {
+            Ok(fraction) => Ok(fraction),
+            Err(_) => Err(Error::AdhocError("Unable to parse percentage.")),
+        },
+    }
+}
+
+/// Parses a geographic point entered as "latitude, longitude" into its coordinates.
+/// The coordinates may optionally be wrapped in parentheses or square brackets (e.g. "[45.5, -122.6]").
+pub fn parse_point(label: &str) -> Result<(f64, f64), Error> {
+    let label: &str = label.trim();
+    let label: &str = label
+        .strip_prefix('[')
+        .and_then(|l| l.strip_suffix(']'))
+        .or_else(|| label.strip_prefix('(').and_then(|l| l.strip_suffix(')')))
+        .unwrap_or(label);
+    let Some((lat, lng)) = label.split_once(',') else {
+        return Err(Error::AdhocError("A point must be entered as \"latitude, longitude\"."));
+    };
+    let (Ok(lat), Ok(lng)) = (f64::from_str(lat.trim()), f64::from_str(lng.trim())) else {
+        return Err(Error::AdhocError("Unable to parse the coordinates of the point."));
+    };
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(Error::AdhocError("Latitude must be between -90 and 90."));
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(Error::AdhocError("Longitude must be between -180 and 180."));
+    }
+    Ok((lat, lng))
+}
+
+/// A file attached to a cell of a File List column.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachedFile {
+    pub file_oid: i64,
+    pub label: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DataCellEntry {
@@ -1787,6 +2220,16 @@ pub struct DataCellEntry {
 }
 
 impl DataCellEntry {
+    /// Constructs an entry for the value of a cell.
+    pub fn new(table_oid: i64, column_oid: i64, row_oid: i64, value: DataCellValue) -> Self {
+        Self {
+            table_oid,
+            column_oid,
+            row_oid,
+            value,
+        }
+    }
+
     /// Retrieves all clipboard data from a given Object.
     pub fn get_object_data(table_oid: i64, row_oid: i64) -> Result<(i64, Vec<Self>), Error> {
         let conn = db::open()?;
@@ -1852,12 +2295,18 @@ impl DataCellEntry {
             let row_ord: String = format!("{root_datasource_alias}_{datasource_path}_OID");
             let row_oid: i64 = row.get::<&str, i64>(&row_ord)?;
 
+            // Row numbers are not stored, so there is nothing to copy
+            if col.column_type == column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) {
+                continue;
+            }
+
             data.push(Self {
                 value: match col.column_type {
                     column_type::ColumnType::Primitive(prim) => {
                         match prim {
                             column_type::Primitive::PlainText
                             | column_type::Primitive::MarkdownText
+                            | column_type::Primitive::LongText
                             | column_type::Primitive::JsonText
                             | column_type::Primitive::XmlText => DataCellValue::Text( 
                                 row.get::<&str, _>(&value_ord)?
@@ -1865,7 +2314,8 @@ impl DataCellEntry {
                             column_type::Primitive::Integer => DataCellValue::Integer( 
                                 row.get::<&str, _>(&value_ord)?
                             ),
-                            column_type::Primitive::Number => DataCellValue::Number(
+                            column_type::Primitive::Number
+                            | column_type::Primitive::Percentage => DataCellValue::Number(
                                 row.get::<&str, _>(&value_ord)?
                             ),
                             column_type::Primitive::Date => DataCellValue::Date { 
@@ -1874,13 +2324,20 @@ impl DataCellEntry {
                             column_type::Primitive::Datetime => DataCellValue::Datetime { 
                                 label: row.get::<&str, _>(&label_ord)?
                             },
+                            column_type::Primitive::Point => DataCellValue::Point { 
+                                label: row.get::<&str, _>(&label_ord)?
+                            },
                             column_type::Primitive::Boolean => DataCellValue::Boolean(
                                 row.get::<&str, _>(&value_ord)?
                             ),
                             column_type::Primitive::File
                             | column_type::Primitive::Image => DataCellValue::File { 
                                 file_oid: row.get::<&str, _>(&value_ord)?
-                            }
+                            },
+                            column_type::Primitive::FileList => DataCellValue::FileList {
+                                file_oid: Self::query_file_list_transact(&conn, col.schema.oid, col.oid, row_oid)?
+                            },
+                            column_type::Primitive::RowNumber => unreachable!("Row Number columns are skipped above")
                         }
                     }
                     column_type::ColumnType::Object { table_oid: object_table_oid, .. } => {
@@ -2003,6 +2460,82 @@ impl DataCellEntry {
         Ok(result)
     }
 
+    /// Sets the values of several cells in the same row in a single transaction.
+    /// Returns the previous values of the cells, in the reverse of the order they were set.
+    pub fn set_row(table_oid: i64, row_oid: i64, values: &Vec<(i64, DataCellValue)>) -> Result<Vec<(i64, DataCellValue)>, Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+
+        let mut old_values: Vec<(i64, DataCellValue)> = Vec::new();
+        for (column_oid, value) in values.iter() {
+            // Files and Objects are set through their own actions
+            match value {
+                DataCellValue::File { .. } | DataCellValue::FileList { .. } | DataCellValue::Object { .. } => {
+                    return Err(Error::AdhocError("File and Object cells cannot be set as part of a row."));
+                }
+                _ => {}
+            }
+
+            // Set the data for the cell transactionally
+            let old_cell: DataCellEntry = Self::new(table_oid, *column_oid, row_oid, value.clone()).set_transact(&trans)?;
+            old_values.push((*column_oid, old_cell.value));
+        }
+        old_values.reverse();
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(old_values)
+    }
+
+    /// Gets the files attached to a cell of a File List column, in order.
+    pub fn list_files(table_oid: i64, column_oid: i64, row_oid: i64) -> Result<Vec<AttachedFile>, Error> {
+        let conn = db::open()?;
+        let sql_select: String = format!(
+            "
+            SELECT l.FILE_OID, f.LABEL 
+            FROM FILELIST{column_oid} l 
+            LEFT JOIN METADATA_FILE_VIEW f ON f.OID = l.FILE_OID 
+            WHERE l.TABLE{table_oid}_OID = ?1 
+            ORDER BY l.ORDERING, l.FILE_OID
+            "
+        );
+        let mut files: Vec<AttachedFile> = Vec::new();
+        for row_result in conn.prepare(&sql_select)?.query_map(params![row_oid], |row| {
+            Ok(AttachedFile {
+                file_oid: row.get("FILE_OID")?,
+                label: row.get("LABEL")?,
+            })
+        })? {
+            files.push(row_result?);
+        }
+        Ok(files)
+    }
+
+    /// Gets the OIDs of the files attached to a cell of a File List column, in order.
+    pub fn query_file_list_transact(conn: &Connection, table_oid: i64, column_oid: i64, row_oid: i64) -> Result<Vec<i64>, Error> {
+        let sql_select: String = format!(
+            "SELECT FILE_OID FROM FILELIST{column_oid} WHERE TABLE{table_oid}_OID = ?1 ORDER BY ORDERING, FILE_OID"
+        );
+        let mut file_oids: Vec<i64> = Vec::new();
+        for row_result in conn.prepare(&sql_select)?.query_map(params![row_oid], |row| row.get::<_, i64>("FILE_OID"))? {
+            file_oids.push(row_result?);
+        }
+        Ok(file_oids)
+    }
+
+    /// Gets the OIDs of the rows selected in a cell of a Multiselect column, in the order that they were selected.
+    pub fn query_multiselect_transact(conn: &Connection, table_oid: i64, column_oid: i64, row_oid: i64) -> Result<Vec<i64>, Error> {
+        let multiselect_table_oid: i64 = conn.query_one("SELECT typ.TABLE_OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__MULTISELECT typ ON c.TYPE_OID = typ.OID WHERE c.OID = ?1", params![column_oid], |row| row.get("TABLE_OID"))?;
+        let sql_select: String = format!(
+            "SELECT TABLE{multiselect_table_oid}_OID AS VALUE FROM MULTISELECT{column_oid} WHERE TABLE{table_oid}_OID = ?1 ORDER BY ORDERING"
+        );
+        let mut linked_row_oids: Vec<i64> = Vec::new();
+        for row_result in conn.prepare(&sql_select)?.query_map(params![row_oid], |row| row.get::<_, i64>("VALUE"))? {
+            linked_row_oids.push(row_result?);
+        }
+        Ok(linked_row_oids)
+    }
+
     /// Sets the value of a data cell.
     pub fn set_transact(&self, trans: &Transaction) -> Result<DataCellEntry, Error> {
         let old_value: DataCellValue = match &self.value {
@@ -2015,6 +2548,26 @@ impl DataCellEntry {
                 let old_value: Option<String> =
                     trans.query_one(&sql_get, params![self.row_oid], |row| row.get("VALUE"))?;
 
+                // Store an empty value as NULL, unless the column opts out
+                let mut value: Option<&str> = value.as_deref();
+                if value == Some("")
+                    && column::FullMetadata::is_empty_as_null_transact(&trans, self.column_oid)?
+                {
+                    value = None;
+                }
+
+                // Reject the new value if it duplicates another row in a case-insensitive unique column, or is malformed JSON in a pretty-printed column
+                if let Some(value) = value {
+                    column::FullMetadata::check_json_transact(&trans, self.column_oid, value)?;
+                    column::FullMetadata::check_case_insensitive_unique_transact(
+                        &trans,
+                        self.column_oid,
+                        self.table_oid,
+                        self.row_oid,
+                        value,
+                    )?;
+                }
+
                 // Update with the new value
                 let sql_update: String = format!(
                     "UPDATE TABLE{} SET COLUMN{} = ?1 WHERE OID = ?2",
@@ -2066,6 +2619,11 @@ impl DataCellEntry {
             DataCellValue::Select {
                 linked_row_oid: value,
             } => {
+                // Verify that the referenced row belongs to the subtype allowed by the column
+                if let Some(value) = value {
+                    column::FullMetadata::check_reference_subtype_transact(trans, self.column_oid, &[*value])?;
+                }
+
                 // Store the old value
                 let sql_get: String = format!(
                     "SELECT COLUMN{} AS VALUE FROM TABLE{} WHERE OID = ?1",
@@ -2105,6 +2663,55 @@ impl DataCellEntry {
                 // Return the old value
                 DataCellValue::Number(old_value)
             }
+            DataCellValue::Percentage { label } => {
+                // Store the old value
+                let sql_get: String = format!(
+                    "SELECT COLUMN{} AS VALUE FROM TABLE{} WHERE OID = ?1",
+                    self.column_oid, self.table_oid
+                );
+                let old_value: Option<f64> =
+                    trans.query_one(&sql_get, params![self.row_oid], |row| row.get("VALUE"))?;
+
+                // Update with the new value, normalized to a fraction
+                let value: Option<f64> = match label {
+                    Some(label) => Some(parse_percentage(label)?),
+                    None => None,
+                };
+                let sql_update: String = format!(
+                    "UPDATE TABLE{} SET COLUMN{} = ?1 WHERE OID = ?2",
+                    self.table_oid, self.column_oid
+                );
+                trans.execute(&sql_update, params![value, self.row_oid])?;
+
+                // Return the old value
+                DataCellValue::Number(old_value)
+            }
+            DataCellValue::Point { label } => {
+                // Store the old value
+                let sql_get: String = format!(
+                    "SELECT COLUMN{} AS VALUE FROM TABLE{} WHERE OID = ?1",
+                    self.column_oid, self.table_oid
+                );
+                let old_value: Option<String> =
+                    trans.query_one(&sql_get, params![self.row_oid], |row| row.get("VALUE"))?;
+
+                // Update with the new value, stored as a JSON pair of [latitude, longitude]
+                let value: Option<String> = match label {
+                    Some(label) => {
+                        let (lat, lng) = parse_point(label)?;
+                        Some(format!("[{lat},{lng}]"))
+                    }
+                    None => None,
+                };
+                let sql_update: String = format!(
+                    "UPDATE TABLE{} SET COLUMN{} = ?1 WHERE OID = ?2",
+                    self.table_oid, self.column_oid
+                );
+                trans.execute(&sql_update, params![value, self.row_oid])?;
+
+                // Return the old value
+                DataCellValue::Point { label: old_value }
+            }
             DataCellValue::Date { label } => {
                 // Store the old value
                 let sql_get: String = format!(
@@ -2126,7 +2733,7 @@ impl DataCellEntry {
             }
             DataCellValue::Datetime { label } => {
                 // Store the old value
-                let sql_get: String = format!("SELECT STRFTIME('%FT%TZ', COLUMN{}, 'julianday') AS VALUE FROM TABLE{} WHERE OID = ?1", self.column_oid, self.table_oid);
+                let sql_get: String = format!("SELECT STRFTIME('%FT%H:%M:%fZ', COLUMN{}, 'julianday') AS VALUE FROM TABLE{} WHERE OID = ?1", self.column_oid, self.table_oid);
                 let old_label: Option<String> =
                     trans.query_one(&sql_get, params![self.row_oid], |row| row.get("VALUE"))?;
 
@@ -2162,6 +2769,29 @@ impl DataCellEntry {
                     file_oid: old_value,
                 }
             }
+            DataCellValue::FileList { file_oid } => {
+                // Store the old value
+                let old_value: Vec<i64> = Self::query_file_list_transact(trans, self.table_oid, self.column_oid, self.row_oid)?;
+
+                // Replace the attached files, keeping the order in which they were given
+                let sql_delete: String = format!(
+                    "DELETE FROM FILELIST{} WHERE TABLE{}_OID = ?1",
+                    self.column_oid, self.table_oid
+                );
+                trans.execute(&sql_delete, params![self.row_oid])?;
+                let sql_insert: String = format!(
+                    "INSERT OR IGNORE INTO FILELIST{} (TABLE{}_OID, FILE_OID, ORDERING) VALUES (?1, ?2, ?3)",
+                    self.column_oid, self.table_oid
+                );
+                for (ordering, attached_file_oid) in file_oid.iter().enumerate() {
+                    trans.execute(&sql_insert, params![self.row_oid, attached_file_oid, ordering as i64])?;
+                }
+
+                // Return the old value
+                DataCellValue::FileList {
+                    file_oid: old_value,
+                }
+            }
             DataCellValue::Object {
                 linked_row_oid: value,
             } => {
@@ -2179,11 +2809,13 @@ impl DataCellEntry {
                 if let Some(old_value) = old_value {
                     // Trash the old Object value
                     let mut completed_table_oid: HashSet<i64> = HashSet::new();
+                    let mut cascaded_rows: Vec<(i64, i64)> = Vec::new();
                     row::trash_transact(
                         &trans,
                         object_table_oid,
                         old_value,
                         &mut completed_table_oid,
+                        &mut cascaded_rows,
                     )?;
                 }
 
@@ -2268,8 +2900,11 @@ impl DataCellEntry {
                 // Get the table OID of the Multiselect column
                 let multiselect_table_oid: i64 = trans.query_one("SELECT typ.TABLE_OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__MULTISELECT typ ON c.TYPE_OID = typ.OID WHERE c.OID = ?1", params![self.column_oid], |row| row.get("TABLE_OID"))?;
 
+                // Verify that the referenced rows belong to the subtype allowed by the column
+                column::FullMetadata::check_reference_subtype_transact(trans, self.column_oid, linked_row_oid)?;
+
                 // Store the old value
-                let sql_get: String = format!("SELECT TABLE{multiselect_table_oid}_OID AS VALUE FROM MULTISELECT{} WHERE TABLE{}_OID = ?1", self.column_oid, self.table_oid);
+                let sql_get: String = format!("SELECT TABLE{multiselect_table_oid}_OID AS VALUE FROM MULTISELECT{} WHERE TABLE{}_OID = ?1 ORDER BY ORDERING", self.column_oid, self.table_oid);
                 let mut old_value: Vec<i64> = Vec::new();
                 for row_result in trans
                     .prepare(&sql_get)?
@@ -2297,14 +2932,14 @@ impl DataCellEntry {
                     ],
                 )?;
 
-                // Insert the selected rows
+                // Insert the selected rows, in the order that they were selected
                 let sql_insert: String = format!(
-                    "INSERT OR IGNORE INTO MULTISELECT{} (TABLE{}_OID, TABLE{multiselect_table_oid}_OID) VALUES (?1, ?2)",
+                    "INSERT INTO MULTISELECT{} (TABLE{}_OID, TABLE{multiselect_table_oid}_OID, ORDERING) VALUES (?1, ?2, ?3) ON CONFLICT DO UPDATE SET ORDERING = excluded.ORDERING",
                     self.column_oid,
                     self.table_oid
                 );
-                for selected_oid in linked_row_oid.iter() {
-                    trans.execute(&sql_insert, params![self.row_oid, selected_oid])?;
+                for (ordering, selected_oid) in linked_row_oid.iter().enumerate() {
+                    trans.execute(&sql_insert, params![self.row_oid, selected_oid, ordering as i64])?;
                 }
 
                 // Return the old value
@@ -2314,6 +2949,9 @@ impl DataCellEntry {
             }
         };
 
+        // Record the modification time of the row
+        row::touch_transact(trans, self.table_oid, self.row_oid, false)?;
+
         Ok(DataCellEntry {
             table_oid: self.table_oid.clone(),
             column_oid: self.column_oid.clone(),
@@ -2322,3 +2960,204 @@ impl DataCellEntry {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::testing;
+
+    #[test]
+    fn parse_percentage_divides_only_values_with_a_percent_sign() {
+        assert_eq!(parse_percentage("75%").unwrap(), 0.75);
+        assert_eq!(parse_percentage(" 150 % ").unwrap(), 1.5);
+        assert_eq!(parse_percentage("0.5").unwrap(), 0.5);
+        assert_eq!(parse_percentage("1").unwrap(), 1.0);
+        assert_eq!(parse_percentage("2").unwrap(), 2.0);
+        assert!(parse_percentage("abc").is_err());
+        assert!(parse_percentage("abc%").is_err());
+    }
+
+    #[test]
+    fn percentage_cells_store_the_parsed_fraction() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Rate", column_type::ColumnType::Primitive(column_type::Primitive::Percentage));
+        let row_oid: i64 = testing::insert_row(table_oid);
+
+        let sql: String = format!("SELECT COLUMN{column_oid} FROM TABLE{table_oid} WHERE OID = {row_oid}");
+        testing::set_cell(table_oid, column_oid, row_oid, DataCellValue::Percentage { label: Some(String::from("75%")) });
+        assert_eq!(testing::query_one::<f64>(&sql), 0.75);
+        testing::set_cell(table_oid, column_oid, row_oid, DataCellValue::Percentage { label: Some(String::from("2")) });
+        assert_eq!(testing::query_one::<f64>(&sql), 2.0);
+    }
+
+    #[test]
+    fn percentage_cells_display_the_fraction_entered_as_a_percentage() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Rate", column_type::ColumnType::Primitive(column_type::Primitive::Percentage));
+        let row_oid: i64 = testing::insert_row(table_oid);
+
+        let value_sql: String = format!("SELECT COLUMN{column_oid} FROM TABLE{table_oid} WHERE OID = {row_oid}");
+        let label_sql: String = format!("SELECT COLUMN{column_oid}_LABEL FROM SCHEMA{table_oid}_VIEW WHERE OID = {row_oid}");
+        for (entered, stored, displayed) in [("75%", 0.75, "75%"), ("0.75", 0.75, "75%"), ("75", 75.0, "7500%")] {
+            testing::set_cell(table_oid, column_oid, row_oid, DataCellValue::Percentage { label: Some(String::from(entered)) });
+            assert_eq!(testing::query_one::<f64>(&value_sql), stored, "stored value of {entered}");
+            assert_eq!(testing::query_one::<String>(&label_sql), displayed, "label of {entered}");
+        }
+    }
+
+    #[test]
+    fn converting_numbers_to_percentages_keeps_the_stored_fraction() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let mut column = testing::column_metadata(table_oid, "Rate", column_type::ColumnType::Primitive(column_type::Primitive::Number));
+        column.create().unwrap();
+        let column_oid: i64 = column.oid;
+        let first_row_oid: i64 = testing::insert_row(table_oid);
+        let second_row_oid: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, column_oid, first_row_oid, DataCellValue::Number(Some(1.0)));
+        testing::set_cell(table_oid, column_oid, second_row_oid, DataCellValue::Number(Some(2.0)));
+
+        column.column_type = column_type::ColumnType::Primitive(column_type::Primitive::Percentage);
+        column.set().unwrap();
+        assert_eq!(testing::query_one::<f64>(&format!("SELECT COLUMN{column_oid} FROM TABLE{table_oid} WHERE OID = {first_row_oid}")), 1.0);
+        assert_eq!(testing::query_one::<f64>(&format!("SELECT COLUMN{column_oid} FROM TABLE{table_oid} WHERE OID = {second_row_oid}")), 2.0);
+    }
+
+    #[test]
+    fn empty_text_is_stored_as_null_unless_the_column_opts_out() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let null_column_oid: i64 = testing::create_column(table_oid, "Empty As Null", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let mut keep_column = testing::column_metadata(table_oid, "Empty Kept", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        keep_column.empty_as_null = false;
+        keep_column.create().unwrap();
+        let row_oid: i64 = testing::insert_row(table_oid);
+
+        testing::set_cell(table_oid, null_column_oid, row_oid, DataCellValue::Text(Some(String::new())));
+        testing::set_cell(table_oid, keep_column.oid, row_oid, DataCellValue::Text(Some(String::new())));
+        let stored_null: Option<String> = testing::query_one(&format!("SELECT COLUMN{null_column_oid} FROM TABLE{table_oid} WHERE OID = {row_oid}"));
+        let stored_kept: Option<String> = testing::query_one(&format!("SELECT COLUMN{} FROM TABLE{table_oid} WHERE OID = {row_oid}", keep_column.oid));
+        assert_eq!(stored_null, None);
+        assert_eq!(stored_kept, Some(String::new()));
+    }
+
+    #[test]
+    fn datetimes_keep_milliseconds_through_write_and_read() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Log", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Logged At", column_type::ColumnType::Primitive(column_type::Primitive::Datetime));
+        let row_oid: i64 = testing::insert_row(table_oid);
+
+        testing::set_cell(table_oid, column_oid, row_oid, DataCellValue::Datetime { label: Some(String::from("2024-01-02T03:04:05.123Z")) });
+        let label: String = testing::query_one(&format!("SELECT COLUMN{column_oid}_LABEL FROM SCHEMA{table_oid}_VIEW WHERE OID = {row_oid}"));
+        assert_eq!(label, "2024-01-02T03:04:05.123Z");
+
+        // The previous value returned when the cell is overwritten keeps its milliseconds as well
+        let old_entry: DataCellEntry = testing::set_cell(table_oid, column_oid, row_oid, DataCellValue::Datetime { label: None });
+        assert!(matches!(old_entry.value, DataCellValue::Datetime { label: Some(old_label) } if old_label == "2024-01-02T03:04:05.123Z"));
+    }
+
+    #[test]
+    fn clearing_an_object_cell_trashes_the_linked_row() {
+        let _db = testing::open_database();
+        let detail_table_oid: i64 = testing::create_table("Detail", &[]);
+        let item_table_oid: i64 = testing::create_table("Item", &[]);
+        let mut column: column::FullMetadata = testing::column_metadata(
+            item_table_oid,
+            "Detail",
+            column_type::ColumnType::Object { oid: 0, table_oid: detail_table_oid },
+        );
+        column.create().unwrap();
+        let item_row_oid: i64 = testing::insert_row(item_table_oid);
+        testing::set_cell(item_table_oid, column.oid, item_row_oid, DataCellValue::Object { linked_row_oid: DataCellObjectBehavior::New });
+        let detail_row_oid: i64 = testing::query_one(&format!("SELECT COLUMN{} FROM TABLE{item_table_oid} WHERE OID = {item_row_oid}", column.oid));
+
+        let old_cell: DataCellEntry = testing::set_cell(item_table_oid, column.oid, item_row_oid, DataCellValue::null_for(&column).unwrap());
+        assert_eq!(testing::query_one::<Option<i64>>(&format!("SELECT COLUMN{} FROM TABLE{item_table_oid} WHERE OID = {item_row_oid}", column.oid)), None);
+        assert!(testing::query_one::<bool>(&format!("SELECT TRASH FROM TABLE{detail_table_oid} WHERE OID = {detail_row_oid}")));
+
+        // Restoring the previous contents links and restores the row again
+        old_cell.set().unwrap();
+        assert_eq!(testing::query_one::<Option<i64>>(&format!("SELECT COLUMN{} FROM TABLE{item_table_oid} WHERE OID = {item_row_oid}", column.oid)), Some(detail_row_oid));
+        assert!(!testing::query_one::<bool>(&format!("SELECT TRASH FROM TABLE{detail_table_oid} WHERE OID = {detail_row_oid}")));
+    }
+
+    #[test]
+    fn clearing_a_multiselect_cell_removes_every_selection() {
+        let _db = testing::open_database();
+        let tag_table_oid: i64 = testing::create_table("Tag", &[]);
+        let item_table_oid: i64 = testing::create_table("Item", &[]);
+        let mut column: column::FullMetadata = testing::column_metadata(
+            item_table_oid,
+            "Tags",
+            column_type::ColumnType::Multiselect { oid: 0, table_oid: tag_table_oid },
+        );
+        column.create().unwrap();
+        let tag_row_oids: Vec<i64> = (0..2).map(|_| testing::insert_row(tag_table_oid)).collect();
+        let item_row_oid: i64 = testing::insert_row(item_table_oid);
+        testing::set_cell(item_table_oid, column.oid, item_row_oid, DataCellValue::Multiselect { linked_row_oid: tag_row_oids.clone() });
+
+        let old_cell: DataCellEntry = testing::set_cell(item_table_oid, column.oid, item_row_oid, DataCellValue::null_for(&column).unwrap());
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM MULTISELECT{}", column.oid)), 0);
+        for tag_row_oid in tag_row_oids.iter() {
+            assert!(!testing::query_one::<bool>(&format!("SELECT TRASH FROM TABLE{tag_table_oid} WHERE OID = {tag_row_oid}")));
+        }
+
+        // Restoring the previous contents brings back the selections
+        old_cell.set().unwrap();
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM MULTISELECT{}", column.oid)), 2);
+    }
+
+    #[test]
+    fn rows_around_a_row_need_a_radius_that_can_be_counted() {
+        assert_eq!(RetrievalLimit::AroundRow { row_oid: 1, radius: 5 }.get_size().unwrap(), 11);
+        assert!(RetrievalLimit::AroundRow { row_oid: 1, radius: -1 }.get_size().is_err());
+        assert!(RetrievalLimit::AroundRow { row_oid: 1, radius: i64::MAX }.get_size().is_err());
+    }
+
+    #[test]
+    fn streamed_rows_carry_the_timestamps_of_the_row() {
+        let _db = testing::open_database();
+        let mut metadata: table::FullMetadata = table::FullMetadata {
+            schema: schema::FullMetadata {
+                oid: 0,
+                name: String::from("Log"),
+                master_schema_oids: HashSet::new(),
+                order_by_column_oids: Vec::new(),
+                order_by_nulls_last: HashMap::new(),
+            },
+            track_timestamps: true,
+            default_page_size: None,
+        };
+        metadata.create().unwrap();
+        let table_oid: i64 = metadata.schema.oid;
+        let untimed_table_oid: i64 = testing::create_table("Note", &[]);
+        let row_oid: i64 = testing::insert_row(table_oid);
+        testing::insert_row(untimed_table_oid);
+
+        let stream_timestamps = |schema_oid: i64| -> Vec<Option<(Option<String>, Option<String>)>> {
+            let mut streamed: Vec<Option<(Option<String>, Option<String>)>> = Vec::new();
+            SchemaCellStream::query_by_schema(
+                Sender::Dummy,
+                Sender::Callback(Box::new(|cell: SchemaCellStream| {
+                    if let SchemaCellStream::Row { timestamps, .. } = cell {
+                        streamed.push(timestamps.map(|t| (t.created_at, t.updated_at)));
+                    }
+                    Ok(())
+                })),
+                schema_oid,
+                Vec::new(),
+                RetrievalLimit::None,
+                None,
+            )
+            .unwrap();
+            streamed
+        };
+        let timestamps: row::RowTimestamps = row::get_timestamps(table_oid, row_oid).unwrap().unwrap();
+        assert!(timestamps.created_at.is_some());
+        assert_eq!(stream_timestamps(table_oid), vec![Some((timestamps.created_at, timestamps.updated_at))]);
+        assert_eq!(stream_timestamps(untimed_table_oid), vec![None]);
+    }
+}
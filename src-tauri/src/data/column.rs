@@ -1,13 +1,129 @@
+use crate::data::cell;
 use crate::data::column_type;
 use crate::data::schema;
-use crate::data::view::regenerate_schema_views;
+use crate::data::table;
+use crate::data::view::{self, regenerate_schema_views};
 use crate::util::channel::Sender;
 use crate::util::db;
 use crate::util::error::Error;
+use crate::util::formula::Formula;
 use rusqlite::OptionalExtension;
 use rusqlite::{params, Connection, Transaction};
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
+use std::num::IntErrorKind;
+use std::sync::Mutex;
+
+/// The longest display length reported for a column, so that a single long value cannot make the column unreasonably wide.
+const MAX_DISPLAY_LENGTH_CAP: i64 = 200;
+
+/// Whether values written to cells are validated against the constraints of their column.
+static VALIDATION_ENABLED: Mutex<bool> = Mutex::new(true);
+
+/// Enables or disables validation of values written to cells, so that bulk operations are not slowed down by checking every write.
+/// While validation is disabled, cells can be left with duplicate values, malformed JSON, or references outside of the allowed subtype.
+/// Validation is enabled again whenever a database is opened.
+/// Cells written while validation was disabled are not checked again when it is enabled; use find_invalid_cells to find the ones that fail.
+pub fn set_validation_enabled(enabled: bool) {
+    let mut validation_enabled = VALIDATION_ENABLED.lock().unwrap();
+    *validation_enabled = enabled;
+}
+
+/// Checks whether values written to cells are validated.
+pub fn is_validation_enabled() -> bool {
+    *VALIDATION_ENABLED.lock().unwrap()
+}
+
+/// A cell whose value does not meet a constraint of its column.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidCell {
+    pub table_oid: i64,
+    pub column_oid: i64,
+    pub row_oid: i64,
+    pub message: String,
+}
+
+/// Finds every cell that would be rejected if it were written with validation enabled, in one table or in every table.
+pub fn find_invalid_cells(table_oid: Option<i64>) -> Result<Vec<InvalidCell>, Error> {
+    let conn = db::open()?;
+
+    // Find the columns of tables that have a constraint checked when their cells are written
+    let mut column_oids: Vec<i64> = Vec::new();
+    for row_result in conn
+        .prepare(
+            "
+            SELECT c.OID 
+            FROM METADATA_COLUMN_VIEW c 
+            INNER JOIN METADATA_TABLE t ON t.OID = c.SCHEMA_OID 
+            WHERE (c.CASE_INSENSITIVE_UNIQUE OR c.PRETTY_PRINT_JSON OR c.REFERENCE_SUBTYPE_FILTER IS NOT NULL) 
+                AND (?1 IS NULL OR c.SCHEMA_OID = ?1) 
+            ORDER BY c.SCHEMA_OID, c.ORDERING
+            ",
+        )?
+        .query_map(params![table_oid], |row| row.get::<_, i64>("OID"))?
+    {
+        column_oids.push(row_result?);
+    }
+
+    let mut invalid_cells: Vec<InvalidCell> = Vec::new();
+    for column_oid in column_oids {
+        let column: FullMetadata = FullMetadata::get_transact(&conn, column_oid)?;
+        let table_oid: i64 = column.schema.oid;
+        let mut checks: Vec<(String, &'static str)> = Vec::new();
+
+        // Text values that match another row, ignoring case
+        if column.case_insensitive_unique {
+            checks.push((
+                format!(
+                    "
+                    SELECT t.OID FROM TABLE{table_oid} t 
+                    WHERE NOT t.TRASH AND t.COLUMN{column_oid} IS NOT NULL 
+                        AND EXISTS (SELECT 1 FROM TABLE{table_oid} t2 WHERE NOT t2.TRASH AND t2.OID <> t.OID AND LOWER(t2.COLUMN{column_oid}) = LOWER(t.COLUMN{column_oid}))
+                    ORDER BY t.OID
+                    "
+                ),
+                "The value already exists in another row, ignoring case.",
+            ));
+        }
+
+        // Malformed JSON in a pretty-printed column
+        if column.pretty_print_json {
+            checks.push((
+                format!("SELECT OID FROM TABLE{table_oid} WHERE NOT TRASH AND COLUMN{column_oid} IS NOT NULL AND NOT json_valid(COLUMN{column_oid}) ORDER BY OID"),
+                "The value is not valid JSON.",
+            ));
+        }
+
+        // References to rows outside of the allowed subtype
+        if let Some(filter_sql) = FullMetadata::get_reference_subtype_filter_sql_transact(&conn, column_oid)? {
+            let sql_select: String = match &column.column_type {
+                column_type::ColumnType::Multiselect { table_oid: referenced_table_oid, .. } => format!(
+                    "
+                    SELECT DISTINCT t.OID FROM TABLE{table_oid} t 
+                    INNER JOIN MULTISELECT{column_oid} m ON m.TABLE{table_oid}_OID = t.OID 
+                    WHERE NOT t.TRASH AND m.TABLE{referenced_table_oid}_OID NOT IN ({filter_sql}) 
+                    ORDER BY t.OID
+                    "
+                ),
+                _ => format!("SELECT OID FROM TABLE{table_oid} WHERE NOT TRASH AND COLUMN{column_oid} IS NOT NULL AND COLUMN{column_oid} NOT IN ({filter_sql}) ORDER BY OID"),
+            };
+            checks.push((sql_select, "The referenced row does not belong to the subtype allowed by the column."));
+        }
+
+        for (sql_select, message) in checks {
+            for row_result in conn.prepare(&sql_select)?.query_map([], |row| row.get::<_, i64>(0))? {
+                invalid_cells.push(InvalidCell {
+                    table_oid,
+                    column_oid,
+                    row_oid: row_result?,
+                    message: String::from(message),
+                });
+            }
+        }
+    }
+    Ok(invalid_cells)
+}
 
 #[derive(Serialize, Clone)]
 pub struct DropdownValue {
@@ -15,6 +131,272 @@ pub struct DropdownValue {
     pub value: i64,
 }
 
+/// Identifies where a column displayed in a schema is defined.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnSource {
+    pub column_oid: i64,
+    pub source_table_oid: i64,
+
+    /// True if the column is defined on a master table, rather than on the schema itself.
+    pub is_inherited: bool,
+}
+
+/// A column of a table, including the columns inherited from each of its master tables.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveColumn {
+    pub column_oid: i64,
+    pub name: String,
+    pub column_type: column_type::ColumnType,
+    pub ordering: i64,
+    pub source_table_oid: i64,
+    pub source_table_name: String,
+
+    /// True if the column is defined on a master table, rather than on the table itself.
+    pub is_inherited: bool,
+}
+
+/// A column whose type has a particular mode.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnOfType {
+    pub schema_oid: i64,
+    pub column_oid: i64,
+    pub name: String,
+
+    /// The table referenced by an Object, Select, or Multiselect column, or the report referenced by a Subreport column.
+    pub referenced_schema_oid: Option<i64>,
+}
+
+/// The CSS style of a column displayed in a schema.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnStyle {
+    pub column_oid: i64,
+    pub style: String,
+}
+
+/// A condition on the value of a cell, under which a conditional formatting rule applies.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum FormatCondition {
+    IsEmpty,
+    IsNotEmpty,
+
+    /// The display value of the cell is exactly the given text.
+    Equals { value: String },
+    NotEquals { value: String },
+
+    /// The display value of the cell contains the given text, ignoring case.
+    Contains { value: String },
+
+    /// The value of the cell is a number less than the given number.
+    LessThan { value: f64 },
+    LessThanOrEqual { value: f64 },
+    GreaterThan { value: f64 },
+    GreaterThanOrEqual { value: f64 },
+}
+
+impl FormatCondition {
+    /// Checks whether a cell meets the condition, given its underlying value and its display value.
+    pub fn matches(&self, value: &rusqlite::types::Value, label: Option<&str>) -> bool {
+        let number: Option<f64> = match value {
+            rusqlite::types::Value::Integer(i) => Some(*i as f64),
+            rusqlite::types::Value::Real(f) => Some(*f),
+            rusqlite::types::Value::Text(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        };
+        let is_empty: bool = matches!(value, rusqlite::types::Value::Null);
+        match self {
+            Self::IsEmpty => is_empty,
+            Self::IsNotEmpty => !is_empty,
+            Self::Equals { value } => label.is_some_and(|label| label == value),
+            Self::NotEquals { value } => !label.is_some_and(|label| label == value),
+            Self::Contains { value } => label.is_some_and(|label| label.to_lowercase().contains(&value.to_lowercase())),
+            Self::LessThan { value } => number.is_some_and(|n| n < *value),
+            Self::LessThanOrEqual { value } => number.is_some_and(|n| n <= *value),
+            Self::GreaterThan { value } => number.is_some_and(|n| n > *value),
+            Self::GreaterThanOrEqual { value } => number.is_some_and(|n| n >= *value),
+        }
+    }
+}
+
+/// A rule that applies a CSS style to the cells of a column which meet a condition.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatRule {
+    pub oid: i64,
+    pub column_oid: i64,
+    pub condition: FormatCondition,
+    pub style: String,
+}
+
+/// The table referenced by an Object, Select, or Multiselect column.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceTarget {
+    pub table_oid: i64,
+    pub table_name: String,
+}
+
+/// Where a new column is placed relative to an existing column.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ColumnPosition {
+    /// The new column is placed immediately to the left of the existing column.
+    Before,
+
+    /// The new column is placed immediately to the right of the existing column.
+    After,
+}
+
+/// The behavior of a Select or Multiselect column when a row that it references is trashed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ReferenceOnDelete {
+    /// The reference is cleared.
+    #[default]
+    SetNull,
+
+    /// The row holding the reference is trashed as well.
+    Cascade,
+
+    /// The referenced row cannot be trashed.
+    Restrict,
+}
+
+impl ReferenceOnDelete {
+    /// Gets the SQL action corresponding to the policy.
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            Self::SetNull => "SET NULL",
+            Self::Cascade => "CASCADE",
+            Self::Restrict => "RESTRICT",
+        }
+    }
+
+    /// Gets the policy from its SQL action.
+    pub fn from_sql(sql: &str) -> Self {
+        match sql {
+            "CASCADE" => Self::Cascade,
+            "RESTRICT" => Self::Restrict,
+            _ => Self::SetNull,
+        }
+    }
+}
+
+/// The constraints that values written to a column are validated against.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnValidations {
+    pub column_oid: i64,
+    pub is_nullable: bool,
+    pub is_primary_key: bool,
+    pub case_insensitive_unique: bool,
+    pub empty_as_null: bool,
+
+    /// Whether values must be valid JSON.
+    pub pretty_print_json: bool,
+
+    /// The subtype that a Select or Multiselect column is restricted to referencing.
+    pub reference_subtype_filter: Option<i64>,
+}
+
+/// How the values of an Integer column are displayed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum IntegerFormat {
+    /// The value is displayed as-is (e.g. 1234567).
+    #[default]
+    Plain,
+
+    /// The value is displayed with thousands separators (e.g. 1,234,567).
+    Thousands,
+
+    /// The value is displayed in hexadecimal (e.g. 0x12D687, or -0x12D687 if negative).
+    Hex,
+
+    /// The value is padded with leading zeroes to a minimum width (e.g. 0001234567).
+    ZeroPadded { width: u8 },
+}
+
+impl IntegerFormat {
+    /// Gets the SQL representation of the format.
+    pub fn to_sql(&self) -> String {
+        match self {
+            Self::Plain => String::from("PLAIN"),
+            Self::Thousands => String::from("THOUSANDS"),
+            Self::Hex => String::from("HEX"),
+            Self::ZeroPadded { width } => format!("ZEROPADDED{width}"),
+        }
+    }
+
+    /// Gets the format from its SQL representation.
+    pub fn from_sql(sql: &str) -> Self {
+        match sql {
+            "THOUSANDS" => Self::Thousands,
+            "HEX" => Self::Hex,
+            _ => match sql.strip_prefix("ZEROPADDED").and_then(|width| width.parse::<u8>().ok()) {
+                Some(width) => Self::ZeroPadded { width },
+                None => Self::Plain,
+            },
+        }
+    }
+
+    /// Constructs an SQL expression that displays an integer value in this format.
+    pub fn construct_label_expr(&self, value_expr: &String) -> String {
+        match self {
+            Self::Plain => format!("CAST({value_expr} AS TEXT)"),
+            Self::Thousands => format!("IF({value_expr} IS NULL, NULL, FORMAT('%,d', {value_expr}))"),
+            Self::Hex => format!("IF({value_expr} IS NULL, NULL, IF({value_expr} < 0, FORMAT('-0x%X', -({value_expr})), FORMAT('0x%X', {value_expr})))"),
+            Self::ZeroPadded { width } => format!("IF({value_expr} IS NULL, NULL, FORMAT('%0{width}d', {value_expr}))"),
+        }
+    }
+
+    /// Parses an integer value that was entered in this format.
+    /// Thousands separators and leading zeroes are always accepted, as are hexadecimal values prefixed with 0x.
+    pub fn parse(&self, text: &str) -> Result<Option<i64>, Error> {
+        let text: String = text.trim().replace(',', "");
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        // Step 1: Check for a hexadecimal value
+        let (is_negative, unsigned_text) = match text.strip_prefix('-') {
+            Some(unsigned_text) => (true, unsigned_text),
+            None => (false, text.as_str()),
+        };
+        let hex_text: Option<&str> = unsigned_text
+            .strip_prefix("0x")
+            .or_else(|| unsigned_text.strip_prefix("0X"))
+            .or(if *self == Self::Hex { Some(unsigned_text) } else { None });
+        if let Some(hex_text) = hex_text {
+            // Reattach the sign, so that the most negative integer can be entered without overflowing
+            let signed_hex_text: String = if is_negative { format!("-{hex_text}") } else { String::from(hex_text) };
+            return match i64::from_str_radix(&signed_hex_text, 16) {
+                Ok(value) => Ok(Some(value)),
+                Err(e) if matches!(e.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) => Err(Error::AdhocError("The entered value is too large to be stored as an integer.")),
+                Err(_) => Err(Error::AdhocError("The entered value is not a hexadecimal integer.")),
+            };
+        }
+
+        // Step 2: Parse as a decimal value
+        // Scientific notation, fractions, and non-finite values (e.g. NaN, inf) are rejected rather than truncated
+        match text.parse::<i64>() {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => match e.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Err(Error::AdhocError("The entered value is too large to be stored as an integer.")),
+                _ => match text.parse::<f64>() {
+                    Ok(_) if text.contains(['e', 'E']) => Err(Error::AdhocError("Integers cannot be entered in scientific notation.")),
+                    Ok(value) if !value.is_finite() => Err(Error::AdhocError("The entered value is not a finite integer.")),
+                    _ => Err(Error::AdhocError("The entered value is not an integer.")),
+                },
+            },
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct FullMetadata {
@@ -25,8 +407,35 @@ pub struct FullMetadata {
     pub column_type: column_type::ColumnType,
     pub style: String,
     pub ordering: i64,
+    /// The value given to this column in newly inserted rows. File, Image and File List columns ignore it.
     pub default_value: Option<String>,
+    #[serde(default = "default_is_nullable")]
+    pub is_nullable: bool,
     pub is_primary_key: bool,
+    #[serde(default)]
+    pub case_insensitive_unique: bool,
+    #[serde(default)]
+    pub pretty_print_json: bool,
+    #[serde(default = "default_empty_as_null")]
+    pub empty_as_null: bool,
+    #[serde(default)]
+    pub reference_on_delete: ReferenceOnDelete,
+    #[serde(default)]
+    pub reference_subtype_filter: Option<i64>,
+    #[serde(default)]
+    pub integer_format: IntegerFormat,
+    #[serde(default)]
+    pub reference_display_column_oid: Option<i64>,
+}
+
+/// Empty text values are stored as NULL unless a column opts out.
+fn default_empty_as_null() -> bool {
+    true
+}
+
+/// Columns accept NULL values unless they are key columns.
+fn default_is_nullable() -> bool {
+    true
 }
 
 impl Hash for FullMetadata {
@@ -46,7 +455,15 @@ impl FullMetadata {
             style,
             ordering,
             default_value,
+            is_nullable,
             is_primary_key,
+            case_insensitive_unique,
+            pretty_print_json,
+            empty_as_null,
+            reference_on_delete,
+            reference_subtype_filter,
+            integer_format,
+            reference_display_column_oid,
         ) = conn.query_one(
             "
             SELECT
@@ -57,7 +474,15 @@ impl FullMetadata {
                 c.STYLE,
                 c.ORDERING,
                 c.DEFAULT_VALUE,
-                c.IS_PRIMARY_KEY
+                c.IS_NULLABLE,
+                c.IS_PRIMARY_KEY,
+                c.CASE_INSENSITIVE_UNIQUE,
+                c.PRETTY_PRINT_JSON,
+                c.EMPTY_AS_NULL,
+                c.REFERENCE_ON_DELETE,
+                c.REFERENCE_SUBTYPE_FILTER,
+                c.INTEGER_FORMAT,
+                c.REFERENCE_DISPLAY_COLUMN_OID
             FROM METADATA_COLUMN c
             WHERE c.OID = ?1
             ",
@@ -71,7 +496,15 @@ impl FullMetadata {
                     row.get::<_, String>("STYLE")?,
                     row.get::<_, i64>("ORDERING")?,
                     row.get::<_, Option<String>>("DEFAULT_VALUE")?,
+                    row.get::<_, bool>("IS_NULLABLE")?,
                     row.get::<_, bool>("IS_PRIMARY_KEY")?,
+                    row.get::<_, bool>("CASE_INSENSITIVE_UNIQUE")?,
+                    row.get::<_, bool>("PRETTY_PRINT_JSON")?,
+                    row.get::<_, bool>("EMPTY_AS_NULL")?,
+                    ReferenceOnDelete::from_sql(&row.get::<_, String>("REFERENCE_ON_DELETE")?),
+                    row.get::<_, Option<i64>>("REFERENCE_SUBTYPE_FILTER")?,
+                    IntegerFormat::from_sql(&row.get::<_, String>("INTEGER_FORMAT")?),
+                    row.get::<_, Option<i64>>("REFERENCE_DISPLAY_COLUMN_OID")?,
                 ))
             },
         )?;
@@ -88,7 +521,15 @@ impl FullMetadata {
             style,
             ordering,
             default_value,
+            is_nullable,
             is_primary_key,
+            case_insensitive_unique,
+            pretty_print_json,
+            empty_as_null,
+            reference_on_delete,
+            reference_subtype_filter,
+            integer_format,
+            reference_display_column_oid,
         })
     }
 
@@ -160,7 +601,14 @@ impl FullMetadata {
                 c.ORDERING,
                 c.DEFAULT_VALUE,
                 c.IS_NULLABLE,
-                c.IS_PRIMARY_KEY
+                c.IS_PRIMARY_KEY,
+                c.CASE_INSENSITIVE_UNIQUE,
+                c.PRETTY_PRINT_JSON,
+                c.EMPTY_AS_NULL,
+                c.REFERENCE_ON_DELETE,
+                c.REFERENCE_SUBTYPE_FILTER,
+                c.INTEGER_FORMAT,
+                c.REFERENCE_DISPLAY_COLUMN_OID
             FROM METADATA_SCHEMA_COLUMN_VIEW sc
             INNER JOIN METADATA_COLUMN c ON c.OID = sc.COLUMN_OID
             WHERE sc.SCHEMA_OID = ?1
@@ -177,7 +625,15 @@ impl FullMetadata {
                 row.get::<_, String>("STYLE")?,
                 row.get::<_, i64>("ORDERING")?,
                 row.get::<_, Option<String>>("DEFAULT_VALUE")?,
+                row.get::<_, bool>("IS_NULLABLE")?,
                 row.get::<_, bool>("IS_PRIMARY_KEY")?,
+                row.get::<_, bool>("CASE_INSENSITIVE_UNIQUE")?,
+                row.get::<_, bool>("PRETTY_PRINT_JSON")?,
+                row.get::<_, bool>("EMPTY_AS_NULL")?,
+                ReferenceOnDelete::from_sql(&row.get::<_, String>("REFERENCE_ON_DELETE")?),
+                row.get::<_, Option<i64>>("REFERENCE_SUBTYPE_FILTER")?,
+                IntegerFormat::from_sql(&row.get::<_, String>("INTEGER_FORMAT")?),
+                row.get::<_, Option<i64>>("REFERENCE_DISPLAY_COLUMN_OID")?,
             ))
         })? {
             let (
@@ -189,7 +645,15 @@ impl FullMetadata {
                 style,
                 ordering,
                 default_value,
+                is_nullable,
                 is_primary_key,
+                case_insensitive_unique,
+                pretty_print_json,
+                empty_as_null,
+                reference_on_delete,
+                reference_subtype_filter,
+                integer_format,
+                reference_display_column_oid,
             ) = row_result?;
 
             let schema: schema::FullMetadata = schema::FullMetadata::get(&conn, schema_oid)?;
@@ -204,12 +668,131 @@ impl FullMetadata {
                 style,
                 ordering,
                 default_value,
+                is_nullable,
                 is_primary_key,
+                case_insensitive_unique,
+                pretty_print_json,
+                empty_as_null,
+                reference_on_delete,
+                reference_subtype_filter,
+                integer_format,
+                reference_display_column_oid,
             })?;
         }
         Ok(())
     }
 
+    /// Queries the full list of columns of a table, across its entire chain of master tables.
+    pub fn query_effective(mut sender: Sender<EffectiveColumn>, table_oid: i64) -> Result<(), Error> {
+        let conn = db::open()?;
+
+        let mut select_statement = conn.prepare(
+            "
+            SELECT
+                c.OID,
+                c.NAME,
+                c.TYPE_OID,
+                c.ORDERING,
+                c.SCHEMA_OID,
+                s.NAME AS SCHEMA_NAME
+            FROM METADATA_SCHEMA_COLUMN_VIEW sc
+            INNER JOIN METADATA_COLUMN_VIEW c ON c.OID = sc.COLUMN_OID
+            INNER JOIN METADATA_SCHEMA s ON s.OID = c.SCHEMA_OID
+            WHERE sc.IS_REQUIRED AND sc.SCHEMA_OID = ?1
+            ORDER BY c.ORDERING
+            ",
+        )?;
+        for row_result in select_statement.query_map(params![table_oid], |row| {
+            Ok((
+                row.get::<_, i64>("OID")?,
+                row.get::<_, String>("NAME")?,
+                row.get::<_, i64>("TYPE_OID")?,
+                row.get::<_, i64>("ORDERING")?,
+                row.get::<_, i64>("SCHEMA_OID")?,
+                row.get::<_, String>("SCHEMA_NAME")?,
+            ))
+        })? {
+            let (column_oid, name, column_type_oid, ordering, source_table_oid, source_table_name) = row_result?;
+            sender.send(EffectiveColumn {
+                column_oid,
+                name,
+                column_type: column_type::ColumnType::get_transact(&conn, column_type_oid)?,
+                ordering,
+                source_table_oid,
+                source_table_name,
+                is_inherited: source_table_oid != table_oid,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Finds every column in the database whose type has the given mode.
+    /// The mode is either the mode of a primitive type (e.g. "integer" or "file"), or one of "formula", "subreport", "object", "select", or "multiselect".
+    pub fn find_by_type_mode(mode: String) -> Result<Vec<ColumnOfType>, Error> {
+        let conn = db::open()?;
+
+        let mut columns: Vec<ColumnOfType> = Vec::new();
+        for row_result in conn.prepare(
+            "
+            SELECT 
+                c.SCHEMA_OID, 
+                c.OID, 
+                c.NAME, 
+                typ.REFERENCED_SCHEMA_OID
+            FROM METADATA_COLUMN_VIEW c
+            INNER JOIN (
+                SELECT OID, MODE, NULL AS REFERENCED_SCHEMA_OID FROM METADATA_COLUMN_TYPE__PRIMITIVE
+                UNION ALL
+                SELECT OID, 'formula' AS MODE, NULL AS REFERENCED_SCHEMA_OID FROM METADATA_COLUMN_TYPE__FORMULA
+                UNION ALL
+                SELECT OID, 'subreport' AS MODE, REPORT_OID AS REFERENCED_SCHEMA_OID FROM METADATA_COLUMN_TYPE__SUBREPORT
+                UNION ALL
+                SELECT OID, 'object' AS MODE, TABLE_OID AS REFERENCED_SCHEMA_OID FROM METADATA_COLUMN_TYPE__OBJECT
+                UNION ALL
+                SELECT OID, 'select' AS MODE, TABLE_OID AS REFERENCED_SCHEMA_OID FROM METADATA_COLUMN_TYPE__SELECT
+                UNION ALL
+                SELECT OID, 'multiselect' AS MODE, TABLE_OID AS REFERENCED_SCHEMA_OID FROM METADATA_COLUMN_TYPE__MULTISELECT
+            ) typ ON typ.OID = c.TYPE_OID
+            WHERE typ.MODE = ?1
+            ORDER BY c.SCHEMA_OID, c.ORDERING
+            ",
+        )?.query_map(params![mode], |row| {
+            Ok(ColumnOfType {
+                schema_oid: row.get("SCHEMA_OID")?,
+                column_oid: row.get("OID")?,
+                name: row.get("NAME")?,
+                referenced_schema_oid: row.get("REFERENCED_SCHEMA_OID")?,
+            })
+        })? {
+            columns.push(row_result?);
+        }
+        Ok(columns)
+    }
+
+    /// Gets the schema that defines each column displayed in a schema.
+    pub fn query_sources(schema_oid: i64) -> Result<Vec<ColumnSource>, Error> {
+        let conn = db::open()?;
+
+        let mut sources: Vec<ColumnSource> = Vec::new();
+        for row_result in conn.prepare(
+            "
+            SELECT sc.COLUMN_OID, c.SCHEMA_OID
+            FROM METADATA_SCHEMA_COLUMN_VIEW sc
+            INNER JOIN METADATA_COLUMN c ON c.OID = sc.COLUMN_OID
+            WHERE sc.IS_REQUIRED AND sc.SCHEMA_OID = ?1
+            ORDER BY sc.ORDERING
+            ",
+        )?.query_map(params![schema_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, i64>("SCHEMA_OID")?)))? {
+            let (column_oid, source_table_oid) = row_result?;
+            sources.push(ColumnSource {
+                column_oid,
+                source_table_oid,
+                is_inherited: source_table_oid != schema_oid,
+            });
+        }
+        Ok(sources)
+    }
+
     /// Queries the tables that can be associated with an Object, Select, or Multiselect column.
     pub fn query_associated_tables(mut sender: Sender<DropdownValue>) -> Result<(), Error> {
         let conn = db::open()?;
@@ -229,6 +812,151 @@ impl FullMetadata {
         Ok(())
     }
 
+    /// Gets the CSS style of each column displayed in a table or report, including inherited columns.
+    pub fn query_styles(schema_oid: i64) -> Result<Vec<ColumnStyle>, Error> {
+        let conn = db::open()?;
+        let mut styles: Vec<ColumnStyle> = Vec::new();
+        for style_result in conn
+            .prepare(
+                "
+                SELECT c.OID, c.STYLE
+                FROM METADATA_SCHEMA_COLUMN_VIEW sc
+                INNER JOIN METADATA_COLUMN c ON c.OID = sc.COLUMN_OID
+                WHERE sc.SCHEMA_OID = ?1
+                ORDER BY c.ORDERING
+                ",
+            )?
+            .query_map(params![schema_oid], |row| {
+                Ok(ColumnStyle {
+                    column_oid: row.get("OID")?,
+                    style: row.get("STYLE")?,
+                })
+            })?
+        {
+            styles.push(style_result?);
+        }
+        Ok(styles)
+    }
+
+    /// Gets the conditional formatting rules of a column, in the order they were added.
+    pub fn get_format_rules_transact(conn: &Connection, column_oid: i64) -> Result<Vec<FormatRule>, Error> {
+        let mut rules: Vec<FormatRule> = Vec::new();
+        for rule_result in conn
+            .prepare("SELECT OID, CONDITION, STYLE FROM METADATA_COLUMN_FORMAT_RULE WHERE COLUMN_OID = ?1 ORDER BY OID")?
+            .query_map(params![column_oid], |row| {
+                Ok((row.get::<_, i64>("OID")?, row.get::<_, String>("CONDITION")?, row.get::<_, String>("STYLE")?))
+            })?
+        {
+            let (oid, condition, style) = rule_result?;
+            let condition: FormatCondition = serde_json::from_str(&condition)
+                .map_err(|_| Error::AdhocError("A conditional formatting rule has an unrecognized condition."))?;
+            rules.push(FormatRule {
+                oid,
+                column_oid,
+                condition,
+                style,
+            });
+        }
+        Ok(rules)
+    }
+
+    /// Gets the conditional formatting rules of a column, in the order they were added.
+    pub fn get_format_rules(column_oid: i64) -> Result<Vec<FormatRule>, Error> {
+        let conn = db::open()?;
+        Self::get_format_rules_transact(&conn, column_oid)
+    }
+
+    /// Gets the OID that the next conditional formatting rule added to any column will be given.
+    pub fn get_next_format_rule_oid() -> Result<i64, Error> {
+        let conn = db::open()?;
+        Ok(conn.query_one("SELECT COALESCE(MAX(OID), 0) + 1 FROM METADATA_COLUMN_FORMAT_RULE", [], |row| row.get(0))?)
+    }
+
+    /// Adds a conditional formatting rule to a column, under the OID given by the rule.
+    pub fn add_format_rule(rule: &FormatRule) -> Result<(), Error> {
+        let conn = db::open()?;
+        let column: Self = Self::get_transact(&conn, rule.column_oid)?;
+        if let column_type::ColumnType::Subreport { .. } = column.column_type {
+            return Err(Error::AdhocError("Subreport columns cannot be formatted conditionally."));
+        }
+
+        let condition: String = serde_json::to_string(&rule.condition)
+            .map_err(|_| Error::AdhocError("The condition of the rule could not be stored."))?;
+        conn.execute(
+            "INSERT INTO METADATA_COLUMN_FORMAT_RULE (OID, COLUMN_OID, CONDITION, STYLE) VALUES (?1, ?2, ?3, ?4)",
+            params![rule.oid, rule.column_oid, condition, rule.style],
+        )?;
+        Ok(())
+    }
+
+    /// Removes a conditional formatting rule from its column.
+    /// Returns the rule that was removed, so that it can be added back.
+    pub fn remove_format_rule(rule_oid: i64) -> Result<FormatRule, Error> {
+        let conn = db::open()?;
+        let column_oid: i64 = match conn
+            .query_one(
+                "SELECT COLUMN_OID FROM METADATA_COLUMN_FORMAT_RULE WHERE OID = ?1",
+                params![rule_oid],
+                |row| row.get("COLUMN_OID"),
+            )
+            .optional()?
+        {
+            Some(column_oid) => column_oid,
+            None => {
+                return Err(Error::AdhocError("The conditional formatting rule does not exist."));
+            }
+        };
+        let rule: FormatRule = Self::get_format_rules_transact(&conn, column_oid)?
+            .into_iter()
+            .find(|rule| rule.oid == rule_oid)
+            .ok_or(Error::AdhocError("The conditional formatting rule does not exist."))?;
+        conn.execute("DELETE FROM METADATA_COLUMN_FORMAT_RULE WHERE OID = ?1", params![rule_oid])?;
+        Ok(rule)
+    }
+
+    /// Gets the table currently referenced by an Object, Select, or Multiselect column.
+    /// Returns None if the column is not one of those types.
+    pub fn get_reference_target(column_oid: i64) -> Result<Option<ReferenceTarget>, Error> {
+        let conn = db::open()?;
+        let column: Self = Self::get_transact(&conn, column_oid)?;
+        let table_oid: i64 = match column.column_type {
+            column_type::ColumnType::Object { table_oid, .. }
+            | column_type::ColumnType::Select { table_oid, .. }
+            | column_type::ColumnType::Multiselect { table_oid, .. } => table_oid,
+            _ => {
+                return Ok(None);
+            }
+        };
+        let table_name: String = conn.query_one(
+            "SELECT NAME FROM METADATA_SCHEMA WHERE OID = ?1",
+            params![table_oid],
+            |row| row.get("NAME"),
+        )?;
+        Ok(Some(ReferenceTarget {
+            table_oid,
+            table_name,
+        }))
+    }
+
+    /// Counts the rows that currently reference a particular row through a Select or Multiselect column.
+    pub fn count_reference_usage(column_oid: i64, linked_row_oid: i64) -> Result<i64, Error> {
+        let conn = db::open()?;
+        let column: Self = Self::get_transact(&conn, column_oid)?;
+        let sql_count: String = match column.column_type {
+            column_type::ColumnType::Select { .. } => format!(
+                "SELECT COUNT(*) FROM TABLE{} WHERE NOT TRASH AND COLUMN{column_oid} = ?1",
+                column.schema.oid
+            ),
+            column_type::ColumnType::Multiselect { table_oid, .. } => format!(
+                "SELECT COUNT(*) FROM MULTISELECT{column_oid}_VIEW WHERE TABLE{table_oid}_OID = ?1"
+            ),
+            _ => {
+                return Err(Error::AdhocError("Only Select and Multiselect columns have dropdown values."));
+            }
+        };
+        Ok(conn.query_one(&sql_count, params![linked_row_oid], |row| row.get::<_, i64>(0))?)
+    }
+
     /// Queries the reports that can be associated with an Object, Select, or Multiselect column.
     pub fn query_associated_reports(mut sender: Sender<DropdownValue>) -> Result<(), Error> {
         let conn = db::open()?;
@@ -268,15 +996,314 @@ impl FullMetadata {
             sender.send(DropdownValue { label, value })?;
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    /// Gets the length, in characters, of the longest text displayed in a column of a table, up to a cap.
+    pub fn get_max_display_length(table_oid: i64, column_oid: i64) -> Result<i64, Error> {
+        let conn = db::open()?;
+        let sql_select = format!(
+            "SELECT COALESCE(MAX(LENGTH(COLUMN{column_oid}_LABEL)), 0) FROM SCHEMA{table_oid}_VIEW"
+        );
+        let max_display_length: i64 = conn.query_one(&sql_select, [], |row| row.get(0))?;
+        Ok(max_display_length.min(MAX_DISPLAY_LENGTH_CAP))
+    }
+
+    /// Gets the single text column that forms the label of each row in a table, such as the name of a dropdown value.
+    /// Returns an error if the label is made from several columns, or from a column that does not hold plain text.
+    pub fn get_label_column(table_oid: i64) -> Result<Self, Error> {
+        let conn = db::open()?;
+        let label_column_oids: Vec<i64> = conn
+            .prepare("SELECT COLUMN_OID FROM METADATA_SCHEMA_COLUMN_VIEW v INNER JOIN METADATA_COLUMN c ON c.OID = v.COLUMN_OID WHERE v.SCHEMA_OID = ?1 AND c.IS_PRIMARY_KEY AND NOT c.TRASH")?
+            .query_map(params![table_oid], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+        let [label_column_oid] = label_column_oids[..] else {
+            return Err(Error::AdhocError("The label of each row is not made from exactly one column."));
+        };
+        let label_column: Self = Self::get_transact(&conn, label_column_oid)?;
+        match label_column.column_type {
+            column_type::ColumnType::Primitive(column_type::Primitive::PlainText)
+            | column_type::ColumnType::Primitive(column_type::Primitive::LongText) => Ok(label_column),
+            _ => Err(Error::AdhocError("The label of each row is not a text column.")),
+        }
+    }
+
+    /// Gets the default value of a column, formatted the way it would be displayed in a cell.
+    pub fn get_default_display(column_oid: i64) -> Result<Option<String>, Error> {
+        let conn = db::open()?;
+        let column = Self::get_transact(&conn, column_oid)?;
+        let default_value = match column.default_value {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        // Dates and timestamps are stored as Julian day numbers, so convert them back to ISO format
+        let sql_format: &str = match column.column_type {
+            column_type::ColumnType::Primitive(column_type::Primitive::Boolean) => {
+                "SELECT CASE WHEN ?1 IN ('0', 'false', '') THEN 'false' ELSE 'true' END"
+            }
+            column_type::ColumnType::Primitive(column_type::Primitive::Date) => {
+                "SELECT COALESCE(DATE(?1, 'julianday'), ?1)"
+            }
+            column_type::ColumnType::Primitive(column_type::Primitive::Datetime) => {
+                "SELECT COALESCE(STRFTIME('%FT%H:%M:%fZ', ?1, 'julianday'), ?1)"
+            }
+            _ => return Ok(Some(default_value)),
+        };
+        Ok(Some(conn.query_one(sql_format, params![default_value], |row| row.get::<_, String>(0))?))
+    }
+
+    /// Finds a value that is duplicated in the column when case is ignored, if the column requires case-insensitive uniqueness.
+    fn find_case_insensitive_duplicate_transact(&self, conn: &Connection) -> Result<Option<String>, Error> {
+        if !self.case_insensitive_unique || !is_validation_enabled() {
+            return Ok(None);
+        }
+        match &self.column_type {
+            column_type::ColumnType::Primitive(column_type::Primitive::PlainText)
+            | column_type::ColumnType::Primitive(column_type::Primitive::MarkdownText)
+            | column_type::ColumnType::Primitive(column_type::Primitive::JsonText)
+            | column_type::ColumnType::Primitive(column_type::Primitive::XmlText) => {
+                let sql_select: String = format!(
+                    "
+                    SELECT MIN(COLUMN{}) AS VALUE 
+                    FROM TABLE{} 
+                    WHERE NOT TRASH AND COLUMN{} IS NOT NULL 
+                    GROUP BY LOWER(COLUMN{}) 
+                    HAVING COUNT(*) > 1 
+                    LIMIT 1
+                    ",
+                    self.oid, self.schema.oid, self.oid, self.oid
+                );
+                Ok(conn
+                    .query_one(&sql_select, [], |row| row.get::<_, String>("VALUE"))
+                    .optional()?)
+            }
+            _ => Ok(None), // Uniqueness is only checked for text columns
+        }
+    }
+
+    /// Gets every constraint that values written to a column are validated against.
+    pub fn get_validations(column_oid: i64) -> Result<ColumnValidations, Error> {
+        let conn = db::open()?;
+        Ok(conn.query_one(
+            "
+            SELECT
+                IS_NULLABLE,
+                IS_PRIMARY_KEY,
+                CASE_INSENSITIVE_UNIQUE,
+                EMPTY_AS_NULL,
+                PRETTY_PRINT_JSON,
+                REFERENCE_SUBTYPE_FILTER
+            FROM METADATA_COLUMN
+            WHERE OID = ?1
+            ",
+            params![column_oid],
+            |row| {
+                Ok(ColumnValidations {
+                    column_oid,
+                    is_nullable: row.get("IS_NULLABLE")?,
+                    is_primary_key: row.get("IS_PRIMARY_KEY")?,
+                    case_insensitive_unique: row.get("CASE_INSENSITIVE_UNIQUE")?,
+                    empty_as_null: row.get("EMPTY_AS_NULL")?,
+                    pretty_print_json: row.get("PRETTY_PRINT_JSON")?,
+                    reference_subtype_filter: row.get("REFERENCE_SUBTYPE_FILTER")?,
+                })
+            },
+        )?)
+    }
+
+    /// Checks that a text value is valid JSON, if the column pretty-prints its JSON values.
+    pub fn check_json_transact(conn: &Connection, column_oid: i64, value: &str) -> Result<(), Error> {
+        if !is_validation_enabled() {
+            return Ok(());
+        }
+        let pretty_print_json: bool = conn.query_one(
+            "SELECT PRETTY_PRINT_JSON FROM METADATA_COLUMN WHERE OID = ?1",
+            params![column_oid],
+            |row| row.get("PRETTY_PRINT_JSON"),
+        )?;
+        if pretty_print_json && serde_json::from_str::<serde_json::Value>(value).is_err() {
+            return Err(Error::AdhocError("The value is not valid JSON."));
+        }
+        Ok(())
+    }
+
+    /// Checks whether empty text values written to the column are stored as NULL.
+    pub fn is_empty_as_null_transact(conn: &Connection, column_oid: i64) -> Result<bool, Error> {
+        Ok(conn.query_one(
+            "SELECT EMPTY_AS_NULL FROM METADATA_COLUMN WHERE OID = ?1",
+            params![column_oid],
+            |row| row.get("EMPTY_AS_NULL"),
+        )?)
+    }
+
+    /// Gets a query for the OIDs of the rows that a Select or Multiselect column may reference, if the column restricts its references to a subtype.
+    /// The query selects a single column of OIDs from the referenced table.
+    pub fn get_reference_subtype_filter_sql_transact(conn: &Connection, column_oid: i64) -> Result<Option<String>, Error> {
+        let column: Self = Self::get_transact(conn, column_oid)?;
+        let subtype_oid: i64 = match column.reference_subtype_filter {
+            Some(subtype_oid) => subtype_oid,
+            None => { return Ok(None); }
+        };
+        let table_oid: i64 = match &column.column_type {
+            column_type::ColumnType::Select { table_oid, .. }
+            | column_type::ColumnType::Multiselect { table_oid, .. } => *table_oid,
+            _ => { return Ok(None); } // Only references to a table can be filtered
+        };
+        if subtype_oid == table_oid {
+            return Ok(None); // Every row of the referenced table belongs to the table itself
+        }
+
+        // Find the chain of inheritance from the referenced table down to the subtype
+        let inheritor_datasource_path: String = match conn.query_one(
+            "SELECT INHERITOR_DATASOURCE_PATH FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW WHERE MASTER_SCHEMA_OID = ?1 AND INHERITOR_SCHEMA_OID = ?2",
+            params![table_oid, subtype_oid],
+            |row| row.get::<_, String>("INHERITOR_DATASOURCE_PATH"),
+        ).optional()? {
+            Some(path) => path,
+            None => { return Err(Error::AdhocError("The subtype filter of the column does not inherit from the referenced table.")); }
+        };
+        let mut chain: Vec<i64> = vec![table_oid];
+        for part in inheritor_datasource_path.split("_INHERITOR").filter(|part| !part.is_empty()) {
+            chain.push(part.parse::<i64>().map_err(|_| Error::AdhocError("Unable to parse the inheritance path of the subtype."))?);
+        }
+
+        // Walk up from the rows of the subtype to the rows of the referenced table that they extend
+        let mut sql: String = format!("SELECT OID FROM TABLE{subtype_oid} WHERE NOT TRASH");
+        for idx in (1..chain.len()).rev() {
+            let (master_oid, inheritor_oid) = (chain[idx - 1], chain[idx]);
+            sql = format!("SELECT MASTER{master_oid}_OID FROM TABLE{inheritor_oid} WHERE OID IN ({sql})");
+        }
+        Ok(Some(sql))
+    }
+
+    /// Checks that each row referenced by a Select or Multiselect column belongs to the subtype that the column is restricted to.
+    pub fn check_reference_subtype_transact(conn: &Connection, column_oid: i64, linked_row_oids: &[i64]) -> Result<(), Error> {
+        if !is_validation_enabled() {
+            return Ok(());
+        }
+        if let Some(filter_sql) = Self::get_reference_subtype_filter_sql_transact(conn, column_oid)? {
+            let sql_select: String = format!("SELECT ?1 IN ({filter_sql}) AS IS_ALLOWED");
+            for linked_row_oid in linked_row_oids {
+                if !conn.query_one(&sql_select, params![linked_row_oid], |row| row.get::<_, bool>("IS_ALLOWED"))? {
+                    return Err(Error::AdhocError("The referenced row does not belong to the subtype allowed by the column."));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that a text value does not match the value of another row in a case-insensitive unique column.
+    pub fn check_case_insensitive_unique_transact(
+        conn: &Connection,
+        column_oid: i64,
+        table_oid: i64,
+        row_oid: i64,
+        value: &str,
+    ) -> Result<(), Error> {
+        // Tables in bulk mode are checked for duplicates once bulk mode ends
+        if !is_validation_enabled() || view::is_in_bulk_mode(table_oid) {
+            return Ok(());
+        }
+        let (column_name, case_insensitive_unique) = conn.query_one(
+            "SELECT NAME, CASE_INSENSITIVE_UNIQUE FROM METADATA_COLUMN WHERE OID = ?1",
+            params![column_oid],
+            |row| {
+                Ok((
+                    row.get::<_, String>("NAME")?,
+                    row.get::<_, bool>("CASE_INSENSITIVE_UNIQUE")?,
+                ))
+            },
+        )?;
+        if !case_insensitive_unique {
+            return Ok(());
+        }
+
+        // Look for another row with the same value, ignoring case
+        let sql_select: String = format!(
+            "SELECT COLUMN{column_oid} AS VALUE FROM TABLE{table_oid} WHERE NOT TRASH AND OID <> ?1 AND LOWER(COLUMN{column_oid}) = LOWER(?2) LIMIT 1"
+        );
+        match conn
+            .query_one(&sql_select, params![row_oid, value], |row| {
+                row.get::<_, String>("VALUE")
+            })
+            .optional()?
+        {
+            Some(existing_value) => Err(Error::DuplicateValue {
+                column_name,
+                value: existing_value,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Finds the column references in a formula that do not point to an existing column.
+    /// Returns the text of each unknown reference.
+    pub fn find_unknown_formula_columns_transact(conn: &Connection, formula: &str) -> Result<Vec<String>, Error> {
+        let mut unknown_params: Vec<String> = Vec::new();
+        for (param_str, datasource_alias, column_oid) in Formula::find_params(formula) {
+            // A reference whose datasource cannot be resolved is unknown as well
+            let schema_oid: Option<i64> = crate::data::datasource::Datasource::from_alias_transact(conn, datasource_alias)
+                .and_then(|datasource| datasource.get_schema_oid())
+                .ok();
+            let is_known: bool = match schema_oid {
+                Some(schema_oid) => conn.query_one(
+                    "SELECT EXISTS (SELECT 1 FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND COLUMN_OID = ?2)",
+                    params![schema_oid, column_oid],
+                    |row| row.get::<_, bool>(0),
+                )?,
+                None => false,
+            };
+            if !is_known && !unknown_params.contains(&param_str) {
+                unknown_params.push(param_str);
+            }
+        }
+        Ok(unknown_params)
+    }
+
+    /// Finds the column references in a formula that do not point to an existing column.
+    pub fn find_unknown_formula_columns(formula: &str) -> Result<Vec<String>, Error> {
+        let conn: Connection = db::open()?;
+        Self::find_unknown_formula_columns_transact(&conn, formula)
     }
 
     /// Creates a new column.
-    fn _create(&mut self, trans: &Transaction) -> Result<(), Error> {
+    pub fn create_transact(&mut self, trans: &Transaction) -> Result<(), Error> {
+        // Reject formulas that reference columns which do not exist
+        if let column_type::ColumnType::Formula { formula, .. } = &self.column_type {
+            let unknown_params: Vec<String> = Self::find_unknown_formula_columns_transact(trans, formula)?;
+            if let Some(first_unknown_param) = unknown_params.into_iter().next() {
+                return Err(Error::FormulaParseError {
+                    msg: String::from("Unknown column."),
+                    full_formula: formula.clone(),
+                    substring_with_error: first_unknown_param,
+                });
+            }
+        }
+
         // Find the column type OID
         let column_type: column_type::ColumnType = self.column_type.clone();
         self.column_type = column_type.find_transact(trans)?;
 
+        // Key columns identify rows, so they cannot be left blank
+        if self.is_primary_key {
+            // Object and Multiselect columns hold collections of rows, which cannot identify a row
+            // Row Number columns change whenever the rows are sorted differently
+            match &self.column_type {
+                column_type::ColumnType::Object { .. } => {
+                    return Err(Error::AdhocError("An Object column cannot be a primary key."));
+                },
+                column_type::ColumnType::Multiselect { .. } => {
+                    return Err(Error::AdhocError("A Multiselect column cannot be a primary key."));
+                },
+                column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {
+                    return Err(Error::AdhocError("A Row Number column cannot be a primary key."));
+                },
+                _ => {}
+            }
+            self.is_nullable = false;
+        }
+
         if self.ordering < 0 {
             // Set the ordering to the maximum
             self.ordering = trans
@@ -310,8 +1337,16 @@ impl FullMetadata {
                 TYPE_OID,
                 STYLE,
                 ORDERING,
+                IS_NULLABLE,
                 IS_PRIMARY_KEY,
-                DEFAULT_VALUE
+                CASE_INSENSITIVE_UNIQUE,
+                PRETTY_PRINT_JSON,
+                EMPTY_AS_NULL,
+                REFERENCE_ON_DELETE,
+                REFERENCE_SUBTYPE_FILTER,
+                INTEGER_FORMAT,
+                DEFAULT_VALUE,
+                REFERENCE_DISPLAY_COLUMN_OID
             ) VALUES (
                 ?1,
                 ?2,
@@ -320,7 +1355,15 @@ impl FullMetadata {
                 ?5,
                 ?6,
                 ?7,
-                ?8
+                ?8,
+                ?9,
+                ?10,
+                ?11,
+                ?12,
+                ?13,
+                ?14,
+                ?15,
+                ?16
             )
             ",
             params![
@@ -330,14 +1373,72 @@ impl FullMetadata {
                 self.column_type.get_oid(),
                 self.style,
                 self.ordering,
+                self.is_nullable,
                 self.is_primary_key,
-                self.default_value
+                self.case_insensitive_unique,
+                self.pretty_print_json,
+                self.empty_as_null,
+                self.reference_on_delete.to_sql(),
+                self.reference_subtype_filter,
+                self.integer_format.to_sql(),
+                self.default_value,
+                self.reference_display_column_oid
             ],
         )?;
         self.oid = trans.last_insert_rowid();
 
+        // Verify that the subtype filter inherits from the referenced table
+        Self::get_reference_subtype_filter_sql_transact(trans, self.oid)?;
+
+        // Verify that the column displayed by a reference belongs to the referenced table
+        if let Some(display_column_oid) = self.reference_display_column_oid {
+            let referenced_table_oid: i64 = match &self.column_type {
+                column_type::ColumnType::Select { table_oid, .. }
+                | column_type::ColumnType::Multiselect { table_oid, .. } => *table_oid,
+                _ => {
+                    return Err(Error::AdhocError("Only Select and Multiselect columns can choose a column to display."));
+                }
+            };
+            let is_referenced_column: bool = trans.query_one(
+                "SELECT EXISTS (SELECT 1 FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND COLUMN_OID = ?2)",
+                params![referenced_table_oid, display_column_oid],
+                |row| row.get(0),
+            )?;
+            if !is_referenced_column {
+                return Err(Error::AdhocError("The column to display does not belong to the referenced table."));
+            }
+        }
+
+        // Verify that Object columns are not nested too deeply
+        if let column_type::ColumnType::Object { .. } = &self.column_type {
+            table::FullMetadata::check_object_nesting_depth_transact(trans)?;
+        }
+
         // If the column is not virtual, add it to the table
         match &self.column_type {
+            column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {} // Row numbers are computed by the view, so nothing is stored
+            column_type::ColumnType::Primitive(column_type::Primitive::FileList) => {
+                // The files of a File List column are stored in a separate table, one row per attached file
+                let cmd: String = format!(
+                    "
+                    CREATE TABLE FILELIST{} (
+                        TABLE{}_OID INTEGER NOT NULL REFERENCES TABLE{} (OID)
+                            ON UPDATE CASCADE
+                            ON DELETE CASCADE,
+                        FILE_OID INTEGER NOT NULL REFERENCES METADATA_FILE (OID)
+                            ON UPDATE CASCADE
+                            ON DELETE CASCADE,
+                        ORDERING INTEGER NOT NULL DEFAULT 0,
+                        PRIMARY KEY (TABLE{}_OID, FILE_OID)
+                    );
+                    ",
+                    self.oid,
+                    self.schema.oid,
+                    self.schema.oid,
+                    self.schema.oid
+                );
+                trans.execute_batch(&cmd)?;
+            }
             column_type::ColumnType::Primitive(prim) => {
                 let cmd: String = format!(
                     "ALTER TABLE TABLE{} ADD COLUMN COLUMN{} {}", 
@@ -346,21 +1447,25 @@ impl FullMetadata {
                     match prim {
                         column_type::Primitive::PlainText
                         | column_type::Primitive::MarkdownText
+                        | column_type::Primitive::LongText
                         | column_type::Primitive::JsonText
-                        | column_type::Primitive::XmlText => "TEXT",
+                        | column_type::Primitive::XmlText
+                        | column_type::Primitive::Point => "TEXT",
                         column_type::Primitive::Boolean
                         | column_type::Primitive::Integer => "INTEGER",
                         column_type::Primitive::Number
+                        | column_type::Primitive::Percentage
                         | column_type::Primitive::Date
                         | column_type::Primitive::Datetime => "REAL",
                         column_type::Primitive::File
-                        | column_type::Primitive::Image => "INTEGER REFERENCES METADATA_FILE (OID) ON UPDATE CASCADE ON DELETE SET NULL"
+                        | column_type::Primitive::Image => "INTEGER REFERENCES METADATA_FILE (OID) ON UPDATE CASCADE ON DELETE SET NULL",
+                        column_type::Primitive::FileList => unreachable!("File List columns are not stored in the table"),
+                        column_type::Primitive::RowNumber => unreachable!("Row Number columns are not stored in the table")
                     }
                 );
                 trans.execute(&cmd, [])?;
             }
-            column_type::ColumnType::Object { table_oid, .. }
-            | column_type::ColumnType::Select { table_oid, .. } => {
+            column_type::ColumnType::Object { table_oid, .. } => {
                 let cmd: String = format!(
                     "
                     ALTER TABLE TABLE{} ADD COLUMN COLUMN{} INTEGER 
@@ -372,7 +1477,24 @@ impl FullMetadata {
                 );
                 trans.execute(&cmd, [])?;
             }
+            column_type::ColumnType::Select { table_oid, .. } => {
+                let cmd: String = format!(
+                    "
+                    ALTER TABLE TABLE{} ADD COLUMN COLUMN{} INTEGER 
+                        REFERENCES TABLE{table_oid} (OID) 
+                        ON UPDATE CASCADE 
+                        ON DELETE {}
+                    ",
+                    self.schema.oid, self.oid, self.reference_on_delete.to_sql()
+                );
+                trans.execute(&cmd, [])?;
+            }
             column_type::ColumnType::Multiselect { table_oid, .. } => {
+                // Clearing a reference from a Multiselect column removes the link
+                let multiselect_on_delete: &str = match self.reference_on_delete {
+                    ReferenceOnDelete::Restrict => "RESTRICT",
+                    _ => "CASCADE",
+                };
                 let cmd: String = format!(
                     "
                     CREATE TABLE MULTISELECT{} (
@@ -381,7 +1503,8 @@ impl FullMetadata {
                             ON DELETE CASCADE,
                         TABLE{table_oid}_OID INTEGER NOT NULL REFERENCES TABLE{table_oid} (OID)
                             ON UPDATE CASCADE
-                            ON DELETE CASCADE,
+                            ON DELETE {multiselect_on_delete},
+                        ORDERING INTEGER NOT NULL DEFAULT 0,
                         PRIMARY KEY (TABLE{}_OID, TABLE{table_oid}_OID)
                     );
                     CREATE VIEW MULTISELECT{}_VIEW AS
@@ -423,7 +1546,40 @@ impl FullMetadata {
         let trans = conn.transaction()?;
 
         // Create the column
-        self._create(&trans)?;
+        self.create_transact(&trans)?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(())
+    }
+
+    /// Creates a new column next to an existing column, as part of a transaction.
+    pub fn create_relative_transact(&mut self, trans: &Transaction, reference_column_oid: i64, position: ColumnPosition) -> Result<(), Error> {
+        // Find the ordering of the column that the new column is placed next to
+        let reference_ordering: i64 = trans
+            .query_one(
+                "SELECT ORDERING FROM METADATA_COLUMN WHERE OID = ?1 AND SCHEMA_OID = ?2",
+                params![reference_column_oid, self.schema.oid],
+                |row| row.get("ORDERING"),
+            )
+            .optional()?
+            .ok_or(Error::AdhocError("The column that the new column is placed next to does not belong to the same table."))?;
+        self.ordering = match position {
+            ColumnPosition::Before => reference_ordering,
+            ColumnPosition::After => reference_ordering + 1,
+        };
+
+        // Create the column, shifting the columns to the right of it
+        self.create_transact(trans)
+    }
+
+    /// Creates a new column next to an existing column.
+    pub fn create_relative(&mut self, reference_column_oid: i64, position: ColumnPosition) -> Result<(), Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+
+        // Create the column
+        self.create_relative_transact(&trans, reference_column_oid, position)?;
 
         // Commit the transaction
         trans.commit()?;
@@ -433,10 +1589,67 @@ impl FullMetadata {
     /// Overwrites the column metadata.
     pub fn set(&mut self) -> Result<(), Error> {
         let mut conn = db::open()?;
-        let mut trans = conn.transaction()?;
+        let trans = conn.transaction()?;
+
+        // Overwrite the column transactionally
+        self.set_transact(&trans)?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(())
+    }
+
+    /// Makes the column non-nullable, after filling every blank cell of the rows that are not in the trash with a value.
+    /// Like set, the column is replaced by a new column, so that the change can be undone by restoring the old column.
+    /// Returns the number of cells that were filled.
+    pub fn set_not_null(&mut self, backfill_value: Option<&str>) -> Result<i64, Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+
+        // Replace the column with a copy that is not nullable
+        self.is_nullable = false;
+        self.set_transact(&trans)?;
 
+        // Fill the blank cells of the new column
+        let sql_select: String = format!("SELECT OID FROM TABLE{} WHERE NOT TRASH AND COLUMN{} IS NULL", self.schema.oid, self.oid);
+        let mut row_oids: Vec<i64> = Vec::new();
+        for row_result in trans.prepare(&sql_select)?.query_map([], |row| row.get::<_, i64>("OID"))? {
+            row_oids.push(row_result?);
+        }
+        if row_oids.len() > 0 {
+            let Some(backfill_value) = backfill_value else {
+                return Err(Error::AdhocError("The column has blank cells, so a value to fill them with is required."));
+            };
+            let value: cell::DataCellValue = cell::DataCellValue::from_label(self, backfill_value)?;
+            for row_oid in row_oids.iter() {
+                cell::DataCellEntry::new(self.schema.oid, self.oid, *row_oid, value.clone()).set_transact(&trans)?;
+            }
+        }
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(row_oids.len() as i64)
+    }
+
+    /// Counts the cells of the column that are blank, in rows that are not in the trash.
+    pub fn count_nulls(&self) -> Result<i64, Error> {
+        let conn = db::open()?;
+        let sql_count: String = match &self.column_type {
+            column_type::ColumnType::Primitive(column_type::Primitive::FileList | column_type::Primitive::RowNumber)
+            | column_type::ColumnType::Multiselect { .. }
+            | column_type::ColumnType::Formula { .. }
+            | column_type::ColumnType::Subreport { .. } => {
+                return Err(Error::AdhocError("Only columns that store a single value can be made non-nullable."));
+            }
+            _ => format!("SELECT COUNT(*) FROM TABLE{} WHERE NOT TRASH AND COLUMN{} IS NULL", self.schema.oid, self.oid),
+        };
+        Ok(conn.query_one(&sql_count, [], |row| row.get(0))?)
+    }
+
+    /// Overwrites the column metadata.
+    pub fn set_transact(&mut self, trans: &Transaction) -> Result<(), Error> {
         // Find the column type OID
-        let old_column: Self = Self::get(self.oid)?;
+        let old_column: Self = Self::get_transact(trans, self.oid)?;
         // Trash the old column
         trans.execute(
             "UPDATE METADATA_COLUMN SET TRASH = TRUE WHERE OID = ?1",
@@ -444,14 +1657,29 @@ impl FullMetadata {
         )?;
 
         // Create a new column
-        self._create(&trans)?;
+        self.create_transact(&trans)?;
+
+        // Keep the conditional formatting rules of the old column
+        trans.execute(
+            "INSERT INTO METADATA_COLUMN_FORMAT_RULE (COLUMN_OID, CONDITION, STYLE) SELECT ?1, CONDITION, STYLE FROM METADATA_COLUMN_FORMAT_RULE WHERE COLUMN_OID = ?2 ORDER BY OID",
+            params![self.oid, old_column.oid],
+        )?;
 
-        if old_column.column_type == self.column_type {
+        // An Object column converted into a Select column of the same table keeps referencing the same rows
+        let is_promoted_object: bool = match (&old_column.column_type, &self.column_type) {
+            (
+                column_type::ColumnType::Object { table_oid: old_table_oid, .. },
+                column_type::ColumnType::Select { table_oid, .. },
+            ) => old_table_oid == table_oid,
+            _ => false,
+        };
+
+        if old_column.column_type == self.column_type || is_promoted_object {
             // Do a batch update to copy over the data from the old column
             match self.column_type {
                 column_type::ColumnType::Multiselect { table_oid, .. } => {
                     let sql_insert: String = format!(
-                        "INSERT INTO MULTISELECT{} (TABLE{}_OID, TABLE{}_OID) SELECT TABLE{}_OID, TABLE{}_OID FROM MULTISELECT{}",
+                        "INSERT INTO MULTISELECT{} (TABLE{}_OID, TABLE{}_OID, ORDERING) SELECT TABLE{}_OID, TABLE{}_OID, ORDERING FROM MULTISELECT{}",
                         self.oid,
                         table_oid,
                         self.schema.oid,
@@ -461,6 +1689,17 @@ impl FullMetadata {
                     );
                     trans.execute(&sql_insert, [])?;
                 }
+                column_type::ColumnType::Primitive(column_type::Primitive::FileList) => {
+                    let sql_insert: String = format!(
+                        "INSERT INTO FILELIST{} (TABLE{}_OID, FILE_OID, ORDERING) SELECT TABLE{}_OID, FILE_OID, ORDERING FROM FILELIST{}",
+                        self.oid,
+                        self.schema.oid,
+                        self.schema.oid,
+                        old_column.oid
+                    );
+                    trans.execute(&sql_insert, [])?;
+                }
+                column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {} // Do nothing, because column is virtual
                 column_type::ColumnType::Primitive(_)
                 | column_type::ColumnType::Object { .. }
                 | column_type::ColumnType::Select { .. } => {
@@ -479,6 +1718,7 @@ impl FullMetadata {
                     match prim {
                         column_type::Primitive::PlainText 
                         | column_type::Primitive::MarkdownText
+                        | column_type::Primitive::LongText
                         | column_type::Primitive::JsonText
                         | column_type::Primitive::XmlText => {
                             // Do batch update, because there shouldn't be any chance of failure
@@ -535,6 +1775,32 @@ impl FullMetadata {
                             );
                             trans.execute(&sql_update, [])?;
                         }
+                        column_type::Primitive::Percentage => {
+                            // Do batch update, because there shouldn't be any chance of failure
+                            // Labels ending in % are divided by 100, while bare numbers are kept as the stored fraction
+                            let sql_update: String = format!(
+                                "
+                                UPDATE TABLE{} AS t 
+                                SET COLUMN{} = 
+                                    CASE 
+                                        WHEN TRIM(l.COLUMN{}_LABEL) GLOB '*%' 
+                                            THEN CAST(RTRIM(TRIM(l.COLUMN{}_LABEL), '%') AS REAL) / 100.0
+                                        ELSE COALESCE(
+                                            NULLIF(CAST(l.COLUMN{}_LABEL AS REAL), 0.0),
+                                            IF(l.COLUMN{}_LABEL LIKE '0%', 0.0, NULL)
+                                        )
+                                    END
+                                FROM SCHEMA{}_VIEW l 
+                                WHERE t.OID = l.OID
+                                ",
+                                self.schema.oid, 
+                                self.oid, 
+                                self.oid, self.oid, 
+                                self.oid, self.oid,
+                                self.schema.oid
+                            );
+                            trans.execute(&sql_update, [])?;
+                        }
                         column_type::Primitive::Date => {
                             // Do batch update, because there shouldn't be any chance of failure
                             let sql_update: String = format!(
@@ -599,15 +1865,24 @@ impl FullMetadata {
                                     match old_prim {
                                         column_type::Primitive::File
                                         | column_type::Primitive::Image => Some(old_column_expr),
+                                        column_type::Primitive::FileList => Some(format!(
+                                            "(SELECT f.FILE_OID FROM FILELIST{} f WHERE f.TABLE{}_OID = t.OID ORDER BY f.ORDERING LIMIT 1)",
+                                            old_column.oid,
+                                            self.schema.oid
+                                        )), // Keep only the first file in the list
                                         column_type::Primitive::Boolean
                                         | column_type::Primitive::Date
                                         | column_type::Primitive::Datetime
                                         | column_type::Primitive::Integer
                                         | column_type::Primitive::Number
+                                        | column_type::Primitive::Percentage
                                         | column_type::Primitive::PlainText
                                         | column_type::Primitive::MarkdownText
+                                        | column_type::Primitive::LongText
                                         | column_type::Primitive::JsonText 
-                                        | column_type::Primitive::XmlText => None, // No conversion from other primitive to File
+                                        | column_type::Primitive::XmlText
+                                        | column_type::Primitive::Point
+                                        | column_type::Primitive::RowNumber => None, // No conversion from other primitive to File
                                     }
                                 }
                                 _ => None, // No data to transfer to File column
@@ -620,6 +1895,43 @@ impl FullMetadata {
                                 trans.execute(&sql_update, [])?;
                             }
                         }
+                        column_type::Primitive::Point => {
+                            // Update each row individually, leaving any label that is not a valid point empty
+                            let sql_select: String = format!(
+                                "SELECT OID, COLUMN{}_LABEL AS LABEL FROM SCHEMA{}_VIEW WHERE COLUMN{}_LABEL IS NOT NULL",
+                                self.oid,
+                                self.schema.oid,
+                                self.oid
+                            );
+                            let sql_update: String = format!(
+                                "UPDATE TABLE{} SET COLUMN{} = ?1 WHERE OID = ?2",
+                                self.schema.oid, self.oid
+                            );
+                            let mut labels: Vec<(i64, String)> = Vec::new();
+                            for row_result in trans.prepare(&sql_select)?.query_map([], |row| Ok((row.get::<_, i64>("OID")?, row.get::<_, String>("LABEL")?)))? {
+                                labels.push(row_result?);
+                            }
+                            for (row_oid, label) in labels {
+                                if let Ok((lat, lng)) = cell::parse_point(&label) {
+                                    trans.execute(&sql_update, params![format!("[{lat},{lng}]"), row_oid])?;
+                                }
+                            }
+                        }
+                        column_type::Primitive::FileList => {
+                            // Only a previous File or Image column has files to attach, each becoming a single-file list
+                            if let column_type::ColumnType::Primitive(column_type::Primitive::File | column_type::Primitive::Image) = &old_column.column_type {
+                                let sql_insert: String = format!(
+                                    "INSERT INTO FILELIST{} (TABLE{}_OID, FILE_OID) SELECT t.OID, t.COLUMN{} FROM TABLE{} t WHERE t.COLUMN{} IS NOT NULL",
+                                    self.oid,
+                                    self.schema.oid,
+                                    old_column.oid,
+                                    self.schema.oid,
+                                    old_column.oid
+                                );
+                                trans.execute(&sql_insert, [])?;
+                            }
+                        }
+                        column_type::Primitive::RowNumber => {} // Do nothing, because column is virtual
                     }
                 }
                 column_type::ColumnType::Object { table_oid, .. } 
@@ -669,11 +1981,105 @@ impl FullMetadata {
             }
         }
 
-        // Commit the transaction
-        trans.commit()?;
+        // Verify that the copied data does not violate case-insensitive uniqueness
+        if let Some(value) = self.find_case_insensitive_duplicate_transact(&trans)? {
+            return Err(Error::DuplicateValue {
+                column_name: self.name.clone(),
+                value,
+            });
+        }
+
+        // Verify that the copied data can identify the rows, if the column has just become a key column
+        if self.is_primary_key && !old_column.is_primary_key {
+            self.check_primary_key_transact(&trans)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that every row has a value in the key column, and that no two rows share the same values in all key columns of the table.
+    fn check_primary_key_transact(&self, conn: &Connection) -> Result<(), Error> {
+        if !is_validation_enabled() {
+            return Ok(());
+        }
+
+        // Only key columns that store their values in the table are checked
+        let mut key_column_oids: Vec<i64> = Vec::new();
+        for row_result in conn
+            .prepare(
+                "
+                SELECT c.OID 
+                FROM METADATA_COLUMN c 
+                WHERE c.SCHEMA_OID = ?1 AND c.IS_PRIMARY_KEY AND NOT c.TRASH 
+                    AND EXISTS (SELECT 1 FROM pragma_table_info('TABLE' || ?1) p WHERE p.name = 'COLUMN' || c.OID)
+                ",
+            )?
+            .query_map(params![self.schema.oid], |row| row.get::<_, i64>("OID"))?
+        {
+            key_column_oids.push(row_result?);
+        }
+        if !key_column_oids.contains(&self.oid) {
+            return Ok(());
+        }
+
+        // Check for blank cells
+        let sql_count_nulls: String = format!(
+            "SELECT COUNT(*) FROM TABLE{} WHERE NOT TRASH AND COLUMN{} IS NULL",
+            self.schema.oid, self.oid
+        );
+        if conn.query_one(&sql_count_nulls, [], |row| row.get::<_, i64>(0))? > 0 {
+            return Err(Error::AdhocError("The column has blank cells, so it cannot be made a primary key."));
+        }
+
+        // Check for rows that cannot be told apart by their key columns
+        let sql_select_duplicate: String = format!(
+            "
+            SELECT CAST(COLUMN{} AS TEXT) AS VALUE 
+            FROM TABLE{} 
+            WHERE NOT TRASH 
+            GROUP BY {} 
+            HAVING COUNT(*) > 1 
+            LIMIT 1
+            ",
+            self.oid,
+            self.schema.oid,
+            key_column_oids.iter().map(|column_oid| format!("COLUMN{column_oid}")).collect::<Vec<String>>().join(", ")
+        );
+        if let Some(value) = conn.query_one(&sql_select_duplicate, [], |row| row.get::<_, String>("VALUE")).optional()? {
+            return Err(Error::DuplicateValue {
+                column_name: self.name.clone(),
+                value,
+            });
+        }
         Ok(())
     }
 
+    /// Gets the Object column that embeds rows of a table, if any.
+    /// Fails if more than one Object column embeds rows of the table, since the owner is then ambiguous.
+    pub fn get_object_owner(table_oid: i64) -> Result<Option<Self>, Error> {
+        let conn = db::open()?;
+        let mut column_oids: Vec<i64> = Vec::new();
+        for row_result in conn
+            .prepare(
+                "
+                SELECT c.OID
+                FROM METADATA_COLUMN_VIEW c
+                INNER JOIN METADATA_COLUMN_TYPE__OBJECT o ON o.OID = c.TYPE_OID
+                WHERE o.TABLE_OID = ?1
+                ORDER BY c.OID
+                LIMIT 2
+                ",
+            )?
+            .query_map(params![table_oid], |row| row.get::<_, i64>("OID"))?
+        {
+            column_oids.push(row_result?);
+        }
+        match column_oids.as_slice() {
+            [] => Ok(None),
+            [column_oid] => Ok(Some(Self::get_transact(&conn, *column_oid)?)),
+            _ => Err(Error::AdhocError("More than one Object column embeds rows of this table.")),
+        }
+    }
+
     /// Sets only the CSS style of the column.
     pub fn set_style(&mut self, new_style: String) -> Result<(), Error> {
         let mut conn = db::open()?;
@@ -729,4 +2135,395 @@ impl FullMetadata {
         trans.commit()?;
         Ok(())
     }
+
+    /// Renumbers the orderings of the columns of a table and its master tables into a dense sequence starting at 0, keeping their relative order.
+    /// Columns with the same ordering are kept in the order they were created.
+    /// The columns of other tables are left as they are, including the columns that inheriting tables add.
+    /// Returns the number of columns whose ordering changed, and the schemas that those columns belong to.
+    pub fn normalize_orderings(table_oid: i64) -> Result<(usize, Vec<i64>), Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        if !trans.query_one(
+            "SELECT EXISTS (SELECT 1 FROM METADATA_TABLE WHERE OID = ?1)",
+            params![table_oid],
+            |row| row.get::<_, bool>(0),
+        )? {
+            return Err(Error::TableNotFound { table_oid });
+        }
+
+        // Renumber the columns displayed in the table in a single update, noting the schema of each column that changed
+        let changed_schema_oids: Vec<i64> = trans
+            .prepare(
+                "
+                UPDATE METADATA_COLUMN AS c
+                SET ORDERING = n.NEW_ORDERING
+                FROM (
+                    SELECT
+                        col.OID,
+                        ROW_NUMBER() OVER (ORDER BY col.ORDERING, col.OID) - 1 AS NEW_ORDERING
+                    FROM METADATA_COLUMN col
+                    INNER JOIN METADATA_SCHEMA_COLUMN_VIEW sc ON sc.COLUMN_OID = col.OID
+                    WHERE sc.SCHEMA_OID = ?1 AND sc.IS_REQUIRED
+                ) n
+                WHERE n.OID = c.OID AND c.ORDERING <> n.NEW_ORDERING
+                RETURNING SCHEMA_OID
+                ",
+            )?
+            .query_map(params![table_oid], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+        let changed_count: usize = changed_schema_oids.len();
+        let mut affected_schema_oids: Vec<i64> = changed_schema_oids;
+        affected_schema_oids.sort();
+        affected_schema_oids.dedup();
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok((changed_count, affected_schema_oids))
+    }
+
+    /// Exchanges the orderings of two columns.
+    pub fn swap_ordering(schema_oid: i64, column_oid_a: i64, column_oid_b: i64) -> Result<(), Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+
+        // Get the current orderings of both columns, checking that both are displayed in the schema
+        let get_ordering = |column_oid: i64| -> Result<i64, Error> {
+            trans
+                .query_one(
+                    "
+                    SELECT ORDERING FROM METADATA_COLUMN 
+                    WHERE OID = ?2 AND EXISTS (SELECT 1 FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND COLUMN_OID = ?2)
+                    ",
+                    params![schema_oid, column_oid],
+                    |row| row.get::<_, i64>("ORDERING"),
+                )
+                .optional()?
+                .ok_or(Error::AdhocError("Only two columns of the same table or report can be swapped."))
+        };
+        let ordering_a: i64 = get_ordering(column_oid_a)?;
+        let ordering_b: i64 = get_ordering(column_oid_b)?;
+
+        // Exchange the orderings in a single update
+        trans.execute(
+            "UPDATE METADATA_COLUMN SET ORDERING = CASE OID WHEN ?1 THEN ?4 ELSE ?3 END WHERE OID IN (?1, ?2)",
+            params![column_oid_a, column_oid_b, ordering_a, ordering_b],
+        )?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::row;
+    use crate::data::testing;
+
+    fn get_ordering(column_oid: i64) -> i64 {
+        testing::query_one(&format!("SELECT ORDERING FROM METADATA_COLUMN WHERE OID = {column_oid}"))
+    }
+
+    #[test]
+    fn swap_ordering_exchanges_columns_of_the_same_table() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid_a: i64 = testing::create_column(table_oid, "A", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let column_oid_b: i64 = testing::create_column(table_oid, "B", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let (ordering_a, ordering_b) = (get_ordering(column_oid_a), get_ordering(column_oid_b));
+
+        FullMetadata::swap_ordering(table_oid, column_oid_a, column_oid_b).unwrap();
+        assert_eq!(get_ordering(column_oid_a), ordering_b);
+        assert_eq!(get_ordering(column_oid_b), ordering_a);
+    }
+
+    #[test]
+    fn swap_ordering_rejects_columns_of_another_table() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let other_table_oid: i64 = testing::create_table("Other Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "A", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let other_column_oid: i64 = testing::create_column(other_table_oid, "B", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let (ordering, other_ordering) = (get_ordering(column_oid), get_ordering(other_column_oid));
+
+        assert!(FullMetadata::swap_ordering(table_oid, column_oid, other_column_oid).is_err());
+        assert_eq!(get_ordering(column_oid), ordering);
+        assert_eq!(get_ordering(other_column_oid), other_ordering);
+    }
+
+    fn make_primary_key(column_oid: i64) -> Result<(), Error> {
+        let mut column: FullMetadata = FullMetadata::get(column_oid).unwrap();
+        column.is_primary_key = true;
+        column.set()
+    }
+
+    #[test]
+    fn making_a_column_the_primary_key_rejects_blank_cells() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let row_oid: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, column_oid, row_oid, cell::DataCellValue::Text(Some(String::from("A"))));
+        testing::insert_row(table_oid);
+
+        assert!(make_primary_key(column_oid).is_err());
+        assert!(!testing::query_one::<bool>(&format!("SELECT IS_PRIMARY_KEY FROM METADATA_COLUMN WHERE SCHEMA_OID = {table_oid} AND NOT TRASH")));
+    }
+
+    #[test]
+    fn making_a_column_the_primary_key_rejects_duplicate_values() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        for _ in 0..2 {
+            let row_oid: i64 = testing::insert_row(table_oid);
+            testing::set_cell(table_oid, column_oid, row_oid, cell::DataCellValue::Text(Some(String::from("A"))));
+        }
+
+        match make_primary_key(column_oid) {
+            Err(Error::DuplicateValue { column_name, value }) => {
+                assert_eq!(column_name, "Name");
+                assert_eq!(value, "A");
+            },
+            result => panic!("Expected a duplicate value error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn making_a_column_the_primary_key_accepts_unique_values() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        for name in ["A", "B"] {
+            let row_oid: i64 = testing::insert_row(table_oid);
+            testing::set_cell(table_oid, column_oid, row_oid, cell::DataCellValue::Text(Some(String::from(name))));
+        }
+
+        make_primary_key(column_oid).unwrap();
+        assert!(testing::query_one::<bool>(&format!("SELECT IS_PRIMARY_KEY FROM METADATA_COLUMN WHERE SCHEMA_OID = {table_oid} AND NOT TRASH")));
+    }
+
+    #[test]
+    fn creating_a_key_column_makes_it_non_nullable() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let mut column: FullMetadata = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        column.is_primary_key = true;
+        column.create().unwrap();
+
+        assert!(!FullMetadata::get(column.oid).unwrap().is_nullable);
+    }
+
+    #[test]
+    fn cells_written_without_validation_are_found_afterwards() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let mut name_column: FullMetadata = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        name_column.case_insensitive_unique = true;
+        name_column.create().unwrap();
+        let mut data_column: FullMetadata = testing::column_metadata(table_oid, "Data", column_type::ColumnType::Primitive(column_type::Primitive::JsonText));
+        data_column.pretty_print_json = true;
+        data_column.create().unwrap();
+
+        set_validation_enabled(false);
+        let row_oid_a: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, name_column.oid, row_oid_a, cell::DataCellValue::Text(Some(String::from("Sword"))));
+        let row_oid_b: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, name_column.oid, row_oid_b, cell::DataCellValue::Text(Some(String::from("SWORD"))));
+        testing::set_cell(table_oid, data_column.oid, row_oid_b, cell::DataCellValue::Text(Some(String::from("{ not json"))));
+        set_validation_enabled(true);
+
+        let invalid_cells: Vec<(i64, i64)> = find_invalid_cells(None).unwrap()
+            .into_iter()
+            .map(|invalid_cell| (invalid_cell.column_oid, invalid_cell.row_oid))
+            .collect();
+        assert_eq!(invalid_cells, vec![(name_column.oid, row_oid_a), (name_column.oid, row_oid_b), (data_column.oid, row_oid_b)]);
+    }
+
+    #[test]
+    fn valid_cells_are_not_reported() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let mut name_column: FullMetadata = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        name_column.case_insensitive_unique = true;
+        name_column.create().unwrap();
+        for name in ["Sword", "Shield"] {
+            let row_oid: i64 = testing::insert_row(table_oid);
+            testing::set_cell(table_oid, name_column.oid, row_oid, cell::DataCellValue::Text(Some(String::from(name))));
+        }
+
+        assert!(find_invalid_cells(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_object_owner_finds_the_only_object_column_embedding_a_table() {
+        let _db = testing::open_database();
+        let stats_table_oid: i64 = testing::create_table("Stats", &[]);
+        assert!(FullMetadata::get_object_owner(stats_table_oid).unwrap().is_none());
+
+        let monster_table_oid: i64 = testing::create_table("Monster", &[]);
+        let column_oid: i64 = testing::create_column(monster_table_oid, "Stats", column_type::ColumnType::Object { oid: 0, table_oid: stats_table_oid });
+        assert_eq!(FullMetadata::get_object_owner(stats_table_oid).unwrap().map(|column| column.oid), Some(column_oid));
+
+        let hero_table_oid: i64 = testing::create_table("Hero", &[]);
+        testing::create_column(hero_table_oid, "Stats", column_type::ColumnType::Object { oid: 0, table_oid: stats_table_oid });
+        assert!(FullMetadata::get_object_owner(stats_table_oid).is_err());
+    }
+
+    #[test]
+    fn collections_of_rows_cannot_be_created_as_primary_keys() {
+        let _db = testing::open_database();
+        let stats_table_oid: i64 = testing::create_table("Stats", &[]);
+        let table_oid: i64 = testing::create_table("Monster", &[]);
+        for column_type in [
+            column_type::ColumnType::Object { oid: 0, table_oid: stats_table_oid },
+            column_type::ColumnType::Multiselect { oid: 0, table_oid: stats_table_oid },
+            column_type::ColumnType::Primitive(column_type::Primitive::RowNumber),
+        ] {
+            let mut column: FullMetadata = testing::column_metadata(table_oid, "Key", column_type);
+            column.is_primary_key = true;
+            assert!(column.create().is_err());
+        }
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM METADATA_COLUMN WHERE SCHEMA_OID = {table_oid}")), 0);
+    }
+
+    #[test]
+    fn collections_of_rows_cannot_be_edited_into_primary_keys() {
+        let _db = testing::open_database();
+        let stats_table_oid: i64 = testing::create_table("Stats", &[]);
+        let table_oid: i64 = testing::create_table("Monster", &[]);
+        for column_type in [
+            column_type::ColumnType::Object { oid: 0, table_oid: stats_table_oid },
+            column_type::ColumnType::Multiselect { oid: 0, table_oid: stats_table_oid },
+            column_type::ColumnType::Primitive(column_type::Primitive::RowNumber),
+        ] {
+            let column_oid: i64 = testing::create_column(table_oid, "Key", column_type);
+            assert!(make_primary_key(column_oid).is_err());
+            assert!(!FullMetadata::get(column_oid).unwrap().is_primary_key);
+        }
+    }
+
+    #[test]
+    fn integer_formats_parse_entered_values() {
+        for format in [IntegerFormat::Plain, IntegerFormat::Thousands, IntegerFormat::Hex, IntegerFormat::ZeroPadded { width: 8 }] {
+            assert_eq!(format.parse("").unwrap(), None);
+            assert_eq!(format.parse("0x1F").unwrap(), Some(31));
+            assert_eq!(format.parse("-0x1F").unwrap(), Some(-31));
+            assert_eq!(format.parse("0x7FFFFFFFFFFFFFFF").unwrap(), Some(i64::MAX));
+            assert_eq!(format.parse("-0x8000000000000000").unwrap(), Some(i64::MIN));
+            assert!(format.parse("0x8000000000000000").is_err());
+            assert!(format.parse("0xFFFFFFFFFFFFFFFF").is_err());
+            assert!(format.parse("0xG").is_err());
+        }
+
+        // Decimal values, with thousands separators and leading zeroes
+        for format in [IntegerFormat::Plain, IntegerFormat::Thousands, IntegerFormat::ZeroPadded { width: 8 }] {
+            assert_eq!(format.parse(" 1,234,567 ").unwrap(), Some(1234567));
+            assert_eq!(format.parse("0001234").unwrap(), Some(1234));
+            assert_eq!(format.parse("-42").unwrap(), Some(-42));
+            assert_eq!(format.parse("9223372036854775807").unwrap(), Some(i64::MAX));
+            assert!(format.parse("9223372036854775808").is_err());
+            assert!(format.parse("1e3").is_err());
+            assert!(format.parse("1.5").is_err());
+            assert!(format.parse("inf").is_err());
+        }
+
+        // Hexadecimal values do not need a prefix when the column displays hexadecimal
+        assert_eq!(IntegerFormat::Hex.parse("ff").unwrap(), Some(255));
+        assert_eq!(IntegerFormat::Hex.parse("-ff").unwrap(), Some(-255));
+        assert!(IntegerFormat::Hex.parse("10000000000000000").is_err());
+    }
+
+    #[test]
+    fn hex_integer_labels_can_be_entered_again() {
+        let _db = testing::open_database();
+        let conn = db::open().unwrap();
+        for value in [0, 255, -255, i64::MAX] {
+            let sql_label: String = format!("SELECT {}", IntegerFormat::Hex.construct_label_expr(&value.to_string()));
+            let label: String = conn.query_one(&sql_label, [], |row| row.get(0)).unwrap();
+            assert_eq!(IntegerFormat::Hex.parse(&label).unwrap(), Some(value));
+        }
+    }
+
+    #[test]
+    fn removed_format_rule_can_be_added_back_under_its_oid() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let column_oid: i64 = testing::create_column(
+            table_oid,
+            "Count",
+            column_type::ColumnType::Primitive(column_type::Primitive::Integer),
+        );
+
+        let rule_oid: i64 = FullMetadata::get_next_format_rule_oid().unwrap();
+        FullMetadata::add_format_rule(&FormatRule {
+            oid: rule_oid,
+            column_oid,
+            condition: FormatCondition::LessThan { value: 0.0 },
+            style: String::from("color: red;"),
+        })
+        .unwrap();
+        assert_eq!(FullMetadata::get_next_format_rule_oid().unwrap(), rule_oid + 1);
+
+        let rule: FormatRule = FullMetadata::remove_format_rule(rule_oid).unwrap();
+        assert_eq!((rule.oid, rule.column_oid, rule.style.as_str()), (rule_oid, column_oid, "color: red;"));
+        assert!(FullMetadata::get_format_rules(column_oid).unwrap().is_empty());
+        assert!(FullMetadata::remove_format_rule(rule_oid).is_err());
+
+        FullMetadata::add_format_rule(&rule).unwrap();
+        let rules: Vec<FormatRule> = FullMetadata::get_format_rules(column_oid).unwrap();
+        assert_eq!(rules.iter().map(|rule| rule.oid).collect::<Vec<i64>>(), vec![rule_oid]);
+    }
+
+    #[test]
+    fn normalizing_orderings_renumbers_only_the_table_and_its_master_tables() {
+        let _db = testing::open_database();
+        let creature_table_oid: i64 = testing::create_table("Creature", &[]);
+        let goblin_table_oid: i64 = testing::create_table("Goblin", &[creature_table_oid]);
+        let troll_table_oid: i64 = testing::create_table("Troll", &[]);
+        let mut column_oids: Vec<i64> = Vec::new();
+        for (table_oid, name, ordering) in [(creature_table_oid, "Name", 10), (troll_table_oid, "Name", 20), (goblin_table_oid, "Age", 30)] {
+            let mut column: FullMetadata = testing::column_metadata(table_oid, name, column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+            column.create().unwrap();
+            db::open().unwrap().execute("UPDATE METADATA_COLUMN SET ORDERING = ?1 WHERE OID = ?2", params![ordering, column.oid]).unwrap();
+            column_oids.push(column.oid);
+        }
+        let get_orderings = || -> String {
+            testing::query_one(&format!(
+                "SELECT GROUP_CONCAT(ORDERING, ',') FROM (SELECT ORDERING FROM METADATA_COLUMN WHERE OID IN ({}) ORDER BY OID)",
+                column_oids.iter().map(|oid| oid.to_string()).collect::<Vec<String>>().join(",")
+            ))
+        };
+
+        // The columns of the unrelated table are left in place
+        let (changed_count, affected_schema_oids) = FullMetadata::normalize_orderings(goblin_table_oid).unwrap();
+        assert_eq!(changed_count, 2);
+        assert_eq!(affected_schema_oids, vec![creature_table_oid, goblin_table_oid]);
+        assert_eq!(get_orderings(), "0,20,1");
+
+        assert_eq!(FullMetadata::normalize_orderings(troll_table_oid).unwrap(), (1, vec![troll_table_oid]));
+        assert_eq!(get_orderings(), "0,0,1");
+
+        // Nothing changes when the orderings are already normalized
+        assert_eq!(FullMetadata::normalize_orderings(goblin_table_oid).unwrap(), (0, Vec::new()));
+    }
+
+    #[test]
+    fn making_a_column_non_nullable_fills_only_the_blank_cells_of_rows_outside_the_trash() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let named_row_oid: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, column_oid, named_row_oid, cell::DataCellValue::Text(Some(String::from("A"))));
+        let blank_row_oid: i64 = testing::insert_row(table_oid);
+        let trashed_row_oid: i64 = testing::insert_row(table_oid);
+        row::trash(table_oid, trashed_row_oid).unwrap();
+
+        let mut column: FullMetadata = FullMetadata::get(column_oid).unwrap();
+        assert_eq!(column.count_nulls().unwrap(), 1);
+        assert_eq!(column.set_not_null(Some("Blank")).unwrap(), 1);
+        let filled_row_oids: String = testing::query_one(&format!("SELECT GROUP_CONCAT(OID) FROM TABLE{table_oid} WHERE COLUMN{} = 'Blank'", column.oid));
+        assert_eq!(filled_row_oids, blank_row_oid.to_string());
+    }
 }
@@ -16,7 +16,12 @@ pub enum Primitive {
     Image,
     JsonText,
     MarkdownText,
-    XmlText
+    XmlText,
+    Percentage,
+    LongText,
+    FileList,
+    Point,
+    RowNumber
 }
 
 impl Primitive {
@@ -33,7 +38,12 @@ impl Primitive {
             Self::Image => -8,
             Self::JsonText => -9,
             Self::MarkdownText => -10,
-            Self::XmlText => -11
+            Self::XmlText => -11,
+            Self::Percentage => -12,
+            Self::LongText => -13,
+            Self::FileList => -14,
+            Self::Point => -15,
+            Self::RowNumber => -16
         }
     }
 
@@ -50,7 +60,12 @@ impl Primitive {
             Self::Number => "Number",
             Self::PlainText => "TextPlain",
             Self::MarkdownText => "TextMarkdown",
-            Self::XmlText => "TextXml"
+            Self::XmlText => "TextXml",
+            Self::Percentage => "Percentage",
+            Self::LongText => "TextLong",
+            Self::FileList => "FileList",
+            Self::Point => "Point",
+            Self::RowNumber => "RowNumber"
         }
     }
 }
@@ -166,6 +181,8 @@ impl ColumnType {
                     Ok(Self::Primitive(Primitive::Integer))
                 } else if mode == "number" {
                     Ok(Self::Primitive(Primitive::Number))
+                } else if mode == "percentage" {
+                    Ok(Self::Primitive(Primitive::Percentage))
                 } else if mode == "checkbox" {
                     Ok(Self::Primitive(Primitive::Boolean))
                 } else if mode == "date" {
@@ -178,6 +195,14 @@ impl ColumnType {
                     Ok(Self::Primitive(Primitive::Image))
                 } else if mode == "JSON" {
                     Ok(Self::Primitive(Primitive::JsonText))
+                } else if mode == "longText" {
+                    Ok(Self::Primitive(Primitive::LongText))
+                } else if mode == "fileList" {
+                    Ok(Self::Primitive(Primitive::FileList))
+                } else if mode == "point" {
+                    Ok(Self::Primitive(Primitive::Point))
+                } else if mode == "rowNumber" {
+                    Ok(Self::Primitive(Primitive::RowNumber))
                 } else {
                     Ok(Self::Primitive(Primitive::PlainText))
                 }
@@ -1 +1,81 @@
+use crate::util::channel::Sender;
+use crate::util::error::Error;
+use crate::util::process::Progress;
+use serde::Deserialize;
+use serde_json::Value;
+
+mod csv;
 mod json;
+pub(crate) use json::{REFERENCE_LABEL_KEY, REFERENCE_OID_KEY};
+
+/// The name of the event reporting the progress of an export.
+pub const EXPORT_PROGRESS_SIGNAL: &'static str = "export-progress";
+
+/// The format of an exported file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// The format of a single row copied as text.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RowExportFormat {
+    Json,
+    Text,
+}
+
+/// Exports the rows of a report, including its formula and subreport columns, to a file.
+/// Progress is reported as the number of rows exported.
+pub fn export_report(
+    report_oid: i64,
+    format: ExportFormat,
+    filepath: String,
+    progress: Sender<Progress>,
+) -> Result<(), Error> {
+    match format {
+        ExportFormat::Csv => csv::export_report(filepath, report_oid, progress),
+        ExportFormat::Json => json::export_report(filepath, report_oid, progress),
+    }
+}
+
+/// Exports the rows of a table to a newline-delimited JSON file, with one object per row.
+/// Progress is reported as the number of rows exported.
+pub fn export_table_ndjson(table_oid: i64, filepath: String, progress: Sender<Progress>) -> Result<(), Error> {
+    json::export_table_ndjson(filepath, table_oid, progress)
+}
+
+/// Exports a single row of a table to a string, with both the display value and the underlying value of each reference.
+pub fn export_row(table_oid: i64, row_oid: i64, format: RowExportFormat) -> Result<String, Error> {
+    let row_value: Value = json::export_row(table_oid, row_oid)?;
+    Ok(match format {
+        RowExportFormat::Json => format!("{row_value:#}"),
+        RowExportFormat::Text => {
+            // List each column on its own line
+            let Value::Object(map) = row_value else {
+                return Err(Error::AdhocError("Expected the row to be exported as an object."));
+            };
+            map.iter()
+                .map(|(column_name, value)| format!("{column_name}: {}", format_text_value(value)))
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+    })
+}
+
+/// Formats a value from an exported row as a line of text.
+fn format_text_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::from(""),
+        Value::String(s) => s.clone(),
+        Value::Object(reference) if reference.contains_key(json::REFERENCE_OID_KEY) && reference.contains_key(json::REFERENCE_LABEL_KEY) => {
+            match (&reference[json::REFERENCE_LABEL_KEY], &reference[json::REFERENCE_OID_KEY]) {
+                (Value::String(label), oid) => format!("{label} (OID {oid})"),
+                (_, oid) => format!("(OID {oid})"),
+            }
+        }
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,110 @@
+use crate::data::column;
+use crate::data::export::json::{construct_row_object, get_columns, ReferenceLabels, REFERENCE_LABEL_KEY};
+use crate::util::db;
+use crate::util::channel::Sender;
+use crate::util::error::Error;
+use crate::util::process::{Progress, ProgressTracker};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File as FilesystemFile;
+use std::io::Write;
+
+/// Gets the display value of an exported reference to a row, if the JSON value is one.
+fn to_reference_label(value: &Value) -> Option<String> {
+    match value.get(REFERENCE_LABEL_KEY)? {
+        Value::String(s) => Some(s.clone()),
+        _ => Some(String::new()),
+    }
+}
+
+/// Converts an exported JSON value into the text of a CSV field.
+/// References are written as their display values, while nested rows (e.g. from a subreport) are summarized by their count.
+fn to_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Object(_) if value.get(REFERENCE_LABEL_KEY).is_some() => to_reference_label(value).unwrap_or_default(),
+        Value::Array(arr) => match arr.iter().map(to_reference_label).collect::<Option<Vec<String>>>() {
+            Some(labels) if !arr.is_empty() => labels.join(", "),
+            _ => arr.len().to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+/// Escapes a CSV field, quoting it if it contains a comma, quote, or line break.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace("\"", "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+/// Exports the rows of a report in CSV format.
+pub fn export_report(filepath: String, report_oid: i64, progress: Sender<Progress>) -> Result<(), Error> {
+    let conn = db::open()?;
+    let mut columns_by_schema: HashMap<i64, Vec<column::FullMetadata>> = HashMap::new();
+    let mut reference_labels: ReferenceLabels = HashMap::new();
+    let column_names: Vec<String> = get_columns(&conn, &mut columns_by_schema, &report_oid)?
+        .iter()
+        .map(|c| c.name.clone())
+        .collect();
+
+    // Write the header line
+    let mut csv: String = column_names
+        .iter()
+        .map(|name| escape(name))
+        .collect::<Vec<String>>()
+        .join(",");
+    csv.push_str("\r\n");
+
+    // Write a line for each row of the report
+    let count_sql: String = format!("SELECT COUNT(*) AS ROW_COUNT FROM SCHEMA{report_oid}_VIEW");
+    let row_count: i64 = conn.query_one(&count_sql, [], |row| row.get("ROW_COUNT"))?;
+    let mut progress: ProgressTracker = ProgressTracker::new(progress, row_count);
+    let select_sql: String = format!("SELECT * FROM SCHEMA{report_oid}_VIEW ORDER BY ROW_INDEX");
+    let mut select_stmt = conn.prepare(&select_sql)?;
+    let mut select_rows = select_stmt.query([])?;
+    loop {
+        let Some(row) = select_rows.next()? else {
+            break;
+        };
+
+        let row_object: Value =
+            construct_row_object(&conn, row, &mut columns_by_schema, &mut reference_labels, &report_oid, &None, &None)?;
+        let line: String = column_names
+            .iter()
+            .map(|name| escape(&to_field(row_object.get(name).unwrap_or(&Value::Null))))
+            .collect::<Vec<String>>()
+            .join(",");
+        csv.push_str(&line);
+        csv.push_str("\r\n");
+        progress.increment()?;
+    }
+
+    // Create or open the file for writing
+    let mut file = match FilesystemFile::create(&filepath) {
+        Ok(f) => f,
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "create file",
+                path: Some(filepath),
+                error: e,
+            });
+        }
+    };
+
+    // Write the contents of the CSV document into the file
+    match file.write_all(csv.as_bytes()) {
+        Ok(_) => {}
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "write to file",
+                path: Some(filepath),
+                error: e,
+            });
+        }
+    }
+    Ok(())
+}
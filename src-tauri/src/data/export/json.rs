@@ -1,12 +1,14 @@
 use crate::data::datasource::Datasource;
 use crate::data::{column, column_type};
 use crate::util::db;
+use crate::util::channel::Sender;
 use crate::util::error::Error;
+use crate::util::process::{Progress, ProgressTracker};
 use rusqlite::{params, Connection, OptionalExtension, Row};
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 use std::fs::File as FilesystemFile;
-use std::io::{BufReader, Read, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
 enum ExportPolymorphism {
@@ -29,8 +31,49 @@ enum ExportSchema {
     },
 }
 
+/// The name of the key holding the OID of a referenced row.
+pub(crate) const REFERENCE_OID_KEY: &'static str = "$oid";
+
+/// The name of the key holding the display value of a referenced row.
+pub(crate) const REFERENCE_LABEL_KEY: &'static str = "$label";
+
+/// The display values of referenced rows, keyed by the OIDs of the referenced table and row.
+pub(super) type ReferenceLabels = HashMap<(i64, i64), Option<String>>;
+
+/// Exports a reference to a row as both its OID and its display value, so that it can be matched by either when imported.
+/// Display values are cached, since the same rows tend to be referenced many times.
+fn export_reference(
+    conn: &Connection,
+    reference_labels: &mut ReferenceLabels,
+    table_oid: i64,
+    row_oid: i64,
+) -> Result<Value, Error> {
+    let label: Option<String> = match reference_labels.get(&(table_oid, row_oid)) {
+        Some(label) => label.clone(),
+        None => {
+            let select_sql: String = format!("SELECT COALESCE(PLAIN_LABEL, JSON_LABEL) AS LABEL FROM SCHEMA{table_oid}_LABEL_VIEW WHERE OID = ?1");
+            let label: Option<String> = conn
+                .query_one(&select_sql, params![row_oid], |row| row.get::<_, Option<String>>("LABEL"))
+                .optional()?
+                .flatten();
+            reference_labels.insert((table_oid, row_oid), label.clone());
+            label
+        }
+    };
+    let mut map: Map<String, Value> = Map::new();
+    map.insert(String::from(REFERENCE_OID_KEY), json!(row_oid));
+    map.insert(
+        String::from(REFERENCE_LABEL_KEY),
+        match label {
+            Some(label) => json!(label),
+            None => Value::Null,
+        },
+    );
+    Ok(Value::Object(map))
+}
+
 /// Get the columns of a schema.
-fn get_columns<'a, 'b>(
+pub(super) fn get_columns<'a, 'b>(
     conn: &'a Connection,
     columns_by_schema: &'b mut HashMap<i64, Vec<column::FullMetadata>>,
     schema_oid: &i64,
@@ -51,10 +94,12 @@ fn get_columns<'a, 'b>(
     return Ok(columns_by_schema.get(schema_oid).unwrap());
 }
 
-fn construct_row_object(
+/// Constructs a JSON object from a row of a schema's view, with a key for each column of the schema.
+pub(super) fn construct_row_object(
     conn: &Connection,
     row: &Row<'_>,
     columns_by_schema: &mut HashMap<i64, Vec<column::FullMetadata>>,
+    reference_labels: &mut ReferenceLabels,
     schema_oid: &i64,
     index_column: &Option<String>,
     oid_column: &Option<String>,
@@ -97,13 +142,14 @@ fn construct_row_object(
                 column_type::ColumnType::Primitive(prim) => {
                     let value_ord: String = format!("COLUMN{}_VALUE", c.oid);
                     match prim {
-                        column_type::Primitive::Integer => {
+                        column_type::Primitive::Integer
+                        | column_type::Primitive::RowNumber => {
                             match row.get::<&str, Option<i64>>(&value_ord)? {
                                 Some(value) => json!(value),
                                 None => Value::Null,
                             }
                         }
-                        column_type::Primitive::Number => {
+                        column_type::Primitive::Number | column_type::Primitive::Percentage => {
                             match row.get::<&str, Option<f64>>(&value_ord)? {
                                 Some(value) => json!(value),
                                 None => Value::Null,
@@ -111,17 +157,20 @@ fn construct_row_object(
                         }
                         column_type::Primitive::PlainText
                         | column_type::Primitive::MarkdownText
+                        | column_type::Primitive::LongText
                         | column_type::Primitive::XmlText => {
                             match row.get::<&str, Option<String>>(&value_ord)? {
                                 Some(value) => json!(value),
                                 None => Value::Null,
                             }
                         }
-                        column_type::Primitive::JsonText => {
+                        column_type::Primitive::JsonText
+                        | column_type::Primitive::Point => {
                             match row.get::<&str, Option<String>>(&value_ord)? {
-                                Some(value) => {
-                                    todo!("Parse the JSON string into a Value")
-                                }
+                                Some(value) => match serde_json::from_str::<Value>(&value) {
+                                    Ok(parsed) => parsed,
+                                    Err(_) => json!(value),
+                                },
                                 None => Value::Null,
                             }
                         }
@@ -133,6 +182,21 @@ fn construct_row_object(
                                 None => Value::Null,
                             }
                         }
+                        column_type::Primitive::FileList => {
+                            // Export the label of each attached file
+                            let sql_select: String = format!(
+                                "SELECT f.LABEL FROM FILELIST{} l INNER JOIN METADATA_FILE_VIEW f ON f.OID = l.FILE_OID WHERE l.TABLE{}_OID = ?1 ORDER BY l.ORDERING, l.FILE_OID",
+                                c.oid, c.schema.oid
+                            );
+                            let mut file_labels: Vec<Value> = Vec::new();
+                            for label_result in conn.prepare(&sql_select)?.query_map(params![row.get::<_, i64>("OID")?], |row| row.get::<_, Option<String>>("LABEL"))? {
+                                file_labels.push(match label_result? {
+                                    Some(label) => json!(label),
+                                    None => Value::Null,
+                                });
+                            }
+                            Value::Array(file_labels)
+                        }
                         column_type::Primitive::Boolean => {
                             match row.get::<&str, Option<bool>>(&value_ord)? {
                                 Some(value) => json!(value),
@@ -156,6 +220,7 @@ fn construct_row_object(
                             export_object_row(
                                 &obj_conn,
                                 columns_by_schema,
+                                reference_labels,
                                 &table_oid,
                                 &row_oid,
                                 &None,
@@ -166,25 +231,23 @@ fn construct_row_object(
                         None => Value::Null,
                     }
                 }
-                column_type::ColumnType::Select { .. } => {
+                column_type::ColumnType::Select { table_oid, .. } => {
                     let value_ord: String = format!("COLUMN{}_VALUE", c.oid);
                     match row.get::<&str, Option<i64>>(&value_ord)? {
-                        Some(value) => json!(value),
+                        Some(value) => export_reference(conn, reference_labels, *table_oid, value)?,
                         None => Value::Null,
                     }
                 }
-                column_type::ColumnType::Multiselect { .. } => {
+                column_type::ColumnType::Multiselect { table_oid, .. } => {
                     let value_ord: String = format!("COLUMN{}_VALUE", c.oid);
                     match row.get::<&str, Option<String>>(&value_ord)? {
-                        Some(value) => Value::Array(
-                            value
-                                .split(',')
-                                .filter_map(|s| match i64::from_str_radix(s, 10) {
-                                    Ok(i) => Some(json!(i)),
-                                    Err(_) => None,
-                                })
-                                .collect(),
-                        ),
+                        Some(value) => {
+                            let mut references: Vec<Value> = Vec::new();
+                            for i in value.split(',').filter_map(|s| i64::from_str_radix(s, 10).ok()) {
+                                references.push(export_reference(conn, reference_labels, *table_oid, i)?);
+                            }
+                            Value::Array(references)
+                        }
                         None => Value::Null,
                     }
                 }
@@ -269,14 +332,15 @@ fn construct_row_object(
                             break;
                         };
 
-                        // Add the object to the array of the schema rows
+                        // Add the object to the array of the subreport rows
                         array_rows.push(construct_row_object(
                             conn,
                             row,
                             columns_by_schema,
-                            &schema_oid,
-                            &index_column,
-                            &oid_column,
+                            reference_labels,
+                            report_oid,
+                            &None,
+                            &None,
                         )?);
                     }
                     json!(array_rows)
@@ -292,6 +356,7 @@ fn construct_row_object(
 fn export_object_row(
     conn: &Connection,
     columns_by_schema: &mut HashMap<i64, Vec<column::FullMetadata>>,
+    reference_labels: &mut ReferenceLabels,
     table_oid: &i64,
     row_oid: &i64,
     index_column: &Option<String>,
@@ -319,6 +384,7 @@ fn export_object_row(
                     conn,
                     row,
                     columns_by_schema,
+                    reference_labels,
                     &table_oid,
                     index_column,
                     oid_column,
@@ -344,10 +410,12 @@ fn export_object_row(
 fn export_schema_individual(
     conn: &Connection,
     columns_by_schema: &mut HashMap<i64, Vec<column::FullMetadata>>,
+    reference_labels: &mut ReferenceLabels,
     table_oid: i64,
     index_column: Option<String>,
     oid_column: Option<String>,
     type_column: Option<String>,
+    progress: &mut ProgressTracker,
 ) -> Result<Value, Error> {
     let mut array_rows: Vec<Value> = Vec::new();
 
@@ -359,12 +427,14 @@ fn export_schema_individual(
         array_rows.push(export_object_row(
             conn,
             columns_by_schema,
+            reference_labels,
             &table_oid,
             &row_oid,
             &index_column,
             &oid_column,
             &type_column,
         )?);
+        progress.increment()?;
     }
     Ok(json!(array_rows))
 }
@@ -373,9 +443,11 @@ fn export_schema_individual(
 fn export_schema_batch(
     conn: &Connection,
     columns_by_schema: &mut HashMap<i64, Vec<column::FullMetadata>>,
+    reference_labels: &mut ReferenceLabels,
     schema_oid: i64,
     index_column: Option<String>,
     oid_column: Option<String>,
+    progress: &mut ProgressTracker,
 ) -> Result<Value, Error> {
     // Query for the rows of the schema
     let select_sql: String = format!("SELECT * FROM SCHEMA{schema_oid}_VIEW ORDER BY ROW_INDEX");
@@ -395,10 +467,12 @@ fn export_schema_batch(
             conn,
             row,
             columns_by_schema,
+            reference_labels,
             &schema_oid,
             &index_column,
             &oid_column,
         )?);
+        progress.increment()?;
     }
     Ok(json!(array_rows))
 }
@@ -406,7 +480,9 @@ fn export_schema_batch(
 fn export_schema(
     conn: &Connection,
     columns_by_schema: &mut HashMap<i64, Vec<column::FullMetadata>>,
+    reference_labels: &mut ReferenceLabels,
     schema: ExportSchema,
+    progress: &mut ProgressTracker,
 ) -> Result<(String, Value), Error> {
     match schema {
         ExportSchema::Table {
@@ -424,9 +500,11 @@ fn export_schema(
                         export_schema_batch(
                             conn,
                             columns_by_schema,
+                            reference_labels,
                             schema_oid,
                             index_column,
                             oid_column,
+                            progress,
                         )?,
                     ))
                 }
@@ -437,10 +515,12 @@ fn export_schema(
                         export_schema_individual(
                             conn,
                             columns_by_schema,
+                            reference_labels,
                             schema_oid,
                             index_column,
                             oid_column,
                             type_column,
+                            progress,
                         )?,
                     ))
                 }
@@ -454,20 +534,39 @@ fn export_schema(
             // Export rows of report in batch
             Ok((
                 schema_name,
-                export_schema_batch(conn, columns_by_schema, schema_oid, index_column, None)?,
+                export_schema_batch(conn, columns_by_schema, reference_labels, schema_oid, index_column, None, progress)?,
             ))
         }
     }
 }
 
 /// Exports schemas in JSON format.
-pub fn export(filepath: String, schemas: Vec<ExportSchema>) -> Result<(), Error> {
-    // Construct the top-level map
+/// Progress is reported as the number of top-level rows exported.
+pub fn export(filepath: String, schemas: Vec<ExportSchema>, progress: Sender<Progress>) -> Result<(), Error> {
+    // Count the rows to be exported
     let conn = db::open()?;
+    let mut total_rows: i64 = 0;
+    for schema in schemas.iter() {
+        let count_sql: String = match schema {
+            ExportSchema::Table {
+                schema_oid,
+                polymorphism: ExportPolymorphism::Yes { .. },
+                ..
+            } => format!("SELECT COUNT(*) AS ROW_COUNT FROM TABLE{schema_oid} WHERE NOT TRASH"),
+            ExportSchema::Table { schema_oid, .. } | ExportSchema::Report { schema_oid, .. } => {
+                format!("SELECT COUNT(*) AS ROW_COUNT FROM SCHEMA{schema_oid}_VIEW")
+            }
+        };
+        total_rows += conn.query_one(&count_sql, [], |row| row.get::<_, i64>("ROW_COUNT"))?;
+    }
+    let mut progress: ProgressTracker = ProgressTracker::new(progress, total_rows);
+
+    // Construct the top-level map
     let mut columns_by_schema: HashMap<i64, Vec<column::FullMetadata>> = HashMap::new();
+    let mut reference_labels: ReferenceLabels = HashMap::new();
     let mut map: Map<String, Value> = Map::new();
     for schema in schemas.into_iter() {
-        let (schema_name, schema_rows) = export_schema(&conn, &mut columns_by_schema, schema)?;
+        let (schema_name, schema_rows) = export_schema(&conn, &mut columns_by_schema, &mut reference_labels, schema, &mut progress)?;
         map.insert(schema_name, schema_rows);
     }
 
@@ -475,19 +574,122 @@ pub fn export(filepath: String, schemas: Vec<ExportSchema>) -> Result<(), Error>
     let json: String = json!(map).to_string();
 
     // Create or open the file for writing
-    let mut file = match FilesystemFile::create(filepath) {
+    let mut file = match FilesystemFile::create(&filepath) {
         Ok(f) => f,
-        Err(_) => {
-            return Err(Error::AdhocError("Unable to open file."));
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "create file",
+                path: Some(filepath),
+                error: e,
+            });
         }
     };
 
     // Write the contents of the JSON document into the file
     match file.write_all(json.as_bytes()) {
         Ok(_) => {}
-        Err(_) => {
-            return Err(Error::AdhocError("Unable to write to file."));
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "write to file",
+                path: Some(filepath),
+                error: e,
+            });
         }
     }
     Ok(())
 }
+
+/// Exports the rows of a report in JSON format.
+pub fn export_report(filepath: String, report_oid: i64, progress: Sender<Progress>) -> Result<(), Error> {
+    let report_name: String = {
+        let conn = db::open()?;
+        conn.query_one(
+            "SELECT NAME FROM METADATA_SCHEMA WHERE OID = ?1",
+            params![report_oid],
+            |row| row.get("NAME"),
+        )?
+    };
+    export(
+        filepath,
+        vec![ExportSchema::Report {
+            schema_oid: report_oid,
+            schema_name: report_name,
+            index_column: None,
+        }],
+        progress,
+    )
+}
+
+/// Exports the rows of a table in newline-delimited JSON format, with each row written as a JSON object on its own line.
+/// Each line is flushed as soon as it is written, so that the file can be consumed while the export is in progress.
+/// Progress is reported as the number of rows exported.
+pub fn export_table_ndjson(filepath: String, table_oid: i64, progress: Sender<Progress>) -> Result<(), Error> {
+    let conn = db::open()?;
+    let count_sql: String = format!("SELECT COUNT(*) AS ROW_COUNT FROM SCHEMA{table_oid}_VIEW");
+    let total_rows: i64 = conn.query_one(&count_sql, [], |row| row.get::<_, i64>("ROW_COUNT"))?;
+    let mut progress: ProgressTracker = ProgressTracker::new(progress, total_rows);
+
+    // Create or open the file for writing
+    let mut writer = match FilesystemFile::create(&filepath) {
+        Ok(f) => BufWriter::new(f),
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "create file",
+                path: Some(filepath),
+                error: e,
+            });
+        }
+    };
+
+    // Query for the rows of the table
+    let select_sql: String = format!("SELECT * FROM SCHEMA{table_oid}_VIEW ORDER BY ROW_INDEX");
+    let mut select_stmt = conn.prepare(&select_sql)?;
+    let mut select_rows = select_stmt.query([])?;
+    let mut columns_by_schema: HashMap<i64, Vec<column::FullMetadata>> = HashMap::new();
+    let mut reference_labels: ReferenceLabels = HashMap::new();
+    loop {
+        let Some(row) = select_rows.next()? else {
+            break;
+        };
+        let row_value: Value = construct_row_object(
+            &conn,
+            row,
+            &mut columns_by_schema,
+            &mut reference_labels,
+            &table_oid,
+            &None,
+            &Some(String::from(REFERENCE_OID_KEY)),
+        )?;
+
+        // Write the row as a single line
+        if let Err(e) = writeln!(writer, "{row_value}").and_then(|_| writer.flush()) {
+            return Err(Error::FileIoError {
+                action: "write to file",
+                path: Some(filepath),
+                error: e,
+            });
+        }
+        progress.increment()?;
+    }
+    Ok(())
+}
+
+/// Exports a single row of a table as a JSON object, including the columns inherited from master tables and the rows of its Object columns.
+pub fn export_row(table_oid: i64, row_oid: i64) -> Result<Value, Error> {
+    let conn = db::open()?;
+    let select_sql: String = format!("SELECT * FROM SCHEMA{table_oid}_VIEW WHERE OID = ?1");
+    let mut select_stmt = conn.prepare(&select_sql)?;
+    let mut select_rows = select_stmt.query(params![row_oid])?;
+    let Some(row) = select_rows.next()? else {
+        return Err(Error::AdhocError("Row does not exist in table."));
+    };
+    construct_row_object(
+        &conn,
+        row,
+        &mut HashMap::new(),
+        &mut HashMap::new(),
+        &table_oid,
+        &None,
+        &Some(String::from(REFERENCE_OID_KEY)),
+    )
+}
@@ -4,9 +4,29 @@ use base64::{prelude::BASE64_STANDARD as base64standard, Engine};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::fs::File as FilesystemFile;
-use std::io::{BufReader, Read, Write};
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::sync::Mutex;
 use std::{collections::btree_map::Entry::Occupied, path::Path};
 
+/// The default for the largest file, in bytes, that will be encoded as base64 and sent to the frontend.
+pub const DEFAULT_MAX_BASE64_FILE_SIZE: i64 = 10 * 1024 * 1024;
+
+/// The largest file, in bytes, that will be encoded as base64 and sent to the frontend.
+/// Larger files should be downloaded instead.
+static MAX_BASE64_FILE_SIZE: Mutex<i64> = Mutex::new(DEFAULT_MAX_BASE64_FILE_SIZE);
+
+/// Sets the largest file, in bytes, that will be encoded as base64 and sent to the frontend.
+pub fn set_max_base64_file_size(max_size: i64) -> Result<(), Error> {
+    if max_size < 0 {
+        return Err(Error::AdhocError("The largest file size for displaying files cannot be negative."));
+    }
+    *MAX_BASE64_FILE_SIZE.lock().unwrap() = max_size;
+    Ok(())
+}
+
+/// The number of bytes copied at a time when uploading a file into the database.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum File {
@@ -53,6 +73,41 @@ impl File {
         })
     }
 
+    /// Gets the size of the file, in bytes.
+    pub fn get_size(&self) -> Result<i64, Error> {
+        let conn = db::open()?;
+        self.get_size_transact(&conn)
+    }
+
+    /// Gets the size of the file, in bytes.
+    pub fn get_size_transact(&self, conn: &Connection) -> Result<i64, Error> {
+        match self {
+            Self::Path { path, .. } => match std::fs::metadata(path) {
+                Ok(metadata) => Ok(metadata.len() as i64),
+                Err(e) => Err(Error::FileIoError {
+                    action: "open file",
+                    path: Some(path.clone()),
+                    error: e,
+                }),
+            },
+            Self::Blob { oid } => Ok(conn.query_one(
+                "SELECT LENGTH(CONTENT) AS SIZE FROM METADATA_FILE__BLOB WHERE OID = ?1",
+                params![oid],
+                |row| row.get::<_, Option<i64>>("SIZE"),
+            )?.unwrap_or(0)),
+        }
+    }
+
+    /// Checks that the file is small enough to be sent to the frontend as a base64 string.
+    fn check_base64_size(&self, conn: &Connection) -> Result<(), Error> {
+        let size: i64 = self.get_size_transact(conn)?;
+        let max_size: i64 = *MAX_BASE64_FILE_SIZE.lock().unwrap();
+        if size > max_size {
+            return Err(Error::FileTooLarge { size, max_size });
+        }
+        Ok(())
+    }
+
     /// Loads the file as a URI (e.g. for an img tag).
     pub fn get_image_src(self) -> Result<String, Error> {
         let conn = db::open()?;
@@ -67,6 +122,9 @@ impl File {
                 return Ok(path.clone());
             }
             Self::Blob { oid } => {
+                // Refuse to encode files that would be too large to send
+                self.check_base64_size(conn)?;
+
                 let blob = conn.blob_open("main", "METADATA_FILE__BLOB", "CONTENT", oid, true)?;
 
                 // Read the BLOB into a buffer
@@ -74,8 +132,12 @@ impl File {
                 let mut buf_reader = BufReader::new(blob);
                 match buf_reader.read_to_end(&mut buf) {
                     Ok(_) => {}
-                    Err(_) => {
-                        return Err(Error::AdhocError("Unable to read stored file."));
+                    Err(e) => {
+                        return Err(Error::FileIoError {
+                            action: "read stored file",
+                            path: None,
+                            error: e,
+                        });
                     }
                 }
 
@@ -98,12 +160,19 @@ impl File {
     pub fn into_base64(self) -> Result<String, Error> {
         let conn = db::open()?;
 
+        // Refuse to encode files that would be too large to send
+        self.check_base64_size(&conn)?;
+
         // Load file content into buffer
         let buf: Vec<u8> = match self {
-            Self::Path { path, .. } => match std::fs::read(path) {
+            Self::Path { path, .. } => match std::fs::read(&path) {
                 Ok(read_buf) => read_buf,
-                Err(_) => {
-                    return Err(Error::AdhocError("Unable to open file."));
+                Err(e) => {
+                    return Err(Error::FileIoError {
+                        action: "open file",
+                        path: Some(path),
+                        error: e,
+                    });
                 }
             },
             Self::Blob { oid } => {
@@ -114,8 +183,12 @@ impl File {
                 let mut buf_reader = BufReader::new(blob);
                 match buf_reader.read_to_end(&mut buf) {
                     Ok(_) => {}
-                    Err(_) => {
-                        return Err(Error::AdhocError("Unable to read stored file."));
+                    Err(e) => {
+                        return Err(Error::FileIoError {
+                            action: "read stored file",
+                            path: None,
+                            error: e,
+                        });
                     }
                 }
                 buf
@@ -132,10 +205,14 @@ impl File {
         let buf: Vec<u8> = match self {
             Self::Path { path, .. } => {
                 // Read the file into a buffer
-                match std::fs::read(path) {
+                match std::fs::read(&path) {
                     Ok(read_buf) => read_buf,
-                    Err(_) => {
-                        return Err(Error::AdhocError("Unable to open file."));
+                    Err(e) => {
+                        return Err(Error::FileIoError {
+                            action: "open file",
+                            path: Some(path),
+                            error: e,
+                        });
                     }
                 }
             }
@@ -149,8 +226,12 @@ impl File {
                 let mut buf: Vec<u8> = Vec::new();
                 match buf_reader.read_to_end(&mut buf) {
                     Ok(_) => {}
-                    Err(_) => {
-                        return Err(Error::AdhocError("Unable to read stored file."));
+                    Err(e) => {
+                        return Err(Error::FileIoError {
+                            action: "read stored file",
+                            path: None,
+                            error: e,
+                        });
                     }
                 }
                 buf
@@ -158,18 +239,26 @@ impl File {
         };
 
         // Load the file from the filesystem
-        let mut file = match FilesystemFile::create(download_to_path) {
+        let mut file = match FilesystemFile::create(&download_to_path) {
             Ok(f) => f,
-            Err(_) => {
-                return Err(Error::AdhocError("Unable to open file."));
+            Err(e) => {
+                return Err(Error::FileIoError {
+                    action: "create file",
+                    path: Some(download_to_path),
+                    error: e,
+                });
             }
         };
 
         // Write the contents of the buffer into the file
         match file.write_all(&buf) {
             Ok(_) => {}
-            Err(_) => {
-                return Err(Error::AdhocError("Unable to write to file."));
+            Err(e) => {
+                return Err(Error::FileIoError {
+                    action: "write to file",
+                    path: Some(download_to_path),
+                    error: e,
+                });
             }
         }
         return Ok(());
@@ -208,14 +297,28 @@ impl File {
                     }
                 };
 
-                // Load the file from the filesystem
-                let buf = match std::fs::read(upload_from_path) {
-                    Ok(read_buf) => read_buf,
-                    Err(_) => {
-                        return Err(Error::AdhocError("Unable to open file."));
+                // Open the file from the filesystem
+                let file = match FilesystemFile::open(&upload_from_path) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        return Err(Error::FileIoError {
+                            action: "open file",
+                            path: Some(upload_from_path),
+                            error: e,
+                        });
+                    }
+                };
+                let file_len: u64 = match file.metadata() {
+                    Ok(metadata) => metadata.len(),
+                    Err(e) => {
+                        return Err(Error::FileIoError {
+                            action: "read the size of file",
+                            path: Some(upload_from_path),
+                            error: e,
+                        });
                     }
                 };
-                let cropped_file_len: i64 = match i64::try_from(buf.len()) {
+                let cropped_file_len: i64 = match i64::try_from(file_len) {
                     Ok(len) => len,
                     Err(_) => {
                         return Err(Error::AdhocError(
@@ -230,18 +333,46 @@ impl File {
                     params![*oid, name, cropped_file_len]
                 )?;
 
-                // Fill the empty blob with the data from the file
+                // Fill the empty blob with the data from the file, one chunk at a time
                 {
                     let mut blob =
                         trans.blob_open("main", "METADATA_FILE__BLOB", "CONTENT", *oid, false)?;
-                    match blob.write_all(&buf) {
-                        Ok(_) => {}
-                        Err(_) => {
-                            return Err(Error::AdhocError(
-                                "Unable to upload file contents to database.",
-                            ));
+                    let mut buf_reader = BufReader::new(file);
+                    let mut chunk: Vec<u8> = vec![0; UPLOAD_CHUNK_SIZE];
+                    let mut copied_len: u64 = 0;
+                    loop {
+                        let read_len: usize = match buf_reader.read(&mut chunk) {
+                            Ok(0) => break,
+                            Ok(read_len) => read_len,
+                            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                            Err(e) => {
+                                return Err(Error::FileIoError {
+                                    action: "read file",
+                                    path: Some(upload_from_path),
+                                    error: e,
+                                });
+                            }
+                        };
+
+                        // The blob cannot grow, so stop if the file has gotten larger since its size was read
+                        copied_len += read_len as u64;
+                        if copied_len > file_len {
+                            return Err(Error::AdhocError("The file changed size while it was being uploaded."));
+                        }
+                        match blob.write_all(&chunk[..read_len]) {
+                            Ok(_) => {}
+                            Err(e) => {
+                                return Err(Error::FileIoError {
+                                    action: "upload file contents to database",
+                                    path: None,
+                                    error: e,
+                                });
+                            }
                         }
                     }
+                    if copied_len < file_len {
+                        return Err(Error::AdhocError("The file changed size while it was being uploaded."));
+                    }
                 }
             }
         }
@@ -251,3 +382,25 @@ impl File {
         return Ok(());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::testing;
+
+    #[test]
+    fn base64_encoding_respects_the_configured_size_limit() {
+        let _db = testing::open_database();
+        let upload_path = std::env::temp_dir().join("base64_size_limit.txt");
+        std::fs::write(&upload_path, "0123456789").unwrap();
+        let mut file: File = File::Blob { oid: 0 };
+        file.upload(String::from(upload_path.to_str().unwrap())).unwrap();
+
+        set_max_base64_file_size(9).unwrap();
+        let too_large = file.clone().into_base64();
+        set_max_base64_file_size(DEFAULT_MAX_BASE64_FILE_SIZE).unwrap();
+        assert!(matches!(too_large, Err(Error::FileTooLarge { size: 10, max_size: 9 })));
+        assert_eq!(file.into_base64().unwrap(), base64standard.encode("0123456789"));
+        assert!(set_max_base64_file_size(-1).is_err());
+    }
+}
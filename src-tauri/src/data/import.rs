@@ -0,0 +1,8 @@
+pub mod csv;
+pub mod json;
+
+/// The name of the event reporting the progress of an import.
+pub const IMPORT_PROGRESS_SIGNAL: &'static str = "import-progress";
+
+/// The name of the event sent when an import is cancelled and rolled back.
+pub const IMPORT_CANCELLED_SIGNAL: &'static str = "import-cancelled";
@@ -0,0 +1,167 @@
+use crate::util::error::Error;
+use serde::Serialize;
+use std::fs::File as FilesystemFile;
+use std::io::Read;
+
+/// The number of bytes read from the start of a file to detect its format.
+const SNIFF_BYTE_COUNT: u64 = 64 * 1024;
+
+/// The number of lines examined to detect the format of a file.
+const SNIFF_LINE_COUNT: usize = 10;
+
+/// The delimiters that are considered when detecting the format of a file.
+const CANDIDATE_DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+/// The detected format of a CSV file.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvFormat {
+    pub delimiter: char,
+    pub quote: char,
+
+    /// True if the first line of the file names the columns, rather than holding data.
+    pub has_header: bool,
+
+    /// The names of the columns, or generated names if the file has no header.
+    pub column_names: Vec<String>,
+}
+
+/// Splits a line into fields, respecting quoted fields and doubled quotes within them.
+fn split_line(line: &str, delimiter: char, quote: char) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut field: String = String::new();
+    let mut in_quotes: bool = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == quote {
+                if chars.peek() == Some(&quote) {
+                    field.push(quote);
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == quote && field.is_empty() {
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(field);
+            field = String::new();
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Detects the quote character by counting fields wrapped in each candidate.
+fn detect_quote(lines: &Vec<&str>) -> char {
+    let count_quoted = |quote: char| -> usize {
+        lines
+            .iter()
+            .flat_map(|line| line.split(CANDIDATE_DELIMITERS))
+            .filter(|field| {
+                let field = field.trim();
+                field.len() >= 2 && field.starts_with(quote) && field.ends_with(quote)
+            })
+            .count()
+    };
+    if count_quoted('\'') > count_quoted('"') {
+        '\''
+    } else {
+        '"'
+    }
+}
+
+/// Detects the delimiter as the candidate that splits every line into the same number of fields, preferring more fields.
+fn detect_delimiter(lines: &Vec<&str>, quote: char) -> char {
+    let mut best: Option<(char, usize)> = None;
+    for delimiter in CANDIDATE_DELIMITERS {
+        let field_counts: Vec<usize> = lines
+            .iter()
+            .map(|line| split_line(line, delimiter, quote).len())
+            .collect();
+        let Some(first_count) = field_counts.first().copied() else {
+            continue;
+        };
+        if first_count < 2 || field_counts.iter().any(|count| *count != first_count) {
+            continue;
+        }
+        if best.is_none_or(|(_, best_count)| first_count > best_count) {
+            best = Some((delimiter, first_count));
+        }
+    }
+    match best {
+        Some((delimiter, _)) => delimiter,
+        None => ',',
+    }
+}
+
+/// Detects the delimiter, quote character, header, and column names of a CSV file from its first few lines.
+pub fn sniff(filepath: String) -> Result<CsvFormat, Error> {
+    // Read the start of the file
+    let file = match FilesystemFile::open(&filepath) {
+        Ok(f) => f,
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "open file",
+                path: Some(filepath),
+                error: e,
+            });
+        }
+    };
+    let mut buf: Vec<u8> = Vec::new();
+    if let Err(e) = file.take(SNIFF_BYTE_COUNT).read_to_end(&mut buf) {
+        return Err(Error::FileIoError {
+            action: "read file",
+            path: Some(filepath),
+            error: e,
+        });
+    }
+
+    // Decode the text, dropping the byte order mark if present
+    let text: String = String::from_utf8_lossy(&buf).into_owned();
+    let text: &str = text.strip_prefix('\u{feff}').unwrap_or(&text);
+
+    // Take the first few non-empty lines, dropping the last line if it may have been cut off
+    let mut lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+    if buf.len() as u64 == SNIFF_BYTE_COUNT && lines.len() > 1 {
+        lines.pop();
+    }
+    lines.truncate(SNIFF_LINE_COUNT);
+    let Some(first_line) = lines.first() else {
+        return Err(Error::AdhocError("The file is empty."));
+    };
+
+    // Detect the format
+    let quote: char = detect_quote(&lines);
+    let delimiter: char = detect_delimiter(&lines, quote);
+    let first_fields: Vec<String> = split_line(first_line, delimiter, quote)
+        .into_iter()
+        .map(|field| String::from(field.trim()))
+        .collect();
+
+    // Treat the first line as a header if its fields are all distinct, non-empty, and non-numeric
+    let has_header: bool = first_fields.iter().enumerate().all(|(idx, field)| {
+        !field.is_empty()
+            && field.parse::<f64>().is_err()
+            && !first_fields[..idx].contains(field)
+    });
+    let column_names: Vec<String> = if has_header {
+        first_fields
+    } else {
+        (1..=first_fields.len())
+            .map(|idx| format!("Column {idx}"))
+            .collect()
+    };
+
+    Ok(CsvFormat {
+        delimiter,
+        quote,
+        has_header,
+        column_names,
+    })
+}
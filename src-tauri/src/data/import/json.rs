@@ -0,0 +1,655 @@
+use crate::data::cell::{DataCellEntry, DataCellObjectBehavior, DataCellValue};
+use crate::data::export::{REFERENCE_LABEL_KEY, REFERENCE_OID_KEY};
+use crate::data::{column, column_type, row, schema, table};
+use crate::util::db;
+use crate::util::channel::Sender;
+use crate::util::error::Error;
+use crate::util::process::{self, Progress, ProgressTracker};
+use rusqlite::{params, OptionalExtension, Transaction};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::File as FilesystemFile;
+use std::io::BufReader;
+
+/// The name of the key identifying the subtype of an exported Object.
+const TYPE_KEY: &'static str = "$type";
+
+/// The number of rows imported between each check for whether the import was cancelled.
+const CANCELLATION_CHECK_INTERVAL: usize = 100;
+
+/// A summary of the outcome of an import.
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// The OIDs of the tables that rows were imported into.
+    pub table_oids: Vec<i64>,
+
+    /// The number of rows that were successfully imported.
+    pub rows_imported: i64,
+
+    /// Issues that did not prevent a row from being imported, such as unresolved references.
+    pub warnings: Vec<String>,
+
+    /// Issues that prevented a row from being imported.
+    pub errors: Vec<String>,
+}
+
+/// A Select or Multiselect cell of an imported row, whose references are resolved once every row has been imported.
+struct PendingReference {
+    column: column::FullMetadata,
+    row_oid: i64,
+    value: Value,
+    location: String,
+}
+
+/// Tracks state over the course of an import.
+struct ImportContext {
+    columns_by_table: HashMap<i64, Vec<column::FullMetadata>>,
+    unknown_columns: HashSet<(i64, String)>,
+    preserve_oids: bool,
+    pending_references: Vec<PendingReference>,
+    report: ImportReport,
+}
+
+impl ImportContext {
+    /// Gets the columns of a table, including those inherited from master tables.
+    fn get_columns(&mut self, trans: &Transaction, table_oid: i64) -> Result<Vec<column::FullMetadata>, Error> {
+        if let Some(cols) = self.columns_by_table.get(&table_oid) {
+            return Ok(cols.clone());
+        }
+
+        let mut cols: Vec<column::FullMetadata> = Vec::new();
+        for row_result in trans.prepare("SELECT COLUMN_OID FROM METADATA_SCHEMA_COLUMN_VIEW WHERE IS_REQUIRED AND SCHEMA_OID = ?1 ORDER BY ORDERING")?.query_map(params![table_oid], |row| row.get::<_, i64>("COLUMN_OID"))? {
+            let column_oid: i64 = row_result?;
+            cols.push(column::FullMetadata::get_transact(trans, column_oid)?);
+        }
+        self.columns_by_table.insert(table_oid, cols.clone());
+        Ok(cols)
+    }
+
+    /// Imports a single row into a table, including any nested Object rows.
+    /// Returns the OID of the new row in the table and each of its master tables.
+    fn import_row(
+        &mut self,
+        trans: &Transaction,
+        table_oid: i64,
+        row_map: &Map<String, Value>,
+        location: &str,
+    ) -> Result<HashMap<i64, i64>, Error> {
+        // Determine which subtype of the table the row belongs to
+        let row_table_oid: i64 = match row_map.get(TYPE_KEY) {
+            Some(Value::String(type_name)) => {
+                match trans.query_one(
+                    "
+                    SELECT s.OID 
+                    FROM METADATA_SCHEMA s 
+                    INNER JOIN METADATA_TABLE t ON t.OID = s.OID 
+                    WHERE NOT s.TRASH 
+                        AND s.NAME = ?1 
+                        AND (s.OID = ?2 OR EXISTS (
+                            SELECT 1 FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW inh 
+                            WHERE inh.MASTER_SCHEMA_OID = ?2 AND inh.INHERITOR_SCHEMA_OID = s.OID
+                        ))
+                    ",
+                    params![type_name, table_oid],
+                    |row| row.get::<_, i64>("OID"),
+                ).optional()? {
+                    Some(row_table_oid) => row_table_oid,
+                    None => {
+                        self.report.warnings.push(format!("{location}: No subtype named \"{type_name}\" was found, so the row was imported without one."));
+                        table_oid
+                    }
+                }
+            }
+            _ => table_oid,
+        };
+
+        // Insert the row
+        let mut master_rows: HashMap<i64, i64> = HashMap::new();
+        row::insert_transact(trans, row_table_oid, None, &mut master_rows)?;
+
+        // Move the row to its original OID, if requested
+        if self.preserve_oids {
+            if let Some(original_oid_value) = row_map.get(REFERENCE_OID_KEY) {
+                let Some(original_oid) = original_oid_value.as_i64() else {
+                    return Err(Error::AdhocError("Expected an integer OID."));
+                };
+                let sql_select: String = format!("SELECT TRASH FROM TABLE{table_oid} WHERE OID = ?1 AND OID <> ?2");
+                match trans.query_one(&sql_select, params![original_oid, master_rows[&table_oid]], |row| row.get::<_, bool>("TRASH")).optional()? {
+                    Some(false) => {
+                        return Err(Error::AdhocError("The original OID of the row is already used by another row."));
+                    }
+                    Some(true) => {
+                        return Err(Error::AdhocError("The original OID of the row is already used by a deleted row."));
+                    }
+                    None => {}
+                }
+
+                // Rows of subtypes follow the change by cascade
+                let sql_update: String = format!("UPDATE TABLE{table_oid} SET OID = ?1 WHERE OID = ?2");
+                trans.execute(&sql_update, params![original_oid, master_rows[&table_oid]])?;
+                master_rows.insert(table_oid, original_oid);
+            }
+        }
+
+        // Fill in the value of each column
+        let cols: Vec<column::FullMetadata> = self.get_columns(trans, row_table_oid)?;
+        for (key, value) in row_map.iter() {
+            if key == TYPE_KEY || key == REFERENCE_OID_KEY {
+                continue;
+            }
+            let Some(c) = cols.iter().find(|c| &c.name == key) else {
+                if self.unknown_columns.insert((row_table_oid, key.clone())) {
+                    self.report.warnings.push(format!("{location}: No column named \"{key}\" was found, so its values were ignored."));
+                }
+                continue;
+            };
+            let Some(cell_row_oid) = master_rows.get(&c.schema.oid) else {
+                return Err(Error::AdhocError("Unable to find the row holding an inherited column."));
+            };
+
+            // References are resolved after every row has been imported, so that a row can reference a row later in the file
+            if let column_type::ColumnType::Select { .. } | column_type::ColumnType::Multiselect { .. } = c.column_type {
+                self.pending_references.push(PendingReference {
+                    column: c.clone(),
+                    row_oid: *cell_row_oid,
+                    value: value.clone(),
+                    location: String::from(location),
+                });
+                continue;
+            }
+            let Some(cell_value) = self.convert_value(trans, c, value, location)? else {
+                continue;
+            };
+            DataCellEntry::new(c.schema.oid, c.oid, *cell_row_oid, cell_value).set_transact(trans)?;
+        }
+
+        Ok(master_rows)
+    }
+
+    /// Fills in the Select and Multiselect cells of the imported rows, now that every row that they can reference has been imported.
+    /// A cell that fails to be filled in is left empty and reported, without affecting the rest of its row.
+    fn resolve_pending_references(&mut self, trans: &Transaction) -> Result<(), Error> {
+        for pending in std::mem::take(&mut self.pending_references) {
+            trans.execute_batch("SAVEPOINT IMPORT_REFERENCE")?;
+            let result: Result<(), Error> = match self.convert_value(trans, &pending.column, &pending.value, &pending.location) {
+                Ok(Some(cell_value)) => DataCellEntry::new(pending.column.schema.oid, pending.column.oid, pending.row_oid, cell_value)
+                    .set_transact(trans)
+                    .map(|_| ()),
+                Ok(None) => Ok(()),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(_) => {
+                    trans.execute_batch("RELEASE IMPORT_REFERENCE")?;
+                }
+                Err(e) => {
+                    trans.execute_batch("ROLLBACK TO IMPORT_REFERENCE; RELEASE IMPORT_REFERENCE")?;
+                    let msg: String = e.into();
+                    self.report.errors.push(format!("{}: Column \"{}\" was left empty. {msg}", pending.location, pending.column.name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a JSON value into the value of a cell in the given column.
+    /// Returns None if the column cannot be imported into.
+    fn convert_value(
+        &mut self,
+        trans: &Transaction,
+        c: &column::FullMetadata,
+        value: &Value,
+        location: &str,
+    ) -> Result<Option<DataCellValue>, Error> {
+        Ok(Some(match &c.column_type {
+            column_type::ColumnType::Primitive(prim) => match prim {
+                column_type::Primitive::PlainText
+                | column_type::Primitive::MarkdownText
+                | column_type::Primitive::LongText
+                | column_type::Primitive::JsonText
+                | column_type::Primitive::XmlText => DataCellValue::Text(match value {
+                    Value::Null => None,
+                    Value::String(s) => Some(s.clone()),
+                    _ => Some(value.to_string()),
+                }),
+                column_type::Primitive::Integer => DataCellValue::Integer(match value {
+                    Value::Null => None,
+                    Value::Number(n) if n.is_i64() => n.as_i64(),
+                    Value::String(s) => match s.trim().parse::<i64>() {
+                        Ok(i) => Some(i),
+                        Err(_) => {
+                            return Err(Error::AdhocError("Expected an integer value."));
+                        }
+                    },
+                    _ => {
+                        return Err(Error::AdhocError("Expected an integer value."));
+                    }
+                }),
+                column_type::Primitive::Number => DataCellValue::Number(match value {
+                    Value::Null => None,
+                    Value::Number(n) => n.as_f64(),
+                    Value::String(s) => match s.trim().parse::<f64>() {
+                        Ok(f) => Some(f),
+                        Err(_) => {
+                            return Err(Error::AdhocError("Expected a number value."));
+                        }
+                    },
+                    _ => {
+                        return Err(Error::AdhocError("Expected a number value."));
+                    }
+                }),
+                column_type::Primitive::Percentage => DataCellValue::Percentage {
+                    label: match value {
+                        Value::Null => None,
+                        Value::Number(n) => Some(n.to_string()),
+                        Value::String(s) => Some(s.clone()),
+                        _ => {
+                            return Err(Error::AdhocError("Expected a percentage value."));
+                        }
+                    },
+                },
+                column_type::Primitive::Point => DataCellValue::Point {
+                    label: match value {
+                        Value::Null => None,
+                        Value::String(s) => Some(s.clone()),
+                        Value::Array(_) => Some(value.to_string()),
+                        _ => {
+                            return Err(Error::AdhocError("Expected a point value."));
+                        }
+                    },
+                },
+                column_type::Primitive::Boolean => DataCellValue::Boolean(match value {
+                    Value::Null => None,
+                    Value::Bool(b) => Some(*b),
+                    _ => {
+                        return Err(Error::AdhocError("Expected a boolean value."));
+                    }
+                }),
+                column_type::Primitive::Date => DataCellValue::Date {
+                    label: match value {
+                        Value::Null => None,
+                        Value::String(s) => Some(s.clone()),
+                        _ => {
+                            return Err(Error::AdhocError("Expected a date value."));
+                        }
+                    },
+                },
+                column_type::Primitive::Datetime => DataCellValue::Datetime {
+                    label: match value {
+                        Value::Null => None,
+                        Value::String(s) => Some(s.clone()),
+                        _ => {
+                            return Err(Error::AdhocError("Expected a datetime value."));
+                        }
+                    },
+                },
+                column_type::Primitive::File | column_type::Primitive::Image | column_type::Primitive::FileList => {
+                    if !value.is_null() {
+                        self.report.warnings.push(format!("{location}: Files cannot be imported, so column \"{}\" was left empty.", c.name));
+                    }
+                    return Ok(None);
+                }
+                column_type::Primitive::RowNumber => {
+                    // Row numbers are computed, so there is nothing to import
+                    return Ok(None);
+                }
+            },
+            column_type::ColumnType::Object { table_oid, .. } => DataCellValue::Object {
+                linked_row_oid: match value {
+                    Value::Null => DataCellObjectBehavior::Delete,
+                    Value::Object(object_map) => {
+                        let object_master_rows: HashMap<i64, i64> = self.import_row(trans, *table_oid, object_map, location)?;
+                        DataCellObjectBehavior::SetExisting(object_master_rows[table_oid])
+                    }
+                    _ => {
+                        return Err(Error::AdhocError("Expected an object value."));
+                    }
+                },
+            },
+            column_type::ColumnType::Select { table_oid, .. } => DataCellValue::Select {
+                linked_row_oid: self.resolve_reference(trans, c, *table_oid, value, location)?,
+            },
+            column_type::ColumnType::Multiselect { table_oid, .. } => DataCellValue::Multiselect {
+                linked_row_oid: match value {
+                    Value::Null => Vec::new(),
+                    Value::Array(values) => {
+                        let mut linked_row_oid: Vec<i64> = Vec::new();
+                        for v in values.iter() {
+                            if let Some(oid) = self.resolve_reference(trans, c, *table_oid, v, location)? {
+                                linked_row_oid.push(oid);
+                            }
+                        }
+                        linked_row_oid
+                    }
+                    _ => {
+                        return Err(Error::AdhocError("Expected an array value."));
+                    }
+                },
+            },
+            column_type::ColumnType::Formula { .. } | column_type::ColumnType::Subreport { .. } => {
+                // Virtual columns are computed, so there is nothing to import
+                return Ok(None);
+            }
+        }))
+    }
+
+    /// Finds the row referenced by a value, either by its display value or by its OID.
+    /// If OIDs are preserved, text holding an integer is also treated as an OID.
+    /// An exported reference holding both an OID and a display value is matched by its OID if OIDs are preserved, and by its display value otherwise.
+    /// Unresolved references are left empty with a warning.
+    fn resolve_reference(
+        &mut self,
+        trans: &Transaction,
+        c: &column::FullMetadata,
+        table_oid: i64,
+        value: &Value,
+        location: &str,
+    ) -> Result<Option<i64>, Error> {
+        let resolved_oid: Option<i64> = match value {
+            Value::Null => {
+                return Ok(None);
+            }
+            Value::String(label) if self.preserve_oids && label.trim().parse::<i64>().is_ok() => {
+                find_row_by_oid(trans, table_oid, label.trim().parse::<i64>().ok())?
+            }
+            Value::String(label) => find_row_by_label(trans, table_oid, label)?,
+            Value::Number(n) if n.is_i64() => find_row_by_oid(trans, table_oid, n.as_i64())?,
+            Value::Object(reference_map) => {
+                match (
+                    reference_map.get(REFERENCE_OID_KEY).and_then(|v| v.as_i64()),
+                    reference_map.get(REFERENCE_LABEL_KEY).and_then(|v| v.as_str()),
+                ) {
+                    (Some(oid), _) if self.preserve_oids => find_row_by_oid(trans, table_oid, Some(oid))?,
+                    (_, Some(label)) => find_row_by_label(trans, table_oid, label)?,
+                    (Some(oid), None) => find_row_by_oid(trans, table_oid, Some(oid))?,
+                    (None, None) => None,
+                }
+            }
+            _ => None,
+        };
+        if resolved_oid.is_none() {
+            self.report.warnings.push(format!("{location}: The value {value} in column \"{}\" does not match any row, so it was left empty.", c.name));
+        }
+        Ok(resolved_oid)
+    }
+}
+
+/// Finds a row of a table by its OID.
+fn find_row_by_oid(trans: &Transaction, table_oid: i64, oid: Option<i64>) -> Result<Option<i64>, Error> {
+    let sql_select: String = format!("SELECT OID FROM TABLE{table_oid} WHERE NOT TRASH AND OID = ?1");
+    Ok(trans.query_one(&sql_select, params![oid], |row| row.get::<_, i64>("OID")).optional()?)
+}
+
+/// Finds the row of a table whose display value matches the given label.
+/// Fails if more than one row has the label, since the reference cannot be resolved unambiguously.
+fn find_row_by_label(trans: &Transaction, table_oid: i64, label: &str) -> Result<Option<i64>, Error> {
+    let sql_select: String = format!("SELECT l.OID FROM SCHEMA{table_oid}_LABEL_VIEW l INNER JOIN TABLE{table_oid} t ON t.OID = l.OID WHERE NOT t.TRASH AND COALESCE(l.PLAIN_LABEL, l.JSON_LABEL) = ?1 ORDER BY l.OID LIMIT 2");
+    let mut row_oids: Vec<i64> = Vec::new();
+    for row_result in trans.prepare(&sql_select)?.query_map(params![label], |row| row.get::<_, i64>("OID"))? {
+        row_oids.push(row_result?);
+    }
+    if row_oids.len() > 1 {
+        return Err(Error::AdhocError("A reference matches the display value of more than one row."));
+    }
+    Ok(row_oids.into_iter().next())
+}
+
+/// Infers the type of a column from the values found for it in the imported rows.
+fn infer_primitive(values: &Vec<&Value>) -> column_type::Primitive {
+    let non_null: Vec<&&Value> = values.iter().filter(|v| !v.is_null()).collect();
+    if non_null.len() == 0 {
+        column_type::Primitive::PlainText
+    } else if non_null.iter().all(|v| v.is_boolean()) {
+        column_type::Primitive::Boolean
+    } else if non_null.iter().all(|v| v.is_i64()) {
+        column_type::Primitive::Integer
+    } else if non_null.iter().all(|v| v.is_number()) {
+        column_type::Primitive::Number
+    } else if non_null.iter().all(|v| v.is_string()) {
+        column_type::Primitive::PlainText
+    } else if non_null.iter().all(|v| v.is_object() || v.is_array()) {
+        column_type::Primitive::JsonText
+    } else {
+        column_type::Primitive::PlainText
+    }
+}
+
+/// Creates a new table to hold imported rows, with a column for each key found in the rows.
+fn create_table(trans: &Transaction, table_name: &String, rows: &Vec<Value>) -> Result<i64, Error> {
+    let mut metadata: table::FullMetadata = table::FullMetadata {
+        schema: schema::FullMetadata {
+            oid: 0,
+            name: table_name.clone(),
+            master_schema_oids: HashSet::new(),
+            order_by_column_oids: Vec::new(),
+            order_by_nulls_last: HashMap::new(),
+        },
+        track_timestamps: false,
+        default_page_size: None,
+    };
+    metadata.create_transact(trans)?;
+
+    // Collect the values of each key, in the order that keys first appear
+    let mut keys: Vec<String> = Vec::new();
+    let mut values_by_key: HashMap<String, Vec<&Value>> = HashMap::new();
+    for row_value in rows.iter() {
+        if let Value::Object(row_map) = row_value {
+            for (key, value) in row_map.iter() {
+                if key == TYPE_KEY || key == REFERENCE_OID_KEY {
+                    continue;
+                }
+                if !values_by_key.contains_key(key) {
+                    keys.push(key.clone());
+                }
+                values_by_key.entry(key.clone()).or_insert_with(Vec::new).push(value);
+            }
+        }
+    }
+
+    // Create a column for each key
+    for (idx, key) in keys.iter().enumerate() {
+        let mut column_metadata: column::FullMetadata = column::FullMetadata {
+            oid: 0,
+            hidden: false,
+            schema: metadata.schema.clone(),
+            name: key.clone(),
+            column_type: column_type::ColumnType::Primitive(infer_primitive(&values_by_key[key])),
+            style: String::from("width: 180px;"),
+            ordering: -1,
+            default_value: None,
+            is_nullable: true,
+            is_primary_key: idx == 0, // The first column identifies the rows
+            case_insensitive_unique: false,
+            pretty_print_json: false,
+            empty_as_null: true,
+            reference_on_delete: column::ReferenceOnDelete::default(),
+            reference_subtype_filter: None,
+            integer_format: column::IntegerFormat::default(),
+            reference_display_column_oid: None,
+        };
+        column_metadata.create_transact(trans)?;
+    }
+    Ok(metadata.schema.oid)
+}
+
+/// Imports tables from a JSON file in the format produced by the JSON export.
+/// The file is an object mapping the name of each table to an array of its rows.
+/// If `create_new` is true, a new table is created for each entry. Otherwise, rows are added to the existing table with the same name.
+/// If `preserve_oids` is true, each row with a `$oid` key is given that OID, and references are resolved by OID.
+/// References are resolved after every row has been imported, so a row may reference a row that appears later in the file.
+/// The import is done in a single transaction, but a row that fails to import is skipped and reported without affecting the others.
+/// Progress is reported as the number of rows processed.
+/// If the process is cancelled, the whole import is rolled back and a `ProcessCancelled` error is returned.
+pub fn import(
+    filepath: String,
+    create_new: bool,
+    preserve_oids: bool,
+    processid: i64,
+    progress: Sender<Progress>,
+) -> Result<ImportReport, Error> {
+    // Read the JSON document
+    let file = match FilesystemFile::open(&filepath) {
+        Ok(f) => f,
+        Err(e) => {
+            return Err(Error::FileIoError {
+                action: "open file",
+                path: Some(filepath),
+                error: e,
+            });
+        }
+    };
+    let Ok(Value::Object(dump)) = serde_json::from_reader::<_, Value>(BufReader::new(file)) else {
+        return Err(Error::AdhocError("The file is not a JSON object mapping table names to rows."));
+    };
+
+    let mut conn = db::open()?;
+    let trans = conn.transaction()?;
+    let mut ctx: ImportContext = ImportContext {
+        columns_by_table: HashMap::new(),
+        unknown_columns: HashSet::new(),
+        preserve_oids,
+        pending_references: Vec::new(),
+        report: ImportReport::default(),
+    };
+    let total_rows: usize = dump
+        .values()
+        .map(|rows_value| rows_value.as_array().map_or(0, |rows| rows.len()))
+        .sum();
+    let mut progress: ProgressTracker = ProgressTracker::new(progress, total_rows as i64);
+    let mut rows_processed: usize = 0;
+
+    for (table_name, rows_value) in dump.iter() {
+        let Value::Array(rows) = rows_value else {
+            ctx.report.errors.push(format!("\"{table_name}\": Expected an array of rows."));
+            continue;
+        };
+
+        // Find or create the table to import into
+        let table_oid: i64 = if create_new {
+            create_table(&trans, table_name, rows)?
+        } else {
+            match trans.query_one(
+                "SELECT s.OID FROM METADATA_SCHEMA s INNER JOIN METADATA_TABLE t ON t.OID = s.OID WHERE NOT s.TRASH AND s.NAME = ?1 ORDER BY s.OID LIMIT 1",
+                params![table_name],
+                |row| row.get::<_, i64>("OID"),
+            ).optional()? {
+                Some(table_oid) => table_oid,
+                None => {
+                    ctx.report.errors.push(format!("\"{table_name}\": No table with this name exists."));
+                    continue;
+                }
+            }
+        };
+        ctx.report.table_oids.push(table_oid);
+
+        // Import each row, rolling back only that row if it fails
+        for (row_idx, row_value) in rows.iter().enumerate() {
+            // Roll back everything imported so far if the import was cancelled
+            if rows_processed % CANCELLATION_CHECK_INTERVAL == 0 && process::is_cancelled(processid) {
+                trans.rollback()?;
+                return Err(Error::ProcessCancelled { processid });
+            }
+            rows_processed += 1;
+
+            let location: String = format!("Row {} of \"{table_name}\"", row_idx + 1);
+            progress.increment()?;
+            let Value::Object(row_map) = row_value else {
+                ctx.report.errors.push(format!("{location}: Expected an object."));
+                continue;
+            };
+
+            trans.execute_batch("SAVEPOINT IMPORT_ROW")?;
+            let pending_reference_count: usize = ctx.pending_references.len();
+            match ctx.import_row(&trans, table_oid, row_map, &location) {
+                Ok(_) => {
+                    trans.execute_batch("RELEASE IMPORT_ROW")?;
+                    ctx.report.rows_imported += 1;
+                }
+                Err(e) => {
+                    trans.execute_batch("ROLLBACK TO IMPORT_ROW; RELEASE IMPORT_ROW")?;
+                    ctx.pending_references.truncate(pending_reference_count);
+                    let msg: String = e.into();
+                    ctx.report.errors.push(format!("{location}: {msg}"));
+                }
+            }
+        }
+    }
+
+    // Fill in the references of the imported rows, once every row that they can reference exists
+    if process::is_cancelled(processid) {
+        trans.rollback()?;
+        return Err(Error::ProcessCancelled { processid });
+    }
+    ctx.resolve_pending_references(&trans)?;
+
+    // Commit the transaction
+    trans.commit()?;
+    Ok(ctx.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::testing;
+
+    #[test]
+    fn references_are_matched_by_a_unique_label() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let mut name_column: column::FullMetadata = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        name_column.is_primary_key = true;
+        name_column.create().unwrap();
+        let mut row_oids: Vec<i64> = Vec::new();
+        for name in ["Sword", "Shield", "Shield"] {
+            let row_oid: i64 = testing::insert_row(table_oid);
+            testing::set_cell(table_oid, name_column.oid, row_oid, DataCellValue::Text(Some(String::from(name))));
+            row_oids.push(row_oid);
+        }
+
+        let mut conn = db::open().unwrap();
+        let trans = conn.transaction().unwrap();
+        assert_eq!(find_row_by_label(&trans, table_oid, "Sword").unwrap(), Some(row_oids[0]));
+        assert_eq!(find_row_by_label(&trans, table_oid, "Bow").unwrap(), None);
+        assert!(find_row_by_label(&trans, table_oid, "Shield").is_err());
+    }
+
+    #[test]
+    fn exported_rows_that_reference_later_rows_are_imported_with_their_references() {
+        let _db = testing::open_database();
+        let create_schema = || -> (i64, i64, i64) {
+            let table_oid: i64 = testing::create_table("Monster", &[]);
+            let mut name_column: column::FullMetadata = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+            name_column.is_primary_key = true;
+            name_column.create().unwrap();
+            let rival_column_oid: i64 = testing::create_column(table_oid, "Rival", column_type::ColumnType::Select { oid: 0, table_oid });
+            (table_oid, name_column.oid, rival_column_oid)
+        };
+
+        // Export a row that references a row after it
+        let (table_oid, name_column_oid, rival_column_oid) = create_schema();
+        testing::insert_row(table_oid);
+        let goblin_oid: i64 = testing::insert_row(table_oid);
+        let troll_oid: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, name_column_oid, goblin_oid, DataCellValue::Text(Some(String::from("Goblin"))));
+        testing::set_cell(table_oid, name_column_oid, troll_oid, DataCellValue::Text(Some(String::from("Troll"))));
+        testing::set_cell(table_oid, rival_column_oid, goblin_oid, DataCellValue::Select { linked_row_oid: Some(troll_oid) });
+        let rows: Vec<Value> = [goblin_oid, troll_oid]
+            .into_iter()
+            .map(|row_oid| serde_json::from_str(&crate::data::export::export_row(table_oid, row_oid, crate::data::export::RowExportFormat::Json).unwrap()).unwrap())
+            .collect();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        serde_json::to_writer(file.as_file(), &serde_json::json!({ "Monster": rows })).unwrap();
+
+        // Import the rows into a fresh database with the same tables
+        db::init_new(db::JournalMode::Delete).unwrap();
+        let (table_oid, _, rival_column_oid) = create_schema();
+        let report: ImportReport = import(file.path().to_string_lossy().into_owned(), false, true, 0, Sender::Dummy).unwrap();
+        assert_eq!(report.rows_imported, 2);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        let rival_oid: i64 = testing::query_one(&format!("SELECT COLUMN{rival_column_oid} FROM TABLE{table_oid} WHERE OID = {goblin_oid}"));
+        assert_eq!(rival_oid, troll_oid);
+    }
+}
@@ -2,7 +2,8 @@ use crate::data::view::regenerate_schema_views;
 use crate::data::{datasource, schema};
 use crate::util::db;
 use crate::util::error::Error;
-use rusqlite::{params, OptionalExtension, Transaction};
+use crate::util::formula::Formula;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::collections::HashSet;
@@ -17,6 +18,14 @@ pub struct FullMetadata {
     pub group_by_column_oids: Vec<i64>,
 }
 
+/// A report that draws data from a particular table.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentReport {
+    pub report_oid: i64,
+    pub report_name: String,
+}
+
 impl Hash for FullMetadata {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.schema.hash(state)
@@ -148,4 +157,82 @@ impl FullMetadata {
         regenerate_schema_views(&trans, self.schema.oid)?;
         Ok(())
     }
+
+    /// Finds every report that draws data from a table, either by inheriting from it or through a formula that references it.
+    pub fn find_using_table(table_oid: i64) -> Result<Vec<DependentReport>, Error> {
+        let conn: Connection = db::open()?;
+        let mut report_oids: HashSet<i64> = HashSet::new();
+
+        // Step 1. Find reports that inherit the columns of the table
+        for row_result in conn
+            .prepare(
+                "SELECT inh.INHERITOR_SCHEMA_OID 
+                FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW inh 
+                INNER JOIN METADATA_REPORT r ON r.OID = inh.INHERITOR_SCHEMA_OID 
+                WHERE inh.MASTER_SCHEMA_OID = ?1",
+            )?
+            .query_map(params![table_oid], |row| row.get::<_, i64>(0))?
+        {
+            report_oids.insert(row_result?);
+        }
+
+        // Step 2. Find reports with a filter or formula column that references a column of the table
+        let mut report_formulas: Vec<(i64, String)> = Vec::new();
+        for row_result in conn
+            .prepare(
+                "SELECT r.OID, r.FILTER_FORMULA AS FORMULA 
+                FROM METADATA_REPORT r 
+                WHERE r.FILTER_FORMULA IS NOT NULL 
+                
+                UNION ALL 
+                
+                SELECT c.SCHEMA_OID, f.FORMULA 
+                FROM METADATA_COLUMN_VIEW c 
+                INNER JOIN METADATA_COLUMN_TYPE__FORMULA f ON f.OID = c.TYPE_OID 
+                INNER JOIN METADATA_REPORT r ON r.OID = c.SCHEMA_OID",
+            )?
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        {
+            report_formulas.push(row_result?);
+        }
+        for (report_oid, formula) in report_formulas {
+            if report_oids.contains(&report_oid) {
+                continue;
+            }
+            for (_, datasource_alias, _) in Formula::find_params(&formula) {
+                // Check every table along the path of the datasource, skipping datasources that no longer resolve
+                let Ok(datasource) = datasource::Datasource::from_alias_transact(&conn, datasource_alias) else {
+                    continue;
+                };
+                let references_table: bool = datasource
+                    .linearize()
+                    .iter()
+                    .any(|path_datasource| path_datasource.get_schema_oid().ok() == Some(table_oid));
+                if references_table {
+                    report_oids.insert(report_oid);
+                    break;
+                }
+            }
+        }
+
+        // Step 3. Look up the name of each report that is still in use
+        let mut dependent_reports: Vec<DependentReport> = Vec::new();
+        for report_oid in report_oids {
+            if let Some(report_name) = conn
+                .query_one(
+                    "SELECT NAME FROM METADATA_SCHEMA WHERE OID = ?1 AND NOT TRASH",
+                    params![report_oid],
+                    |row| row.get::<_, String>("NAME"),
+                )
+                .optional()?
+            {
+                dependent_reports.push(DependentReport {
+                    report_oid,
+                    report_name,
+                });
+            }
+        }
+        dependent_reports.sort_by(|a, b| a.report_name.cmp(&b.report_name));
+        Ok(dependent_reports)
+    }
 }
@@ -5,6 +5,7 @@ use crate::util::db;
 use crate::util::error::Error;
 use rusqlite::Connection;
 use rusqlite::{params, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 /// Constructs a mapping of all associated rows in master tables.
@@ -30,6 +31,14 @@ fn map_all_master_tables(
     Ok(())
 }
 
+/// Gets the row in a master table that a row inherits from, whether directly or through intermediate tables.
+pub fn get_master_row_oid(table_oid: i64, row_oid: i64, master_table_oid: i64) -> Result<Option<i64>, Error> {
+    let conn = db::open()?;
+    let mut mapped_table_oid: HashMap<i64, Option<i64>> = HashMap::new();
+    map_all_master_tables(&conn, table_oid, row_oid, &mut mapped_table_oid)?;
+    Ok(mapped_table_oid.get(&master_table_oid).cloned().flatten())
+}
+
 /// Constructs a mapping of all associated rows in inheritor tables.
 fn map_all_inheritor_tables(
     conn: &Connection,
@@ -65,13 +74,71 @@ fn map_all_inheritor_tables(
                 map_all_inheritor_tables(conn, inheritor_table_oid, None, mapped_table_oid)?;
             };
         }
-        return Ok((deepest_level + 1, deepest_table_oid));
+        // If no inheritor row is untrashed, this table is the deepest
+        return Ok((deepest_level + 1, deepest_table_oid.or(Some(table_oid))));
     }
     Ok((0, None))
 }
 
+/// How a new row is positioned when the OID it is inserted at already belongs to another row.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum InsertMode {
+    /// The new row takes the OID, and every row from that OID onwards is shifted down by one.
+    #[default]
+    ShiftDown,
+
+    /// The new row is placed immediately before the existing row, taking the OID before it if that is free.
+    Before,
+
+    /// The new row is placed immediately after the existing row.
+    After,
+
+    /// The row is not inserted.
+    Fail,
+}
+
+/// Makes space for a new row at an OID, resolving any conflict with an existing row according to the insert mode.
+/// Returns the OID that the new row should be inserted at.
+fn make_space_transact(
+    trans: &Transaction,
+    table_oid: i64,
+    row_oid: i64,
+    insert_mode: InsertMode,
+) -> Result<i64, Error> {
+    let sql_is_occupied: String = format!("SELECT EXISTS (SELECT 1 FROM TABLE{table_oid} WHERE OID = ?1)");
+    let is_occupied = |o: i64| trans.query_one(&sql_is_occupied, params![o], |row| row.get::<_, bool>(0));
+
+    let (row_oid, is_shifted) = match insert_mode {
+        InsertMode::ShiftDown => (row_oid, true),
+        _ if !is_occupied(row_oid)? => (row_oid, false),
+        InsertMode::Before => {
+            if row_oid > 1 && !is_occupied(row_oid - 1)? {
+                (row_oid - 1, false)
+            } else {
+                (row_oid, true)
+            }
+        }
+        InsertMode::After => (row_oid + 1, is_occupied(row_oid + 1)?),
+        InsertMode::Fail => {
+            return Err(Error::AdhocError("A row already exists at the position where the new row was to be inserted."));
+        }
+    };
+
+    if is_shifted {
+        // Shift every row from the OID onwards down by one
+        let sql_invert_oids: String =
+            format!("UPDATE TABLE{table_oid} SET OID = -OID WHERE OID >= ?1");
+        trans.execute(&sql_invert_oids, params![row_oid])?;
+        let sql_revert_oids: String =
+            format!("UPDATE TABLE{table_oid} SET OID = 1 - OID WHERE OID < 0");
+        trans.execute(&sql_revert_oids, [])?;
+    }
+    Ok(row_oid)
+}
+
 /// Inserts a row into the table.
-/// Optionally, a specific OID for the row can be provided.
+/// Optionally, a specific OID for the row can be provided, which must not already belong to a row in the table.
 pub fn insert_transact(
     trans: &Transaction,
     table_oid: i64,
@@ -128,6 +195,7 @@ pub fn insert_transact(
     }
 
     // Query for any default values that need to be populated
+    // A new row starts with the default value of each column that has one, rather than blank
     {
         let mut col_query_stmt = trans.prepare(
             "
@@ -136,27 +204,21 @@ pub fn insert_transact(
             INNER JOIN METADATA_COLUMN_TYPE__PRIMITIVE typ ON typ.OID = c.TYPE_OID
             WHERE c.SCHEMA_OID = ?1 
                 AND c.DEFAULT_VALUE IS NOT NULL 
-                AND typ.MODE NOT IN ('file', 'image')
+                AND typ.MODE NOT IN ('file', 'image', 'fileList')
             ",
         )?;
-        let _ = col_query_stmt.query_and_then(params![table_oid], |row| {
+        let default_rows = col_query_stmt.query_and_then(params![table_oid], |row| {
             let column_oid: i64 = row.get("OID")?;
             let default_value: String = row.get("DEFAULT_VALUE")?;
-            cols.push((format!("COLUMN{column_oid}"), default_value));
-            Ok::<(), rusqlite::Error>(())
+            Ok::<(String, String), rusqlite::Error>((format!("COLUMN{column_oid}"), default_value))
         })?;
+        for default_row in default_rows {
+            cols.push(default_row?);
+        }
     }
 
     // Handle insertion at a specific location in the table
     if let Some(o) = row_oid {
-        // Make space for the new row at the designated OID
-        let sql_invert_oids: String =
-            format!("UPDATE TABLE{table_oid} SET OID = -OID WHERE OID >= ?1");
-        trans.execute(&sql_invert_oids, params![o])?;
-        let sql_revert_oids: String =
-            format!("UPDATE TABLE{table_oid} SET OID = 1 - OID WHERE OID < 0");
-        trans.execute(&sql_revert_oids, [])?;
-
         // Add initial value for the OID
         cols.push((String::from("OID"), format!("{o}")));
     }
@@ -200,21 +262,259 @@ pub fn insert_transact(
     // Get the OID and add to the HashMap of master tables
     let row_oid: i64 = trans.last_insert_rowid();
     master_rows.insert(table_oid, row_oid);
+
+    // Record the creation time of the row
+    touch_transact(trans, table_oid, row_oid, true)?;
     Ok(row_oid)
 }
 
+/// The creation and last modification time of a row, formatted as ISO-8601 datetimes.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RowTimestamps {
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Updates the modification timestamp of a row, if the table tracks timestamps.
+/// If `is_created` is true, the creation timestamp is set as well.
+pub fn touch_transact(
+    trans: &Transaction,
+    table_oid: i64,
+    row_oid: i64,
+    is_created: bool,
+) -> Result<(), Error> {
+    let track_timestamps: bool = trans
+        .query_one(
+            "SELECT TRACK_TIMESTAMPS FROM METADATA_TABLE WHERE OID = ?1",
+            params![table_oid],
+            |row| row.get("TRACK_TIMESTAMPS"),
+        )
+        .optional()?
+        .unwrap_or(false);
+    bump_version_transact(trans, table_oid, row_oid)?;
+    if track_timestamps {
+        let sql_touch: String = if is_created {
+            format!("UPDATE TABLE{table_oid} SET CREATED_AT = JULIANDAY('now'), UPDATED_AT = JULIANDAY('now') WHERE OID = ?1")
+        } else {
+            format!("UPDATE TABLE{table_oid} SET UPDATED_AT = JULIANDAY('now') WHERE OID = ?1")
+        };
+        trans.execute(&sql_touch, params![row_oid])?;
+    }
+    Ok(())
+}
+
+/// Marks a row as changed by giving it the next version of its table.
+pub fn bump_version_transact(trans: &Transaction, table_oid: i64, row_oid: i64) -> Result<(), Error> {
+    let version: i64 = trans.query_one(
+        "UPDATE METADATA_TABLE SET ROW_VERSION = ROW_VERSION + 1 WHERE OID = ?1 RETURNING ROW_VERSION",
+        params![table_oid],
+        |row| row.get(0),
+    )?;
+    let sql_bump: String = format!("UPDATE TABLE{table_oid} SET VERSION = ?1 WHERE OID = ?2");
+    trans.execute(&sql_bump, params![version, row_oid])?;
+    Ok(())
+}
+
+/// The rows of a table that changed after a given version.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedRows {
+    /// The latest version of the table, to pass in the next time changes are requested.
+    pub version: i64,
+    pub row_oids: Vec<i64>,
+    pub trashed_row_oids: Vec<i64>,
+}
+
+/// Records the time at which a row was trashed, or clears it if the row has been restored.
+fn set_trashed_at_transact(trans: &Transaction, table_oid: i64, row_oid: i64, is_trashed: bool) -> Result<(), Error> {
+    let table_name: String = format!("TABLE{table_oid}");
+    let sql_update: String = if is_trashed {
+        format!("UPDATE {table_name} SET TRASHED_AT = JULIANDAY('now') WHERE OID = ?1")
+    } else {
+        format!("UPDATE {table_name} SET TRASHED_AT = NULL WHERE OID = ?1")
+    };
+    trans.execute(&sql_update, params![row_oid])?;
+    Ok(())
+}
+
+/// The rows of a table that were permanently deleted from the trash.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgedRows {
+    pub table_oid: i64,
+    pub table_name: String,
+    pub row_count: i64,
+}
+
+/// Permanently deletes a row, along with its links in Multiselect and File List columns and the rows embedded in its Object columns.
+/// Returns false if the row had already been deleted.
+fn delete_row_transact(conn: &Connection, table_oid: i64, row_oid: i64) -> Result<bool, Error> {
+    // Find the rows embedded in the Object columns of the row
+    let mut embedded_rows: Vec<(i64, i64)> = Vec::new();
+    for object_column_result in conn.prepare("SELECT c.OID, typ.TABLE_OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__OBJECT typ ON typ.OID = c.TYPE_OID WHERE c.SCHEMA_OID = ?1")?.query_map(params![table_oid], |row| Ok((row.get::<_, i64>("OID")?, row.get::<_, i64>("TABLE_OID")?)))? {
+        let (column_oid, object_table_oid) = object_column_result?;
+        let sql_select: String = format!("SELECT COLUMN{column_oid} FROM TABLE{table_oid} WHERE OID = ?1");
+        if let Some(Some(object_row_oid)) = conn.query_one(&sql_select, params![row_oid], |row| row.get::<_, Option<i64>>(0)).optional()? {
+            embedded_rows.push((object_table_oid, object_row_oid));
+        }
+    }
+
+    // Remove the links of the row's Multiselect and File List columns
+    for multiselect_column_oid_result in conn.prepare("SELECT c.OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__MULTISELECT typ ON typ.OID = c.TYPE_OID WHERE c.SCHEMA_OID = ?1")?.query_map(params![table_oid], |row| row.get::<_, i64>(0))? {
+        let multiselect_column_oid: i64 = multiselect_column_oid_result?;
+        conn.execute(&format!("DELETE FROM MULTISELECT{multiselect_column_oid} WHERE TABLE{table_oid}_OID = ?1"), params![row_oid])?;
+    }
+    for file_list_column_oid_result in conn.prepare("SELECT c.OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__PRIMITIVE typ ON typ.OID = c.TYPE_OID WHERE c.SCHEMA_OID = ?1 AND typ.MODE = 'fileList'")?.query_map(params![table_oid], |row| row.get::<_, i64>(0))? {
+        let file_list_column_oid: i64 = file_list_column_oid_result?;
+        conn.execute(&format!("DELETE FROM FILELIST{file_list_column_oid} WHERE TABLE{table_oid}_OID = ?1"), params![row_oid])?;
+    }
+
+    // Delete the row itself, then the rows that were embedded in it
+    let is_deleted: bool = conn.execute(&format!("DELETE FROM TABLE{table_oid} WHERE OID = ?1"), params![row_oid])? > 0;
+    for (object_table_oid, object_row_oid) in embedded_rows {
+        delete_row_transact(conn, object_table_oid, object_row_oid)?;
+    }
+    Ok(is_deleted)
+}
+
+/// Permanently deletes every row that has been in the trash for longer than the given number of days.
+/// Deleting a row also deletes the rows embedded in its Object columns, and its links in Multiselect and File List columns.
+/// Rows that are still referenced by a column that restricts deletion are left in the trash.
+pub fn purge_expired_trash(retention_days: f64) -> Result<Vec<PurgedRows>, Error> {
+    let mut conn = db::open()?;
+    let mut trans: Transaction = conn.transaction()?;
+
+    let tables: Vec<(i64, String)> = trans
+        .prepare("SELECT t.OID, s.NAME FROM METADATA_TABLE t INNER JOIN METADATA_SCHEMA s ON s.OID = t.OID WHERE NOT s.TRASH")?
+        .query_map([], |row| Ok((row.get::<_, i64>("OID")?, row.get::<_, String>("NAME")?)))?
+        .collect::<Result<Vec<(i64, String)>, _>>()?;
+    let mut purged: Vec<PurgedRows> = Vec::new();
+    for (table_oid, table_name) in tables {
+        let data_table_name: String = format!("TABLE{table_oid}");
+        if !trans.table_exists(Some("main"), &data_table_name)? {
+            continue;
+        }
+
+        // Delete the expired rows one at a time, so that a row that cannot be deleted does not hold back the others
+        let sql_select: String = format!(
+            "SELECT OID FROM {data_table_name} WHERE TRASH AND TRASHED_AT <= JULIANDAY('now') - ?1"
+        );
+        let expired_row_oids: Vec<i64> = trans
+            .prepare(&sql_select)?
+            .query_map(params![retention_days], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+        let mut row_count: i64 = 0;
+        for row_oid in expired_row_oids {
+            // Each row is deleted under a savepoint, so a row that cannot be deleted leaves none of its embedded rows or links deleted
+            let savepoint = trans.savepoint()?;
+            match delete_row_transact(&savepoint, table_oid, row_oid) {
+                Ok(is_deleted) => {
+                    savepoint.commit()?;
+                    if is_deleted {
+                        row_count += 1;
+                    }
+                }
+                Err(Error::RusqliteError(rusqlite::Error::SqliteFailure(e, _)))
+                    if e.code == rusqlite::ErrorCode::ConstraintViolation => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if row_count > 0 {
+            purged.push(PurgedRows {
+                table_oid,
+                table_name,
+                row_count,
+            });
+        }
+    }
+
+    trans.commit()?;
+    Ok(purged)
+}
+
+/// Gets the rows of a table that were inserted, edited, trashed, or restored after the given version.
+pub fn get_changed_rows(table_oid: i64, since_version: i64) -> Result<ChangedRows, Error> {
+    let conn = db::open()?;
+    let mut changed_rows: ChangedRows = ChangedRows {
+        version: since_version,
+        row_oids: Vec::new(),
+        trashed_row_oids: Vec::new(),
+    };
+
+    let table_name: String = format!("TABLE{table_oid}");
+    let sql_select: String = format!("SELECT OID, TRASH, VERSION FROM {table_name} WHERE VERSION > ?1 ORDER BY OID");
+    for row_result in conn.prepare(&sql_select)?.query_map(params![since_version], |row| {
+        Ok((
+            row.get::<_, i64>("OID")?,
+            row.get::<_, bool>("TRASH")?,
+            row.get::<_, i64>("VERSION")?,
+        ))
+    })? {
+        let (row_oid, is_trashed, version) = row_result?;
+        changed_rows.version = changed_rows.version.max(version);
+        if is_trashed {
+            changed_rows.trashed_row_oids.push(row_oid);
+        } else {
+            changed_rows.row_oids.push(row_oid);
+        }
+    }
+    Ok(changed_rows)
+}
+
+/// Gets the creation and modification timestamps of a row.
+/// Returns None if the table does not track timestamps.
+pub fn get_timestamps_transact(
+    conn: &Connection,
+    table_oid: i64,
+    row_oid: i64,
+) -> Result<Option<RowTimestamps>, Error> {
+    let track_timestamps: bool = conn.query_one(
+        "SELECT TRACK_TIMESTAMPS FROM METADATA_TABLE WHERE OID = ?1",
+        params![table_oid],
+        |row| row.get("TRACK_TIMESTAMPS"),
+    )?;
+    if !track_timestamps {
+        return Ok(None);
+    }
+
+    let sql_get: String = format!(
+        "SELECT STRFTIME('%FT%H:%M:%fZ', CREATED_AT, 'julianday') AS CREATED_AT, STRFTIME('%FT%H:%M:%fZ', UPDATED_AT, 'julianday') AS UPDATED_AT FROM TABLE{table_oid} WHERE OID = ?1"
+    );
+    Ok(Some(conn.query_one(&sql_get, params![row_oid], |row| {
+        Ok(RowTimestamps {
+            created_at: row.get("CREATED_AT")?,
+            updated_at: row.get("UPDATED_AT")?,
+        })
+    })?))
+}
+
+/// Gets the creation and modification timestamps of a row.
+/// Returns None if the table does not track timestamps.
+pub fn get_timestamps(table_oid: i64, row_oid: i64) -> Result<Option<RowTimestamps>, Error> {
+    let conn = db::open()?;
+    get_timestamps_transact(&conn, table_oid, row_oid)
+}
+
 /// Inserts a row into the table.
-/// Optionally, a specific OID for the new row can be provided.
+/// Optionally, a specific OID for the new row can be provided, with the insert mode deciding what happens if a row already has that OID.
 /// Returns the OID of the new row.
 pub fn insert(
     table_oid: i64,
     row_oid: Option<i64>,
+    insert_mode: InsertMode,
     fixed_parent_datasource: Option<(i64, i64, column::FullMetadata)>,
 ) -> Result<i64, Error> {
     // Start a transaction
     let mut conn = db::open()?;
     let trans: Transaction = conn.transaction()?;
 
+    // Make space for the new row at the designated OID
+    let row_oid: Option<i64> = match row_oid {
+        Some(o) => Some(make_space_transact(&trans, table_oid, o, insert_mode)?),
+        None => None,
+    };
+
     // Insert the row into the table, + related rows for each master table
     let mut master_rows: HashMap<i64, i64> = HashMap::new();
     let row_oid: i64 = insert_transact(&trans, table_oid, row_oid, &mut master_rows)?;
@@ -249,7 +549,7 @@ pub fn insert(
             column_type::ColumnType::Multiselect { .. } => {
                 // Automatically add a Multiselect choice to link the parent datasource row with the newly-created row
                 let sql_fix_parent: String = format!(
-                    "INSERT INTO MULTISELECT{} (TABLE{}_OID, TABLE{}_OID) VALUES (?1, ?2)",
+                    "INSERT INTO MULTISELECT{0} (TABLE{1}_OID, TABLE{2}_OID, ORDERING) VALUES (?1, ?2, (SELECT COALESCE(MAX(ORDERING), 0) + 1 FROM MULTISELECT{0} WHERE TABLE{1}_OID = ?1))",
                     fixed_parent_datasource_relationship_column.oid,
                     fixed_parent_datasource_table_oid,
                     table_oid
@@ -297,11 +597,14 @@ pub fn copy_transact(
 
 /// Sets the TRASH flag for the row + all master rows + all inheritor rows.
 /// Returns the table OID and row OID of the deepest schema level where a row was trashed.
+/// Rows trashed because they reference the row through a column with a cascading delete policy are added to cascaded_rows,
+/// identified by the deepest schema level where they were trashed.
 pub fn trash_transact(
     trans: &Transaction,
     table_oid: i64,
     row_oid: i64,
     completed_table_oid: &mut HashSet<i64>,
+    cascaded_rows: &mut Vec<(i64, i64)>,
 ) -> Result<Option<(i64, i64)>, Error> {
     // Check if the row is already trashed
     let sql_is_trashed: String = format!("SELECT TRASH FROM TABLE{table_oid} WHERE OID = ?1");
@@ -313,6 +616,73 @@ pub fn trash_transact(
     // Trash the row
     let sql_trash: String = format!("UPDATE TABLE{table_oid} SET TRASH = TRUE WHERE OID = ?1");
     trans.execute(&sql_trash, params![row_oid])?;
+    bump_version_transact(trans, table_oid, row_oid)?;
+    set_trashed_at_transact(trans, table_oid, row_oid, true)?;
+
+    // Apply the delete policy of each Select or Multiselect column referencing the row
+    let mut query_reference_cmd = trans.prepare(
+        "
+        SELECT c.OID, c.SCHEMA_OID, c.NAME, c.REFERENCE_ON_DELETE, FALSE AS IS_MULTISELECT
+        FROM METADATA_COLUMN_VIEW c
+        INNER JOIN METADATA_COLUMN_TYPE__SELECT typ ON typ.OID = c.TYPE_OID
+        WHERE typ.TABLE_OID = ?1 AND c.REFERENCE_ON_DELETE <> 'SET NULL'
+        UNION ALL
+        SELECT c.OID, c.SCHEMA_OID, c.NAME, c.REFERENCE_ON_DELETE, TRUE AS IS_MULTISELECT
+        FROM METADATA_COLUMN_VIEW c
+        INNER JOIN METADATA_COLUMN_TYPE__MULTISELECT typ ON typ.OID = c.TYPE_OID
+        WHERE typ.TABLE_OID = ?1 AND c.REFERENCE_ON_DELETE <> 'SET NULL'
+        ",
+    )?;
+    for reference_result in query_reference_cmd.query_map(params![table_oid], |row| {
+        Ok((
+            row.get::<_, i64>("OID")?,
+            row.get::<_, i64>("SCHEMA_OID")?,
+            row.get::<_, String>("NAME")?,
+            column::ReferenceOnDelete::from_sql(&row.get::<_, String>("REFERENCE_ON_DELETE")?),
+            row.get::<_, bool>("IS_MULTISELECT")?,
+        ))
+    })? {
+        let (column_oid, referencing_table_oid, column_name, reference_on_delete, is_multiselect) =
+            reference_result?;
+
+        // Find the rows that reference the trashed row
+        // The row itself has already been trashed, so Multiselect links are read from the base table rather than the view
+        let sql_referencing_rows: String = if is_multiselect {
+            format!("SELECT m.TABLE{referencing_table_oid}_OID FROM MULTISELECT{column_oid} m INNER JOIN TABLE{referencing_table_oid} t ON t.OID = m.TABLE{referencing_table_oid}_OID WHERE NOT t.TRASH AND m.TABLE{table_oid}_OID = ?1")
+        } else {
+            format!("SELECT OID FROM TABLE{referencing_table_oid} WHERE NOT TRASH AND COLUMN{column_oid} = ?1")
+        };
+        let mut referencing_row_oids: Vec<i64> = Vec::new();
+        for referencing_row_result in trans
+            .prepare(&sql_referencing_rows)?
+            .query_map(params![row_oid], |row| row.get::<_, i64>(0))?
+        {
+            referencing_row_oids.push(referencing_row_result?);
+        }
+
+        match reference_on_delete {
+            column::ReferenceOnDelete::Restrict => {
+                if referencing_row_oids.len() > 0 {
+                    return Err(Error::RestrictedDelete { column_name });
+                }
+            }
+            column::ReferenceOnDelete::Cascade => {
+                for referencing_row_oid in referencing_row_oids {
+                    let mut referencing_completed_table_oid: HashSet<i64> = HashSet::new();
+                    if let Some(cascaded_row) = trash_transact(
+                        trans,
+                        referencing_table_oid,
+                        referencing_row_oid,
+                        &mut referencing_completed_table_oid,
+                        cascaded_rows,
+                    )? {
+                        cascaded_rows.push(cascaded_row);
+                    }
+                }
+            }
+            column::ReferenceOnDelete::SetNull => {}
+        }
+    }
 
     // Trash upwards in the inheritance tree
     let mut query_master_cmd = trans.prepare("SELECT MASTER_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_VIEW WHERE INHERITOR_SCHEMA_OID = ?1")?;
@@ -337,6 +707,7 @@ pub fn trash_transact(
                 master_schema_oid,
                 master_schema_row_oid,
                 completed_table_oid,
+                cascaded_rows,
             )?;
         }
     }
@@ -367,6 +738,7 @@ pub fn trash_transact(
                     inheritor_schema_oid,
                     inheritor_schema_row_oid,
                     completed_table_oid,
+                    cascaded_rows,
                 )? {
                     return Ok(Some(deepest_level_trashed_table_and_row));
                 }
@@ -379,19 +751,21 @@ pub fn trash_transact(
 }
 
 /// Sets the flag labelling a row for garbage collection.
-pub fn trash(table_oid: i64, row_oid: i64) -> Result<Option<(i64, i64)>, Error> {
+/// Returns the deepest schema level where the row was trashed, along with the rows that were trashed by a cascading delete policy.
+pub fn trash(table_oid: i64, row_oid: i64) -> Result<(Option<(i64, i64)>, Vec<(i64, i64)>), Error> {
     // Start a transaction
     let mut conn = db::open()?;
     let trans: Transaction = conn.transaction()?;
 
     // Trash the row + all related rows up and down the inheritance tree
     let mut completed_table_oid: HashSet<i64> = HashSet::new();
+    let mut cascaded_rows: Vec<(i64, i64)> = Vec::new();
     let deepest_level_trashed_table_and_row: Option<(i64, i64)> =
-        trash_transact(&trans, table_oid, row_oid, &mut completed_table_oid)?;
+        trash_transact(&trans, table_oid, row_oid, &mut completed_table_oid, &mut cascaded_rows)?;
 
     // Commit the transaction
     trans.commit()?;
-    Ok(deepest_level_trashed_table_and_row)
+    Ok((deepest_level_trashed_table_and_row, cascaded_rows))
 }
 
 /// Unsets the TRASH flag for a row + all master rows.
@@ -404,6 +778,8 @@ pub fn untrash_transact(
     // Untrash the row
     let sql_trash: String = format!("UPDATE TABLE{table_oid} SET TRASH = FALSE WHERE OID = ?1");
     trans.execute(&sql_trash, params![row_oid])?;
+    bump_version_transact(trans, table_oid, row_oid)?;
+    set_trashed_at_transact(trans, table_oid, row_oid, false)?;
 
     // Untrash upwards in the inheritance tree
     let mut query_master_cmd = trans.prepare("SELECT MASTER_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_VIEW WHERE INHERITOR_SCHEMA_OID = ?1")?;
@@ -435,7 +811,8 @@ pub fn untrash_transact(
 }
 
 /// Unsets the flag labelling a row for garbage collection.
-pub fn untrash(table_oid: i64, row_oid: i64) -> Result<(), Error> {
+/// The rows that were trashed by a cascading delete policy when the row was trashed are restored as well.
+pub fn untrash(table_oid: i64, row_oid: i64, cascaded_rows: &Vec<(i64, i64)>) -> Result<(), Error> {
     // Start a transaction
     let mut conn = db::open()?;
     let trans: Transaction = conn.transaction()?;
@@ -444,6 +821,12 @@ pub fn untrash(table_oid: i64, row_oid: i64) -> Result<(), Error> {
     let mut completed_table_oid: HashSet<i64> = HashSet::new();
     untrash_transact(&trans, table_oid, row_oid, &mut completed_table_oid)?;
 
+    // Unset the TRASH flag for each row that was trashed by a cascading delete policy
+    for (cascaded_table_oid, cascaded_row_oid) in cascaded_rows {
+        let mut completed_table_oid: HashSet<i64> = HashSet::new();
+        untrash_transact(&trans, *cascaded_table_oid, *cascaded_row_oid, &mut completed_table_oid)?;
+    }
+
     // Commit the transaction
     trans.commit()?;
     Ok(())
@@ -456,23 +839,24 @@ pub fn change_object_type(
     inheritor_table_oid: i64,
 ) -> Result<i64, Error> {
     // Start a transaction
+    // The related rows are mapped within the transaction, so that a failure at any step leaves the row's original type intact
     let mut conn = db::open()?;
+    let trans: Transaction = conn.transaction()?;
 
     // Map all existing related rows, up and down the inheritance tree
     let mut mapped_table_oid: HashMap<i64, Option<i64>> = HashMap::new();
-    map_all_master_tables(&conn, table_oid, row_oid, &mut mapped_table_oid)?;
+    map_all_master_tables(&trans, table_oid, row_oid, &mut mapped_table_oid)?;
     mapped_table_oid.remove(&table_oid);
     let (_, deepest_untrashed_table_oid) =
-        map_all_inheritor_tables(&conn, table_oid, Some(row_oid), &mut mapped_table_oid)?;
-    println!("  Changing object type. {:?}", mapped_table_oid);
+        map_all_inheritor_tables(&trans, table_oid, Some(row_oid), &mut mapped_table_oid)?;
 
     // Trash the row + all related rows up and down the inheritance tree
-    let trans: Transaction = conn.transaction()?;
     for (related_table_oid, related_row_oid) in mapped_table_oid.iter() {
         if let Some(related_row_oid) = related_row_oid {
             let sql_update: String =
                 format!("UPDATE TABLE{related_table_oid} SET TRASH = TRUE WHERE OID = ?1");
             trans.execute(&sql_update, params![related_row_oid])?;
+            set_trashed_at_transact(&trans, *related_table_oid, *related_row_oid, true)?;
         }
     }
 
@@ -506,6 +890,275 @@ pub fn change_object_type(
     Ok(deepest_untrashed_table_oid.unwrap_or(table_oid))
 }
 
+/// Reattaches a row to a different row in one of its table's master tables.
+/// Returns the OID of the master row that the row was previously attached to.
+pub fn reparent(
+    table_oid: i64,
+    row_oid: i64,
+    master_table_oid: i64,
+    new_master_row_oid: i64,
+) -> Result<i64, Error> {
+    // Start a transaction
+    let mut conn = db::open()?;
+    let trans: Transaction = conn.transaction()?;
+
+    // Verify that the table inherits from the master table
+    if !trans.query_one(
+        "SELECT EXISTS (SELECT 1 FROM METADATA_SCHEMA_INHERITANCE_VIEW WHERE INHERITOR_SCHEMA_OID = ?1 AND MASTER_SCHEMA_OID = ?2)",
+        params![table_oid, master_table_oid],
+        |row| row.get::<_, bool>(0),
+    )? {
+        return Err(Error::AdhocError(
+            "The row cannot be reattached to a table that its table does not inherit from.",
+        ));
+    }
+
+    // Verify that the new master row exists
+    let sql_master_is_trashed: String =
+        format!("SELECT TRASH FROM TABLE{master_table_oid} WHERE OID = ?1");
+    match trans
+        .query_one(&sql_master_is_trashed, params![new_master_row_oid], |row| {
+            row.get::<_, bool>("TRASH")
+        })
+        .optional()?
+    {
+        Some(false) => {}
+        Some(true) | None => {
+            return Err(Error::AdhocError(
+                "The row cannot be reattached to a row that does not exist.",
+            ));
+        }
+    }
+
+    // Verify that the new master row is not already attached to another row in the table
+    let sql_is_attached: String = format!(
+        "SELECT EXISTS (SELECT 1 FROM TABLE{table_oid} WHERE MASTER{master_table_oid}_OID = ?1 AND OID <> ?2)"
+    );
+    if trans.query_one(
+        &sql_is_attached,
+        params![new_master_row_oid, row_oid],
+        |row| row.get::<_, bool>(0),
+    )? {
+        return Err(Error::AdhocError(
+            "The row cannot be reattached to a row that already has a row of this type attached to it.",
+        ));
+    }
+
+    // Store the old master row
+    let sql_select: String =
+        format!("SELECT MASTER{master_table_oid}_OID FROM TABLE{table_oid} WHERE OID = ?1");
+    let old_master_row_oid: i64 =
+        trans.query_one(&sql_select, params![row_oid], |row| row.get(0))?;
+
+    // Reattach the row
+    let sql_update: String =
+        format!("UPDATE TABLE{table_oid} SET MASTER{master_table_oid}_OID = ?1 WHERE OID = ?2");
+    trans.execute(&sql_update, params![new_master_row_oid, row_oid])?;
+
+    // Commit the transaction
+    trans.commit()?;
+    Ok(old_master_row_oid)
+}
+
+/// A reference from a cell to a row, which was repointed to another row.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReassignedReference {
+    pub table_oid: i64,
+    pub column_oid: i64,
+    pub row_oid: i64,
+
+    /// If the cell already selected the new row in a Multiselect column, the selection of the old row is removed instead.
+    /// This holds the ordering of the removed selection, so that it can be restored.
+    pub merged_ordering: Option<i64>,
+}
+
+/// Lists the Object, Select, and Multiselect columns that reference rows of a table.
+/// Each column is listed along with the table it belongs to, and whether it is a Multiselect column.
+fn get_referencing_columns_transact(trans: &Transaction, table_oid: i64) -> Result<Vec<(i64, i64, bool)>, Error> {
+    let mut referencing_columns: Vec<(i64, i64, bool)> = Vec::new();
+    for reference_result in trans
+        .prepare(
+            "
+            SELECT c.OID, c.SCHEMA_OID, FALSE AS IS_MULTISELECT
+            FROM METADATA_COLUMN_VIEW c
+            INNER JOIN METADATA_COLUMN_TYPE__OBJECT typ ON typ.OID = c.TYPE_OID
+            WHERE typ.TABLE_OID = ?1
+            UNION ALL
+            SELECT c.OID, c.SCHEMA_OID, FALSE AS IS_MULTISELECT
+            FROM METADATA_COLUMN_VIEW c
+            INNER JOIN METADATA_COLUMN_TYPE__SELECT typ ON typ.OID = c.TYPE_OID
+            WHERE typ.TABLE_OID = ?1
+            UNION ALL
+            SELECT c.OID, c.SCHEMA_OID, TRUE AS IS_MULTISELECT
+            FROM METADATA_COLUMN_VIEW c
+            INNER JOIN METADATA_COLUMN_TYPE__MULTISELECT typ ON typ.OID = c.TYPE_OID
+            WHERE typ.TABLE_OID = ?1
+            ",
+        )?
+        .query_map(params![table_oid], |row| {
+            Ok((row.get::<_, i64>("OID")?, row.get::<_, i64>("SCHEMA_OID")?, row.get::<_, bool>("IS_MULTISELECT")?))
+        })?
+    {
+        referencing_columns.push(reference_result?);
+    }
+    Ok(referencing_columns)
+}
+
+/// Repoints every reference to a row so that it references another row of the same table instead.
+/// If a list of references is given, only those references are repointed.
+/// Returns the references that were repointed.
+pub fn reassign_references(
+    table_oid: i64,
+    from_row_oid: i64,
+    to_row_oid: i64,
+    only_references: Option<&Vec<ReassignedReference>>,
+) -> Result<Vec<ReassignedReference>, Error> {
+    if from_row_oid == to_row_oid {
+        return Err(Error::AdhocError("References cannot be reassigned to the row that they already reference."));
+    }
+
+    // Start a transaction
+    let mut conn = db::open()?;
+    let trans: Transaction = conn.transaction()?;
+
+    // Verify that the new row exists
+    let sql_is_trashed: String = format!("SELECT TRASH FROM TABLE{table_oid} WHERE OID = ?1");
+    match trans
+        .query_one(&sql_is_trashed, params![to_row_oid], |row| row.get::<_, bool>("TRASH"))
+        .optional()?
+    {
+        Some(false) => {}
+        Some(true) | None => {
+            return Err(Error::AdhocError("References cannot be reassigned to a row that does not exist."));
+        }
+    }
+
+    let mut reassigned_references: Vec<ReassignedReference> = Vec::new();
+    for (column_oid, referencing_table_oid, is_multiselect) in get_referencing_columns_transact(&trans, table_oid)? {
+        // Find the rows that reference the old row
+        let sql_referencing_rows: String = if is_multiselect {
+            format!("SELECT TABLE{referencing_table_oid}_OID FROM MULTISELECT{column_oid} WHERE TABLE{table_oid}_OID = ?1")
+        } else {
+            format!("SELECT OID FROM TABLE{referencing_table_oid} WHERE COLUMN{column_oid} = ?1")
+        };
+        let mut referencing_row_oids: Vec<i64> = Vec::new();
+        for referencing_row_result in trans
+            .prepare(&sql_referencing_rows)?
+            .query_map(params![from_row_oid], |row| row.get::<_, i64>(0))?
+        {
+            let referencing_row_oid: i64 = referencing_row_result?;
+            if let Some(only_references) = only_references {
+                if !only_references.iter().any(|r| r.column_oid == column_oid && r.row_oid == referencing_row_oid) {
+                    continue;
+                }
+            }
+            referencing_row_oids.push(referencing_row_oid);
+        }
+        if referencing_row_oids.len() == 0 {
+            continue;
+        }
+
+        // An Object column links each row to its own object, so the new row cannot be linked from another cell
+        if !is_multiselect && trans.query_one(
+            "SELECT EXISTS (SELECT 1 FROM METADATA_COLUMN_TYPE__OBJECT typ INNER JOIN METADATA_COLUMN c ON c.TYPE_OID = typ.OID WHERE c.OID = ?1)",
+            params![column_oid],
+            |row| row.get::<_, bool>(0),
+        )? {
+            let sql_is_linked: String = format!("SELECT EXISTS (SELECT 1 FROM TABLE{referencing_table_oid} WHERE COLUMN{column_oid} = ?1)");
+            if referencing_row_oids.len() > 1 || trans.query_one(&sql_is_linked, params![to_row_oid], |row| row.get::<_, bool>(0))? {
+                return Err(Error::AdhocError("References cannot be reassigned to an object that is already linked from another row."));
+            }
+        }
+
+        // Repoint each reference
+        for referencing_row_oid in referencing_row_oids {
+            let mut merged_ordering: Option<i64> = None;
+            if is_multiselect {
+                let sql_is_selected: String = format!(
+                    "SELECT EXISTS (SELECT 1 FROM MULTISELECT{column_oid} WHERE TABLE{referencing_table_oid}_OID = ?1 AND TABLE{table_oid}_OID = ?2)"
+                );
+                if trans.query_one(&sql_is_selected, params![referencing_row_oid, to_row_oid], |row| row.get::<_, bool>(0))? {
+                    // The new row is already selected, so remove the selection of the old row
+                    let sql_ordering: String = format!(
+                        "SELECT ORDERING FROM MULTISELECT{column_oid} WHERE TABLE{referencing_table_oid}_OID = ?1 AND TABLE{table_oid}_OID = ?2"
+                    );
+                    merged_ordering = Some(trans.query_one(&sql_ordering, params![referencing_row_oid, from_row_oid], |row| row.get(0))?);
+                    let sql_delete: String = format!(
+                        "DELETE FROM MULTISELECT{column_oid} WHERE TABLE{referencing_table_oid}_OID = ?1 AND TABLE{table_oid}_OID = ?2"
+                    );
+                    trans.execute(&sql_delete, params![referencing_row_oid, from_row_oid])?;
+                } else {
+                    let sql_update: String = format!(
+                        "UPDATE MULTISELECT{column_oid} SET TABLE{table_oid}_OID = ?1 WHERE TABLE{referencing_table_oid}_OID = ?2 AND TABLE{table_oid}_OID = ?3"
+                    );
+                    trans.execute(&sql_update, params![to_row_oid, referencing_row_oid, from_row_oid])?;
+                }
+            } else {
+                let sql_update: String = format!("UPDATE TABLE{referencing_table_oid} SET COLUMN{column_oid} = ?1 WHERE OID = ?2");
+                trans.execute(&sql_update, params![to_row_oid, referencing_row_oid])?;
+            }
+            touch_transact(&trans, referencing_table_oid, referencing_row_oid, false)?;
+            reassigned_references.push(ReassignedReference {
+                table_oid: referencing_table_oid,
+                column_oid,
+                row_oid: referencing_row_oid,
+                merged_ordering,
+            });
+        }
+    }
+
+    // Commit the transaction
+    trans.commit()?;
+    Ok(reassigned_references)
+}
+
+/// Points references that were reassigned to a row back at the row that they referenced before.
+pub fn restore_references(
+    table_oid: i64,
+    from_row_oid: i64,
+    to_row_oid: i64,
+    references: &Vec<ReassignedReference>,
+) -> Result<(), Error> {
+    // Start a transaction
+    let mut conn = db::open()?;
+    let trans: Transaction = conn.transaction()?;
+
+    for reference in references {
+        let referencing_table_oid: i64 = reference.table_oid;
+        let column_oid: i64 = reference.column_oid;
+        let is_multiselect: bool = trans.query_one(
+            "SELECT EXISTS (SELECT 1 FROM METADATA_COLUMN_TYPE__MULTISELECT typ INNER JOIN METADATA_COLUMN c ON c.TYPE_OID = typ.OID WHERE c.OID = ?1)",
+            params![column_oid],
+            |row| row.get::<_, bool>(0),
+        )?;
+        match (is_multiselect, reference.merged_ordering) {
+            (true, Some(ordering)) => {
+                // The selection of the original row was removed, so add it back alongside the selection of the new row
+                let sql_insert: String = format!(
+                    "INSERT INTO MULTISELECT{column_oid} (TABLE{referencing_table_oid}_OID, TABLE{table_oid}_OID, ORDERING) VALUES (?1, ?2, ?3)"
+                );
+                trans.execute(&sql_insert, params![reference.row_oid, to_row_oid, ordering])?;
+            }
+            (true, None) => {
+                let sql_update: String = format!(
+                    "UPDATE MULTISELECT{column_oid} SET TABLE{table_oid}_OID = ?1 WHERE TABLE{referencing_table_oid}_OID = ?2 AND TABLE{table_oid}_OID = ?3"
+                );
+                trans.execute(&sql_update, params![to_row_oid, reference.row_oid, from_row_oid])?;
+            }
+            (false, _) => {
+                let sql_update: String = format!("UPDATE TABLE{referencing_table_oid} SET COLUMN{column_oid} = ?1 WHERE OID = ?2 AND COLUMN{column_oid} = ?3");
+                trans.execute(&sql_update, params![to_row_oid, reference.row_oid, from_row_oid])?;
+            }
+        }
+        touch_transact(&trans, referencing_table_oid, reference.row_oid, false)?;
+    }
+
+    // Commit the transaction
+    trans.commit()?;
+    Ok(())
+}
+
 /// Reorders a row in a table.
 pub fn reorder(table_oid: i64, row_oid: i64, new_row_oid: Option<i64>) -> Result<i64, Error> {
     // Start a transaction
@@ -553,3 +1206,630 @@ pub fn reorder(table_oid: i64, row_oid: i64, new_row_oid: Option<i64>) -> Result
     trans.commit()?;
     Ok(new_row_oid)
 }
+
+/// Finds groups of rows in a table that display the same values in every one of the given columns.
+/// Returns the OIDs of the rows in each group with more than one member.
+pub fn find_duplicates(table_oid: i64, column_oids: Vec<i64>) -> Result<Vec<Vec<i64>>, Error> {
+    let conn = db::open()?;
+    if column_oids.len() == 0 {
+        return Err(Error::AdhocError("At least one column must be chosen to find duplicate rows."));
+    }
+
+    // Check that each column is displayed in the table
+    let mut group_by_exprs: Vec<String> = Vec::new();
+    for column_oid in column_oids.iter() {
+        let is_table_column: bool = conn.query_one(
+            "SELECT EXISTS (SELECT 1 FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND COLUMN_OID = ?2 AND NOT IS_SUBREPORT)",
+            params![table_oid, column_oid],
+            |row| row.get(0),
+        )?;
+        if !is_table_column {
+            return Err(Error::AdhocError("Duplicate rows can only be found using columns of the table."));
+        }
+        group_by_exprs.push(format!("COLUMN{column_oid}_LABEL"));
+    }
+
+    // Group the rows by the display values of the columns
+    let sql_select: String = format!(
+        "
+        SELECT GROUP_CONCAT(OID, ',' ORDER BY OID) AS ROW_OIDS 
+        FROM SCHEMA{table_oid}_VIEW 
+        GROUP BY {} 
+        HAVING COUNT(*) > 1 
+        ORDER BY MIN(OID)
+        ",
+        group_by_exprs.join(", ")
+    );
+    let mut duplicates: Vec<Vec<i64>> = Vec::new();
+    for row_result in conn
+        .prepare(&sql_select)?
+        .query_map([], |row| row.get::<_, String>("ROW_OIDS"))?
+    {
+        let row_oids: String = row_result?;
+        duplicates.push(
+            row_oids
+                .split(',')
+                .filter_map(|row_oid| row_oid.parse::<i64>().ok())
+                .collect(),
+        );
+    }
+    Ok(duplicates)
+}
+
+/// The number of rows of a base table that are currently of a particular subtype.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtypeCount {
+    pub table_oid: i64,
+    pub table_name: String,
+    pub row_count: i64,
+}
+
+/// Collects the master-inheritor pairs of every table that inherits from a table, whether directly or through intermediate tables.
+fn collect_inheritor_tables(conn: &Connection, table_oid: i64, collected_table_oid: &mut HashSet<i64>, inheritances: &mut Vec<(i64, i64)>) -> Result<(), Error> {
+    if collected_table_oid.insert(table_oid) {
+        for inheritor_table_oid_result in conn.prepare("SELECT inh.INHERITOR_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_VIEW inh INNER JOIN METADATA_SCHEMA s ON s.OID = inh.INHERITOR_SCHEMA_OID WHERE inh.MASTER_SCHEMA_OID = ?1")?.query_map(params![table_oid], |row| row.get::<_, i64>(0))? {
+            let inheritor_table_oid: i64 = inheritor_table_oid_result?;
+            if conn.table_exists(Some("main"), &format!("TABLE{inheritor_table_oid}"))? {
+                inheritances.push((table_oid, inheritor_table_oid));
+                collect_inheritor_tables(conn, inheritor_table_oid, collected_table_oid, inheritances)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Counts the rows of a table by their deepest subtype in the inheritance tree.
+/// Rows that are not of any subtype are counted under the table itself.
+pub fn get_subtype_counts(base_table_oid: i64) -> Result<Vec<SubtypeCount>, Error> {
+    let conn = db::open()?;
+
+    // Map the inheritance tree below the base table once, up front
+    let mut inheritances: Vec<(i64, i64)> = Vec::new();
+    collect_inheritor_tables(&conn, base_table_oid, &mut HashSet::new(), &mut inheritances)?;
+
+    // Follow each row of the base table down through the untrashed rows of its inheritor tables
+    let sql_inheritor_rows: String = inheritances
+        .iter()
+        .map(|(master_table_oid, inheritor_table_oid)| format!(
+            "SELECT {master_table_oid} AS MASTER_TABLE_OID, MASTER{master_table_oid}_OID AS MASTER_OID, {inheritor_table_oid} AS TABLE_OID, OID FROM TABLE{inheritor_table_oid} WHERE NOT TRASH"
+        ))
+        .chain(std::iter::once(String::from("SELECT NULL, NULL, NULL, NULL WHERE FALSE")))
+        .collect::<Vec<String>>()
+        .join(" UNION ALL ");
+    let sql_select: String = format!(
+        "
+        WITH RECURSIVE INHERITOR_ROW (MASTER_TABLE_OID, MASTER_OID, TABLE_OID, OID) AS (
+            {sql_inheritor_rows}
+        ), SUBTYPE_ROW (BASE_OID, TABLE_OID, OID, DEPTH) AS (
+            SELECT OID, {base_table_oid}, OID, 0 FROM TABLE{base_table_oid} WHERE NOT TRASH
+            UNION ALL
+            SELECT sub.BASE_OID, inh.TABLE_OID, inh.OID, sub.DEPTH + 1
+            FROM SUBTYPE_ROW sub
+            INNER JOIN INHERITOR_ROW inh ON inh.MASTER_TABLE_OID = sub.TABLE_OID AND inh.MASTER_OID = sub.OID
+        ), DEEPEST_SUBTYPE_ROW AS (
+            SELECT TABLE_OID, MAX(DEPTH) FROM SUBTYPE_ROW GROUP BY BASE_OID
+        )
+        SELECT d.TABLE_OID, s.NAME, COUNT(*) AS ROW_COUNT
+        FROM DEEPEST_SUBTYPE_ROW d
+        INNER JOIN METADATA_SCHEMA s ON s.OID = d.TABLE_OID
+        GROUP BY d.TABLE_OID, s.NAME
+        ORDER BY ROW_COUNT DESC, s.NAME
+        "
+    );
+
+    let mut subtype_counts: Vec<SubtypeCount> = Vec::new();
+    for subtype_count_result in conn.prepare(&sql_select)?.query_map([], |row| {
+        Ok(SubtypeCount {
+            table_oid: row.get("TABLE_OID")?,
+            table_name: row.get("NAME")?,
+            row_count: row.get("ROW_COUNT")?,
+        })
+    })? {
+        subtype_counts.push(subtype_count_result?);
+    }
+    Ok(subtype_counts)
+}
+
+/// A subtype that a row could be changed to, with the columns the change would hide or add.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RetypeOption {
+    pub table_oid: i64,
+    pub table_name: String,
+    pub is_current: bool,
+    pub columns_lost: i64,
+    pub columns_gained: i64,
+}
+
+/// Collects a table and every table it inherits from, whether directly or through intermediate tables.
+fn collect_master_tables(conn: &Connection, table_oid: i64, collected_table_oid: &mut HashSet<i64>) -> Result<(), Error> {
+    if collected_table_oid.insert(table_oid) {
+        for master_table_oid_result in conn.prepare("SELECT MASTER_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_VIEW WHERE INHERITOR_SCHEMA_OID = ?1")?.query_map(params![table_oid], |row| row.get::<_, i64>(0))? {
+            collect_master_tables(conn, master_table_oid_result?, collected_table_oid)?;
+        }
+    }
+    Ok(())
+}
+
+/// Lists the subtypes that a row of a base table could be changed to.
+/// Each option counts the columns whose values would be hidden by the change, and the columns that would be newly added.
+pub fn get_retype_options(base_table_oid: i64, base_row_oid: i64) -> Result<Vec<RetypeOption>, Error> {
+    let conn = db::open()?;
+    let sql_is_trashed: String = format!("SELECT TRASH FROM TABLE{base_table_oid} WHERE OID = ?1");
+    match conn.query_one(&sql_is_trashed, params![base_row_oid], |row| row.get::<_, bool>(0)).optional()? {
+        Some(false) => {}
+        Some(true) => return Err(Error::AdhocError("Cannot change the type of a deleted row.")),
+        None => return Err(Error::AdhocError("The row does not exist.")),
+    }
+
+    // Map all existing related rows, up and down the inheritance tree
+    let mut mapped_table_oid: HashMap<i64, Option<i64>> = HashMap::new();
+    map_all_master_tables(&conn, base_table_oid, base_row_oid, &mut mapped_table_oid)?;
+    mapped_table_oid.remove(&base_table_oid);
+    let master_table_oids: HashSet<i64> = mapped_table_oid.keys().cloned().collect();
+    let (_, deepest_table_oid) =
+        map_all_inheritor_tables(&conn, base_table_oid, Some(base_row_oid), &mut mapped_table_oid)?;
+    let current_table_oid: i64 = deepest_table_oid.unwrap_or(base_table_oid);
+
+    // Determine which tables the row currently has an untrashed row in
+    let mut current_table_oids: HashSet<i64> = HashSet::new();
+    collect_master_tables(&conn, current_table_oid, &mut current_table_oids)?;
+
+    // Count the columns of each table once
+    let mut column_counts: HashMap<i64, i64> = HashMap::new();
+    let mut count_columns = |table_oid: i64| -> Result<i64, Error> {
+        if let Some(column_count) = column_counts.get(&table_oid) {
+            return Ok(*column_count);
+        }
+        let column_count: i64 = conn.query_one(
+            "SELECT COUNT(*) FROM METADATA_COLUMN WHERE SCHEMA_OID = ?1 AND NOT TRASH",
+            params![table_oid],
+            |row| row.get(0),
+        )?;
+        column_counts.insert(table_oid, column_count);
+        Ok(column_count)
+    };
+
+    // Compare the tables of each candidate subtype against the tables the row currently has
+    let mut retype_options: Vec<RetypeOption> = Vec::new();
+    for candidate_table_oid in mapped_table_oid.keys().filter(|table_oid| !master_table_oids.contains(table_oid)) {
+        let mut candidate_table_oids: HashSet<i64> = HashSet::new();
+        collect_master_tables(&conn, *candidate_table_oid, &mut candidate_table_oids)?;
+
+        let mut columns_lost: i64 = 0;
+        for table_oid in current_table_oids.difference(&candidate_table_oids) {
+            columns_lost += count_columns(*table_oid)?;
+        }
+        let mut columns_gained: i64 = 0;
+        for table_oid in candidate_table_oids.difference(&current_table_oids) {
+            columns_gained += count_columns(*table_oid)?;
+        }
+
+        let table_name: String = conn.query_one(
+            "SELECT NAME FROM METADATA_SCHEMA WHERE OID = ?1",
+            params![candidate_table_oid],
+            |row| row.get(0),
+        )?;
+        retype_options.push(RetypeOption {
+            table_oid: *candidate_table_oid,
+            table_name,
+            is_current: *candidate_table_oid == current_table_oid,
+            columns_lost,
+            columns_gained,
+        });
+    }
+    retype_options.sort_by(|a, b| a.table_name.cmp(&b.table_name));
+    Ok(retype_options)
+}
+
+/// Checks that a referenced row exists and has not been trashed.
+fn check_referenced_row_transact(conn: &Connection, table_oid: i64, row_oid: i64) -> Result<(), Error> {
+    let sql_is_trashed: String = format!("SELECT TRASH FROM TABLE{table_oid} WHERE OID = ?1");
+    match conn
+        .query_one(&sql_is_trashed, params![row_oid], |row| row.get::<_, bool>("TRASH"))
+        .optional()?
+    {
+        Some(false) => Ok(()),
+        Some(true) => Err(Error::AdhocError("The referenced row has been deleted.")),
+        None => Err(Error::AdhocError("The referenced row does not exist.")),
+    }
+}
+
+/// Gets the label displayed for a row when it is referenced by an Object, Select, or Multiselect column.
+pub fn get_label(table_oid: i64, row_oid: i64) -> Result<Option<String>, Error> {
+    let conn = db::open()?;
+
+    // Check that the row exists and has not been trashed
+    check_referenced_row_transact(&conn, table_oid, row_oid)?;
+
+    // Get the label of the row
+    let sql_label: String = format!(
+        "SELECT COALESCE(l.PLAIN_LABEL, l.JSON_LABEL) AS LABEL FROM SCHEMA{table_oid}_LABEL_VIEW l WHERE l.OID = ?1"
+    );
+    Ok(conn
+        .query_one(&sql_label, params![row_oid], |row| row.get::<_, Option<String>>("LABEL"))
+        .optional()?
+        .flatten())
+}
+
+/// The display value of one primary key column of a referenced row.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceKeyPart {
+    pub column_oid: i64,
+    pub column_name: String,
+    pub label: Option<String>,
+}
+
+/// Gets the display value of each primary key column of a row, in the order the columns are displayed.
+/// These are the parts joined together in the label of the row when it is referenced.
+pub fn get_key_parts(table_oid: i64, row_oid: i64) -> Result<Vec<ReferenceKeyPart>, Error> {
+    let conn = db::open()?;
+
+    // Check that the row exists and has not been trashed
+    check_referenced_row_transact(&conn, table_oid, row_oid)?;
+
+    // Find the primary key columns of the table, including inherited columns
+    let mut key_columns: Vec<(i64, String)> = Vec::new();
+    for column_result in conn
+        .prepare(
+            "
+            SELECT sc.COLUMN_OID, c.NAME
+            FROM METADATA_SCHEMA_COLUMN_VIEW sc
+            INNER JOIN METADATA_COLUMN_VIEW c ON c.OID = sc.COLUMN_OID
+            WHERE sc.SCHEMA_OID = ?1 AND sc.IS_PRIMARY_KEY AND sc.IS_REQUIRED
+            ORDER BY sc.ORDERING
+            ",
+        )?
+        .query_map(params![table_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, String>("NAME")?)))?
+    {
+        key_columns.push(column_result?);
+    }
+    if key_columns.len() == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Get the display value of each primary key column
+    let sql_labels: String = format!(
+        "SELECT {} FROM SCHEMA{table_oid}_VIEW WHERE OID = ?1",
+        key_columns
+            .iter()
+            .map(|(column_oid, _)| format!("COLUMN{column_oid}_LABEL"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let labels: Vec<Option<String>> = conn
+        .query_one(&sql_labels, params![row_oid], |row| {
+            (0..key_columns.len())
+                .map(|i| row.get::<_, Option<String>>(i))
+                .collect::<Result<Vec<Option<String>>, _>>()
+        })
+        .optional()?
+        .unwrap_or_else(|| vec![None; key_columns.len()]);
+    Ok(key_columns
+        .into_iter()
+        .zip(labels.into_iter())
+        .map(|((column_oid, column_name), label)| ReferenceKeyPart {
+            column_oid,
+            column_name,
+            label,
+        })
+        .collect())
+}
+
+/// Finds the non-trashed row of a table whose label exactly matches a display value.
+pub fn find_by_label(table_oid: i64, display_value: &str) -> Result<i64, Error> {
+    let conn = db::open()?;
+
+    // Look for at most two matches, to tell a unique match apart from an ambiguous one
+    let sql_select: String = format!(
+        "SELECT l.OID FROM SCHEMA{table_oid}_LABEL_VIEW l INNER JOIN TABLE{table_oid} t ON t.OID = l.OID WHERE NOT t.TRASH AND COALESCE(l.PLAIN_LABEL, l.JSON_LABEL) = ?1 ORDER BY l.OID LIMIT 2"
+    );
+    let mut matched_oids: Vec<i64> = Vec::new();
+    for oid_result in conn
+        .prepare(&sql_select)?
+        .query_map(params![display_value], |row| row.get::<_, i64>("OID"))?
+    {
+        matched_oids.push(oid_result?);
+    }
+    match matched_oids[..] {
+        [oid] => Ok(oid),
+        [] => Err(Error::AdhocError("No row matches the entered value.")),
+        _ => Err(Error::AdhocError("More than one row matches the entered value.")),
+    }
+}
+
+/// Gets the OID that the next row inserted into a table would receive, if no OID is specified.
+pub fn get_next_oid(table_oid: i64) -> Result<i64, Error> {
+    let conn = db::open()?;
+
+    // Trashed rows still occupy their OID, so they are included
+    let sql_select: String = format!("SELECT COALESCE(MAX(OID), 0) + 1 AS NEXT_OID FROM TABLE{table_oid}");
+    Ok(conn.query_one(&sql_select, [], |row| row.get::<_, i64>("NEXT_OID"))?)
+}
+
+/// Gets the lowest and highest OIDs of the rows in a table that are not trashed, or None if there are no such rows.
+pub fn get_oid_range(table_oid: i64) -> Result<Option<(i64, i64)>, Error> {
+    let conn = db::open()?;
+    let sql_select: String = format!("SELECT MIN(OID) AS MIN_OID, MAX(OID) AS MAX_OID FROM TABLE{table_oid} WHERE NOT TRASH");
+    let (min_oid, max_oid) = conn.query_one(&sql_select, [], |row| {
+        Ok((row.get::<_, Option<i64>>("MIN_OID")?, row.get::<_, Option<i64>>("MAX_OID")?))
+    })?;
+    Ok(min_oid.zip(max_oid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::cell::DataCellValue;
+    use crate::data::testing;
+
+    /// Creates a table of items and a table of lists referencing those items through a Multiselect column with the given delete policy.
+    /// Returns the OIDs of the item table, the list table, and the Multiselect column.
+    fn create_multiselect_tables(reference_on_delete: column::ReferenceOnDelete) -> (i64, i64, i64) {
+        let item_table_oid: i64 = testing::create_table("Item", &[]);
+        let list_table_oid: i64 = testing::create_table("List", &[]);
+        let mut column: column::FullMetadata = testing::column_metadata(
+            list_table_oid,
+            "Items",
+            column_type::ColumnType::Multiselect { oid: 0, table_oid: item_table_oid },
+        );
+        column.reference_on_delete = reference_on_delete;
+        column.create().unwrap();
+        (item_table_oid, list_table_oid, column.oid)
+    }
+
+    fn is_trashed(table_oid: i64, row_oid: i64) -> bool {
+        testing::query_one(&format!("SELECT TRASH FROM TABLE{table_oid} WHERE OID = {row_oid}"))
+    }
+
+    #[test]
+    fn trash_is_restricted_by_multiselect_references() {
+        let _db = testing::open_database();
+        let (item_table_oid, list_table_oid, column_oid) = create_multiselect_tables(column::ReferenceOnDelete::Restrict);
+        let item_row_oid: i64 = testing::insert_row(item_table_oid);
+        let list_row_oid: i64 = testing::insert_row(list_table_oid);
+        testing::set_cell(list_table_oid, column_oid, list_row_oid, DataCellValue::Multiselect { linked_row_oid: vec![item_row_oid] });
+
+        assert!(matches!(trash(item_table_oid, item_row_oid), Err(Error::RestrictedDelete { .. })));
+        assert!(!is_trashed(item_table_oid, item_row_oid));
+    }
+
+    #[test]
+    fn trash_cascades_through_multiselect_references_and_untrash_restores_them() {
+        let _db = testing::open_database();
+        let (item_table_oid, list_table_oid, column_oid) = create_multiselect_tables(column::ReferenceOnDelete::Cascade);
+        let item_row_oid: i64 = testing::insert_row(item_table_oid);
+        let list_row_oid: i64 = testing::insert_row(list_table_oid);
+        let unrelated_list_row_oid: i64 = testing::insert_row(list_table_oid);
+        testing::set_cell(list_table_oid, column_oid, list_row_oid, DataCellValue::Multiselect { linked_row_oid: vec![item_row_oid] });
+
+        let (trashed_row, cascaded_rows) = trash(item_table_oid, item_row_oid).unwrap();
+        assert_eq!(trashed_row, Some((item_table_oid, item_row_oid)));
+        assert_eq!(cascaded_rows, vec![(list_table_oid, list_row_oid)]);
+        assert!(is_trashed(list_table_oid, list_row_oid));
+        assert!(!is_trashed(list_table_oid, unrelated_list_row_oid));
+
+        untrash(item_table_oid, item_row_oid, &cascaded_rows).unwrap();
+        assert!(!is_trashed(item_table_oid, item_row_oid));
+        assert!(!is_trashed(list_table_oid, list_row_oid));
+    }
+
+    #[test]
+    fn trash_cascades_through_select_references() {
+        let _db = testing::open_database();
+        let item_table_oid: i64 = testing::create_table("Item", &[]);
+        let list_table_oid: i64 = testing::create_table("List", &[]);
+        let mut column: column::FullMetadata = testing::column_metadata(
+            list_table_oid,
+            "Item",
+            column_type::ColumnType::Select { oid: 0, table_oid: item_table_oid },
+        );
+        column.reference_on_delete = column::ReferenceOnDelete::Cascade;
+        column.create().unwrap();
+        let item_row_oid: i64 = testing::insert_row(item_table_oid);
+        let list_row_oid: i64 = testing::insert_row(list_table_oid);
+        testing::set_cell(list_table_oid, column.oid, list_row_oid, DataCellValue::Select { linked_row_oid: Some(item_row_oid) });
+
+        let (_, cascaded_rows) = trash(item_table_oid, item_row_oid).unwrap();
+        assert_eq!(cascaded_rows, vec![(list_table_oid, list_row_oid)]);
+        assert!(is_trashed(list_table_oid, list_row_oid));
+    }
+
+    /// Creates a table with rows named "A" and "B" at OIDs 2 and 4, returning the OIDs of the table and its name column.
+    fn create_gapped_table() -> (i64, i64) {
+        let table_oid: i64 = testing::create_table("Table", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        for (row_oid, name) in [(2, "A"), (4, "B")] {
+            insert(table_oid, Some(row_oid), InsertMode::Fail, None).unwrap();
+            testing::set_cell(table_oid, column_oid, row_oid, DataCellValue::Text(Some(String::from(name))));
+        }
+        (table_oid, column_oid)
+    }
+
+    /// Lists the OID and name of each row of a table, with blank names shown as "_".
+    fn list_rows(table_oid: i64, column_oid: i64) -> String {
+        testing::query_one(&format!("SELECT GROUP_CONCAT(OID || ':' || COALESCE(COLUMN{column_oid}, '_'), ',') FROM (SELECT * FROM TABLE{table_oid} ORDER BY OID)"))
+    }
+
+    #[test]
+    fn shift_down_insert_mode_moves_the_occupying_rows() {
+        let _db = testing::open_database();
+        let (table_oid, column_oid) = create_gapped_table();
+        assert_eq!(insert(table_oid, Some(2), InsertMode::ShiftDown, None).unwrap(), 2);
+        assert_eq!(list_rows(table_oid, column_oid), "2:_,3:A,5:B");
+    }
+
+    #[test]
+    fn before_insert_mode_takes_the_free_oid_before_the_occupying_row() {
+        let _db = testing::open_database();
+        let (table_oid, column_oid) = create_gapped_table();
+        assert_eq!(insert(table_oid, Some(4), InsertMode::Before, None).unwrap(), 3);
+        assert_eq!(list_rows(table_oid, column_oid), "2:A,3:_,4:B");
+
+        // Without a free OID before the occupying row, the rows are shifted down
+        assert_eq!(insert(table_oid, Some(3), InsertMode::Before, None).unwrap(), 3);
+        assert_eq!(list_rows(table_oid, column_oid), "2:A,3:_,4:_,5:B");
+    }
+
+    #[test]
+    fn after_insert_mode_places_the_row_after_the_occupying_row() {
+        let _db = testing::open_database();
+        let (table_oid, column_oid) = create_gapped_table();
+        assert_eq!(insert(table_oid, Some(2), InsertMode::After, None).unwrap(), 3);
+        assert_eq!(list_rows(table_oid, column_oid), "2:A,3:_,4:B");
+
+        // If the OID after the occupying row is also taken, the rows from there on are shifted down
+        assert_eq!(insert(table_oid, Some(2), InsertMode::After, None).unwrap(), 3);
+        assert_eq!(list_rows(table_oid, column_oid), "2:A,3:_,4:_,5:B");
+    }
+
+    #[test]
+    fn fail_insert_mode_rejects_an_occupied_oid() {
+        let _db = testing::open_database();
+        let (table_oid, column_oid) = create_gapped_table();
+        assert!(insert(table_oid, Some(2), InsertMode::Fail, None).is_err());
+        assert_eq!(list_rows(table_oid, column_oid), "2:A,4:B");
+
+        // A free OID is used as is
+        assert_eq!(insert(table_oid, Some(3), InsertMode::Fail, None).unwrap(), 3);
+        assert_eq!(list_rows(table_oid, column_oid), "2:A,3:_,4:B");
+    }
+
+    #[test]
+    fn change_object_type_rolls_back_when_a_step_fails() {
+        let _db = testing::open_database();
+        let creature_table_oid: i64 = testing::create_table("Creature", &[]);
+        let goblin_table_oid: i64 = testing::create_table("Goblin", &[creature_table_oid]);
+        let troll_table_oid: i64 = testing::create_table("Troll", &[creature_table_oid]);
+        let goblin_row_oid: i64 = testing::insert_row(goblin_table_oid);
+        let creature_row_oid: i64 = testing::query_one(&format!("SELECT MASTER{creature_table_oid}_OID FROM TABLE{goblin_table_oid} WHERE OID = {goblin_row_oid}"));
+
+        // Make the insert of the new Troll row fail, after the Goblin row has already been trashed
+        db::open().unwrap().execute_batch(&format!(
+            "CREATE TRIGGER FAIL_TROLL_INSERT BEFORE INSERT ON TABLE{troll_table_oid} BEGIN SELECT RAISE(ABORT, 'Injected failure'); END;"
+        )).unwrap();
+        assert!(change_object_type(goblin_table_oid, goblin_row_oid, troll_table_oid).is_err());
+
+        // The row keeps its original type
+        assert!(!testing::query_one::<bool>(&format!("SELECT TRASH FROM TABLE{goblin_table_oid} WHERE OID = {goblin_row_oid}")));
+        assert!(!testing::query_one::<bool>(&format!("SELECT TRASH FROM TABLE{creature_table_oid} WHERE OID = {creature_row_oid}")));
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM TABLE{troll_table_oid}")), 0);
+    }
+
+    #[test]
+    fn insert_populates_column_default_values() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let mut column: column::FullMetadata = testing::column_metadata(
+            table_oid,
+            "Name",
+            column_type::ColumnType::Primitive(column_type::Primitive::PlainText),
+        );
+        column.default_value = Some(String::from("Unnamed"));
+        column.create().unwrap();
+        let blank_column_oid: i64 = testing::create_column(
+            table_oid,
+            "Notes",
+            column_type::ColumnType::Primitive(column_type::Primitive::PlainText),
+        );
+
+        let row_oid: i64 = testing::insert_row(table_oid);
+        assert_eq!(
+            testing::query_one::<String>(&format!("SELECT COLUMN{} FROM TABLE{table_oid} WHERE OID = {row_oid}", column.oid)),
+            "Unnamed"
+        );
+        assert_eq!(
+            testing::query_one::<Option<String>>(&format!("SELECT COLUMN{blank_column_oid} FROM TABLE{table_oid} WHERE OID = {row_oid}")),
+            None
+        );
+    }
+
+    #[test]
+    fn subtype_counts_count_rows_by_deepest_untrashed_subtype() {
+        let _db = testing::open_database();
+        let creature_table_oid: i64 = testing::create_table("Creature", &[]);
+        let goblin_table_oid: i64 = testing::create_table("Goblin", &[creature_table_oid]);
+        let chief_table_oid: i64 = testing::create_table("Chief", &[goblin_table_oid]);
+        testing::insert_row(creature_table_oid);
+        testing::insert_row(goblin_table_oid);
+        testing::insert_row(goblin_table_oid);
+        testing::insert_row(chief_table_oid);
+        let demoted_chief_row_oid: i64 = testing::insert_row(chief_table_oid);
+
+        // A row whose Chief part alone is trashed counts as a Goblin, and a fully trashed row is not counted
+        let demoted_goblin_row_oid: i64 = testing::query_one(&format!("SELECT MASTER{goblin_table_oid}_OID FROM TABLE{chief_table_oid} WHERE OID = {demoted_chief_row_oid}"));
+        change_object_type(chief_table_oid, demoted_chief_row_oid, goblin_table_oid).unwrap();
+        assert!(is_trashed(chief_table_oid, demoted_chief_row_oid));
+        assert!(!is_trashed(goblin_table_oid, demoted_goblin_row_oid));
+        let trashed_chief_row_oid: i64 = testing::insert_row(chief_table_oid);
+        trash(chief_table_oid, trashed_chief_row_oid).unwrap();
+
+        let subtype_counts: Vec<(i64, i64)> = get_subtype_counts(creature_table_oid)
+            .unwrap()
+            .into_iter()
+            .map(|subtype_count| (subtype_count.table_oid, subtype_count.row_count))
+            .collect();
+        assert_eq!(subtype_counts, vec![(goblin_table_oid, 3), (chief_table_oid, 1), (creature_table_oid, 1)]);
+    }
+
+    #[test]
+    fn purge_expired_trash_deletes_embedded_rows_and_links() {
+        let _db = testing::open_database();
+        let detail_table_oid: i64 = testing::create_table("Detail", &[]);
+        let (tag_table_oid, item_table_oid, multiselect_column_oid) = create_multiselect_tables(column::ReferenceOnDelete::Cascade);
+        let mut object_column: column::FullMetadata = testing::column_metadata(
+            item_table_oid,
+            "Detail",
+            column_type::ColumnType::Object { oid: 0, table_oid: detail_table_oid },
+        );
+        object_column.is_nullable = false;
+        object_column.create().unwrap();
+        let file_list_column_oid: i64 = testing::create_column(
+            item_table_oid,
+            "Files",
+            column_type::ColumnType::Primitive(column_type::Primitive::FileList),
+        );
+
+        let tag_row_oid: i64 = testing::insert_row(tag_table_oid);
+        let item_row_oid: i64 = testing::insert_row(item_table_oid);
+        let kept_item_row_oid: i64 = testing::insert_row(item_table_oid);
+        for row_oid in [item_row_oid, kept_item_row_oid] {
+            testing::set_cell(item_table_oid, multiselect_column_oid, row_oid, DataCellValue::Multiselect { linked_row_oid: vec![tag_row_oid] });
+        }
+        db::open().unwrap().execute_batch(&format!(
+            "INSERT INTO METADATA_FILE (OID) VALUES (1); INSERT INTO FILELIST{file_list_column_oid} (TABLE{item_table_oid}_OID, FILE_OID) VALUES ({item_row_oid}, 1);"
+        )).unwrap();
+        trash(item_table_oid, item_row_oid).unwrap();
+
+        let purged: Vec<PurgedRows> = purge_expired_trash(0.0).unwrap();
+        assert_eq!(purged.iter().map(|purged_rows| (purged_rows.table_oid, purged_rows.row_count)).collect::<Vec<(i64, i64)>>(), vec![(item_table_oid, 1)]);
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM TABLE{item_table_oid}")), 1);
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM TABLE{detail_table_oid}")), 1);
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM MULTISELECT{multiselect_column_oid}")), 1);
+        assert_eq!(testing::query_one::<i64>(&format!("SELECT COUNT(*) FROM FILELIST{file_list_column_oid}")), 0);
+    }
+
+    #[test]
+    fn changed_rows_are_those_versioned_after_the_given_version() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Monster", &[]);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let goblin_row_oid: i64 = testing::insert_row(table_oid);
+        let troll_row_oid: i64 = testing::insert_row(table_oid);
+        let orc_row_oid: i64 = testing::insert_row(table_oid);
+        let version: i64 = get_changed_rows(table_oid, 0).unwrap().version;
+        assert_eq!(version, testing::query_one::<i64>(&format!("SELECT ROW_VERSION FROM METADATA_TABLE WHERE OID = {table_oid}")));
+
+        testing::set_cell(table_oid, column_oid, troll_row_oid, DataCellValue::Text(Some(String::from("Troll"))));
+        trash(table_oid, orc_row_oid).unwrap();
+        let changed_rows: ChangedRows = get_changed_rows(table_oid, version).unwrap();
+        assert_eq!(changed_rows.row_oids, vec![troll_row_oid]);
+        assert_eq!(changed_rows.trashed_row_oids, vec![orc_row_oid]);
+        assert!(changed_rows.version > version);
+        assert!(get_changed_rows(table_oid, changed_rows.version).unwrap().row_oids.is_empty());
+        assert!(!get_changed_rows(table_oid, 0).unwrap().row_oids.contains(&orc_row_oid));
+        assert!(get_changed_rows(table_oid, 0).unwrap().row_oids.contains(&goblin_row_oid));
+    }
+}
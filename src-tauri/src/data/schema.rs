@@ -1,5 +1,5 @@
 use crate::data::view::regenerate_schema_views;
-use crate::data::{datasource, report, table};
+use crate::data::{datasource, report, table, view};
 use crate::util::channel::Sender;
 use crate::util::db;
 use crate::util::error::Error;
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 
 #[derive(Serialize, Clone)]
@@ -25,7 +27,7 @@ impl FlatListItemMetadata {
 
         // Run query for flat table data
         for list_item_result in conn.prepare(
-            "SELECT s.OID, s.NAME FROM METADATA_TABLE tbl INNER JOIN METADATA_SCHEMA s ON s.OID = tbl.OID ORDER BY s.NAME"
+            "SELECT s.OID, s.NAME FROM METADATA_TABLE tbl INNER JOIN METADATA_SCHEMA s ON s.OID = tbl.OID ORDER BY s.DISPLAY_ORDER NULLS LAST, s.NAME"
             )?
             .query_and_then([], |row| {
                 Ok::<Self, rusqlite::Error>(Self {
@@ -45,7 +47,7 @@ impl FlatListItemMetadata {
 
         // Run query for flat table data
         for list_item_result in conn.prepare(
-            "SELECT s.OID, s.NAME FROM METADATA_REPORT r INNER JOIN METADATA_SCHEMA s ON s.OID = r.OID ORDER BY s.NAME"
+            "SELECT s.OID, s.NAME FROM METADATA_REPORT r INNER JOIN METADATA_SCHEMA s ON s.OID = r.OID ORDER BY s.DISPLAY_ORDER NULLS LAST, s.NAME"
             )?
             .query_and_then([], |row| {
                 Ok::<Self, rusqlite::Error>(Self {
@@ -67,6 +69,9 @@ pub struct HierarchicalListItemMetadata {
     name: String,
     master_oid: Option<i64>,
     level: i64,
+
+    /// The folder that the top-level table of the hierarchy is grouped into.
+    group_oid: Option<i64>,
 }
 
 impl HierarchicalListItemMetadata {
@@ -76,12 +81,14 @@ impl HierarchicalListItemMetadata {
 
         // Run query for flat table data
         for list_item_result in conn.prepare("
-            WITH TABLE_HIERARCHY (OID, NAME, MASTER_OID, LEVEL) AS (
+            WITH TABLE_HIERARCHY (OID, NAME, DISPLAY_ORDER, MASTER_OID, LEVEL, GROUP_OID) AS (
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     NULL AS MASTER_OID,
-                    0 AS LEVEL
+                    0 AS LEVEL,
+                    tbl.GROUP_OID
                 FROM METADATA_TABLE tbl
                 INNER JOIN METADATA_SCHEMA s ON s.OID = tbl.OID AND NOT s.TRASH
                 WHERE tbl.OID NOT IN (SELECT INHERITOR_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_VIEW)
@@ -91,14 +98,16 @@ impl HierarchicalListItemMetadata {
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     h.OID AS MASTER_OID,
-                    h.LEVEL + 1 AS LEVEL
+                    h.LEVEL + 1 AS LEVEL,
+                    h.GROUP_OID -- Inheritors are displayed in the same folder as their master table
                 FROM TABLE_HIERARCHY h
                 INNER JOIN METADATA_SCHEMA_INHERITANCE_VIEW inh ON inh.MASTER_SCHEMA_OID = h.OID
                 INNER JOIN METADATA_SCHEMA s ON s.OID = inh.INHERITOR_SCHEMA_OID
                 WHERE EXISTS(SELECT OID FROM METADATA_TABLE WHERE OID = s.OID)
 
-                ORDER BY LEVEL DESC, NAME -- Order depth first, then by name within a depth
+                ORDER BY LEVEL DESC, DISPLAY_ORDER NULLS LAST, NAME -- Order depth first, then by user-defined order and name within a depth
             )
             SELECT * FROM TABLE_HIERARCHY
             ")?
@@ -107,7 +116,8 @@ impl HierarchicalListItemMetadata {
                     oid: row.get("OID")?,
                     name: row.get("NAME")?,
                     master_oid: row.get("MASTER_OID")?,
-                    level: row.get("LEVEL")?
+                    level: row.get("LEVEL")?,
+                    group_oid: row.get("GROUP_OID")?
                 })
             })? {
             
@@ -122,10 +132,11 @@ impl HierarchicalListItemMetadata {
 
         // Run query for flat table data
         for list_item_result in conn.prepare("
-            WITH REPORT_HIERARCHY (OID, NAME, MASTER_OID, LEVEL) AS (
+            WITH REPORT_HIERARCHY (OID, NAME, DISPLAY_ORDER, MASTER_OID, LEVEL) AS (
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     NULL AS MASTER_OID,
                     0 AS LEVEL
                 FROM METADATA_REPORT r
@@ -137,6 +148,7 @@ impl HierarchicalListItemMetadata {
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     h.OID AS MASTER_OID,
                     h.LEVEL + 1 AS LEVEL
                 FROM REPORT_HIERARCHY h
@@ -144,7 +156,7 @@ impl HierarchicalListItemMetadata {
                 INNER JOIN METADATA_SCHEMA s ON s.OID = inh.INHERITOR_SCHEMA_OID
                 WHERE EXISTS(SELECT OID FROM METADATA_REPORT WHERE OID = s.OID)
 
-                ORDER BY LEVEL DESC, NAME -- Order depth first, then by name within a depth
+                ORDER BY LEVEL DESC, DISPLAY_ORDER NULLS LAST, NAME -- Order depth first, then by user-defined order and name within a depth
             )
             SELECT * FROM REPORT_HIERARCHY
             ")?
@@ -153,7 +165,8 @@ impl HierarchicalListItemMetadata {
                     oid: row.get("OID")?,
                     name: row.get("NAME")?,
                     master_oid: row.get("MASTER_OID")?,
-                    level: row.get("LEVEL")?
+                    level: row.get("LEVEL")?,
+                    group_oid: None
                 })
             })? {
             
@@ -188,10 +201,11 @@ impl SelectedHierarchicalListItemMetadata {
         for list_item_result in conn
             .prepare(
                 "
-            WITH TABLE_HIERARCHY (OID, NAME, MASTER_OID, LEVEL) AS (
+            WITH TABLE_HIERARCHY (OID, NAME, DISPLAY_ORDER, MASTER_OID, LEVEL) AS (
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     NULL AS MASTER_OID,
                     0 AS LEVEL
                 FROM METADATA_SCHEMA s
@@ -202,6 +216,7 @@ impl SelectedHierarchicalListItemMetadata {
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     h.OID AS MASTER_OID,
                     h.LEVEL + 1 AS LEVEL
                 FROM TABLE_HIERARCHY h
@@ -209,7 +224,7 @@ impl SelectedHierarchicalListItemMetadata {
                 INNER JOIN METADATA_SCHEMA s ON s.OID = inh.INHERITOR_SCHEMA_OID
                 WHERE EXISTS(SELECT OID FROM METADATA_TABLE WHERE OID = s.OID)
 
-                ORDER BY LEVEL DESC, NAME -- Order depth first, then by name within a depth
+                ORDER BY LEVEL DESC, DISPLAY_ORDER NULLS LAST, NAME -- Order depth first, then by user-defined order and name within a depth
             )
             SELECT * FROM TABLE_HIERARCHY
             ",
@@ -272,10 +287,11 @@ impl ToggledHierarchicalListItemMetadata {
             if is_table {
                 // If the schema is a table, allow for inheritance from other tables
                 "
-                WITH SCHEMA_HIERARCHY (OID, NAME, MASTER_OID, LEVEL, DISABLED) AS (
+                WITH SCHEMA_HIERARCHY (OID, NAME, DISPLAY_ORDER, MASTER_OID, LEVEL, DISABLED) AS (
                     SELECT
                         s.OID,
                         s.NAME,
+                        s.DISPLAY_ORDER,
                         NULL AS MASTER_OID,
                         0 AS LEVEL,
                         (s.OID IS ?1) AS DISABLED
@@ -288,6 +304,7 @@ impl ToggledHierarchicalListItemMetadata {
                     SELECT
                         s.OID,
                         s.NAME,
+                        s.DISPLAY_ORDER,
                         h.OID AS MASTER_OID,
                         h.LEVEL + 1 AS LEVEL,
                         (s.OID IS ?1 OR s.OID IN (SELECT INHERITOR_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW WHERE MASTER_SCHEMA_OID = ?1)) AS DISABLED
@@ -295,17 +312,18 @@ impl ToggledHierarchicalListItemMetadata {
                     INNER JOIN METADATA_SCHEMA_INHERITANCE_VIEW inh ON inh.MASTER_SCHEMA_OID = h.OID
                     INNER JOIN METADATA_SCHEMA s ON s.OID = inh.INHERITOR_SCHEMA_OID
 
-                    ORDER BY LEVEL DESC, NAME -- Order depth first, then by name within a depth
+                    ORDER BY LEVEL DESC, DISPLAY_ORDER NULLS LAST, NAME -- Order depth first, then by user-defined order and name within a depth
                 )
                 SELECT * FROM SCHEMA_HIERARCHY
                 "
         } else {
             // If the schema is a report, then only allow inheritance from other reports
             "
-            WITH REPORT_HIERARCHY (OID, NAME, MASTER_OID, LEVEL, DISABLED) AS (
+            WITH REPORT_HIERARCHY (OID, NAME, DISPLAY_ORDER, MASTER_OID, LEVEL, DISABLED) AS (
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     NULL AS MASTER_OID,
                     0 AS LEVEL,
                     (s.OID IS ?1) AS DISABLED
@@ -318,6 +336,7 @@ impl ToggledHierarchicalListItemMetadata {
                 SELECT
                     s.OID,
                     s.NAME,
+                    s.DISPLAY_ORDER,
                     h.OID AS MASTER_OID,
                     h.LEVEL + 1 AS LEVEL,
                     (s.OID IS ?1 OR s.OID IN (SELECT INHERITOR_SCHEMA_OID FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW WHERE MASTER_SCHEMA_OID = ?1)) AS DISABLED
@@ -326,7 +345,7 @@ impl ToggledHierarchicalListItemMetadata {
                 INNER JOIN METADATA_REPORT r ON r.OID = inh.INHERITOR_SCHEMA_OID
                 INNER JOIN METADATA_SCHEMA s ON s.OID = inh.INHERITOR_SCHEMA_OID
 
-                ORDER BY LEVEL DESC, NAME -- Order depth first, then by name within a depth
+                ORDER BY LEVEL DESC, DISPLAY_ORDER NULLS LAST, NAME -- Order depth first, then by user-defined order and name within a depth
             )
             SELECT * FROM REPORT_HIERARCHY
             "
@@ -393,6 +412,12 @@ impl Schema {
 
 pub const UPDATE_SCHEMA_SIGNAL: &'static str = "schema";
 
+/// How long a schema must go without further signals to update it before its signal is emitted.
+const SCHEMA_SIGNAL_DEBOUNCE: Duration = Duration::from_millis(100);
+/// For each schema with a signal waiting to be emitted, the number of signals received for it so far.
+/// A waiting signal is only emitted if no later signal for the same schema has been received since.
+static PENDING_SCHEMA_SIGNALS: Mutex<Option<HashMap<i64, u64>>> = Mutex::new(None);
+
 /// Data structure representing the schema metadata.
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -401,6 +426,10 @@ pub struct FullMetadata {
     pub name: String,
     pub master_schema_oids: HashSet<i64>,
     pub order_by_column_oids: Vec<(i64, bool)>,
+    /// Whether empty values are sorted last for each ORDER BY column, keyed by column OID.
+    /// Columns that are not present sort empty values last when ascending and first when descending.
+    #[serde(default)]
+    pub order_by_nulls_last: HashMap<i64, bool>,
 }
 
 impl Hash for FullMetadata {
@@ -438,26 +467,32 @@ impl FullMetadata {
 
         // Query for ORDER BY columns
         let mut order_by_column_oids: Vec<(i64, bool)> = Vec::new();
+        let mut order_by_nulls_last: HashMap<i64, bool> = HashMap::new();
         {
             let mut order_by_column_oids_statement = conn.prepare(
                 "
                 SELECT 
                     COLUMN_OID,
-                    SORT_ASCENDING
+                    SORT_ASCENDING,
+                    NULLS_LAST
                 FROM METADATA_SCHEMA_ORDERBY_VIEW
                 WHERE SCHEMA_OID = ?1
+                ORDER BY ORDERING
                 ",
             )?;
             let order_by_column_oids_rows =
                 order_by_column_oids_statement.query_and_then(params![oid], |row| {
-                    Ok::<(i64, bool), rusqlite::Error>((
+                    Ok::<(i64, bool, bool), rusqlite::Error>((
                         row.get("COLUMN_OID")?,
                         row.get("SORT_ASCENDING")?,
+                        row.get("NULLS_LAST")?,
                     ))
                 })?;
             for order_by_column_oids_result in order_by_column_oids_rows {
-                let (order_by_column_oid, order_by_column_ascending) = order_by_column_oids_result?;
+                let (order_by_column_oid, order_by_column_ascending, order_by_column_nulls_last) =
+                    order_by_column_oids_result?;
                 order_by_column_oids.push((order_by_column_oid, order_by_column_ascending));
+                order_by_nulls_last.insert(order_by_column_oid, order_by_column_nulls_last);
             }
         }
 
@@ -471,23 +506,115 @@ impl FullMetadata {
                     name: row.get("NAME")?,
                     master_schema_oids: master_schemas,
                     order_by_column_oids,
+                    order_by_nulls_last,
                 })
             },
         )?)
     }
 
-    /// Flags the schema for garbage collection.
-    pub fn trash(oid: i64) -> Result<(), Error> {
+    /// Gets the user-defined display order of the schema among its siblings.
+    pub fn get_display_order(oid: i64) -> Result<Option<i64>, Error> {
+        let conn: Connection = db::open()?;
+        Ok(conn.query_one(
+            "SELECT DISPLAY_ORDER FROM METADATA_SCHEMA WHERE OID = ?1",
+            params![oid],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Sets the user-defined display order of the schema among its siblings.
+    /// If None, the schema is ordered by name after all siblings with a defined ordering.
+    pub fn set_display_order(oid: i64, display_order: Option<i64>) -> Result<(), Error> {
         let mut conn: Connection = db::open()?;
         let trans: Transaction = conn.transaction()?;
         trans.execute(
-            "UPDATE METADATA_SCHEMA SET TRASH = TRUE WHERE OID = ?1",
-            params![oid],
+            "UPDATE METADATA_SCHEMA SET DISPLAY_ORDER = ?2 WHERE OID = ?1",
+            params![oid, display_order],
         )?;
         trans.commit()?;
         Ok(())
     }
 
+    /// Moves the schema to a position in the display order, shifting the schemas at or after that position down by one.
+    /// The schemas after the previous position of the schema are shifted up by one, so that moving the schema back undoes the move.
+    /// Returns the previous position of the schema.
+    pub fn reorder(oid: i64, new_position: i64) -> Result<Option<i64>, Error> {
+        let mut conn: Connection = db::open()?;
+        let trans: Transaction = conn.transaction()?;
+        let old_position: Option<i64> = trans.query_one(
+            "SELECT DISPLAY_ORDER FROM METADATA_SCHEMA WHERE OID = ?1",
+            params![oid],
+            |row| row.get(0),
+        )?;
+        if let Some(old_position) = old_position {
+            trans.execute(
+                "UPDATE METADATA_SCHEMA SET DISPLAY_ORDER = DISPLAY_ORDER - 1 WHERE DISPLAY_ORDER > ?1 AND OID <> ?2",
+                params![old_position, oid],
+            )?;
+        }
+        trans.execute(
+            "UPDATE METADATA_SCHEMA SET DISPLAY_ORDER = -DISPLAY_ORDER WHERE DISPLAY_ORDER >= ?1",
+            params![new_position],
+        )?;
+        trans.execute(
+            "UPDATE METADATA_SCHEMA SET DISPLAY_ORDER = ?2 WHERE OID = ?1",
+            params![oid, new_position],
+        )?;
+        trans.execute(
+            "UPDATE METADATA_SCHEMA SET DISPLAY_ORDER = 1 - DISPLAY_ORDER WHERE DISPLAY_ORDER < 0",
+            [],
+        )?;
+        trans.commit()?;
+        Ok(old_position)
+    }
+
+    /// Flags the schema for garbage collection.
+    /// If other tables inherit from the schema, this fails unless forced, in which case those tables are flagged as well.
+    /// Returns the OIDs of every schema that was flagged.
+    pub fn trash(oid: i64, force: bool) -> Result<Vec<i64>, Error> {
+        let mut conn: Connection = db::open()?;
+        let trans: Transaction = conn.transaction()?;
+
+        // Find every table that inherits from the schema, directly or indirectly
+        let mut inheritors: Vec<(i64, String)> = Vec::new();
+        {
+            let mut stmt = trans.prepare(
+                "SELECT p.INHERITOR_SCHEMA_OID, s.NAME 
+                FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW p 
+                INNER JOIN METADATA_SCHEMA s ON s.OID = p.INHERITOR_SCHEMA_OID 
+                WHERE p.MASTER_SCHEMA_OID = ?1 
+                ORDER BY s.NAME",
+            )?;
+            let rows = stmt.query_map(params![oid], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            for row in rows {
+                inheritors.push(row?);
+            }
+        }
+        if !inheritors.is_empty() && !force {
+            let schema_name: String = trans.query_one(
+                "SELECT NAME FROM METADATA_SCHEMA WHERE OID = ?1",
+                params![oid],
+                |row| row.get(0),
+            )?;
+            return Err(Error::InheritedSchemaTrash {
+                schema_name,
+                inheritor_names: inheritors.into_iter().map(|(_, name)| name).collect(),
+            });
+        }
+
+        // Flag the schema and its inheritors
+        let mut trashed_oids: Vec<i64> = vec![oid];
+        trashed_oids.extend(inheritors.into_iter().map(|(inheritor_oid, _)| inheritor_oid));
+        for trashed_oid in trashed_oids.iter() {
+            trans.execute(
+                "UPDATE METADATA_SCHEMA SET TRASH = TRUE WHERE OID = ?1",
+                params![trashed_oid],
+            )?;
+        }
+        trans.commit()?;
+        Ok(trashed_oids)
+    }
+
     /// Unflags the schema for garbage collection.
     pub fn untrash(oid: i64) -> Result<(), Error> {
         let mut conn: Connection = db::open()?;
@@ -527,6 +654,27 @@ impl FullMetadata {
         Ok(())
     }
 
+    /// Checks that every master schema is an existing table that has not been trashed, so that a table is not left half-created or half-altered when it inherits from them.
+    pub fn validate_master_tables_transact(&self, trans: &Transaction) -> Result<(), Error> {
+        let mut invalid_schema_oids: Vec<i64> = Vec::new();
+        for master_schema_oid in self.master_schema_oids.iter() {
+            let is_valid: bool = *master_schema_oid != self.oid
+                && trans.query_one(
+                    "SELECT EXISTS (SELECT 1 FROM METADATA_TABLE t INNER JOIN METADATA_SCHEMA s ON s.OID = t.OID WHERE t.OID = ?1 AND NOT s.TRASH)",
+                    params![master_schema_oid],
+                    |row| row.get::<_, bool>(0),
+                )?
+                && trans.table_exists(Some("main"), &format!("TABLE{master_schema_oid}"))?;
+            if !is_valid && !invalid_schema_oids.contains(master_schema_oid) {
+                invalid_schema_oids.push(*master_schema_oid);
+            }
+        }
+        if invalid_schema_oids.len() > 0 {
+            return Err(Error::InvalidMasterTables { schema_oids: invalid_schema_oids });
+        }
+        Ok(())
+    }
+
     /// Sets the inheritance pattern for the schema.
     fn set_transact(&self, trans: &Transaction) -> Result<(), Error> {
         // Clear all metadata describing inheritance
@@ -587,19 +735,21 @@ impl FullMetadata {
             trans.execute(
                 "
                 INSERT INTO METADATA_SCHEMA_ORDERBY 
-                    (SCHEMA_OID, COLUMN_OID, ORDERING, SORT_ASCENDING)
+                    (SCHEMA_OID, COLUMN_OID, ORDERING, SORT_ASCENDING, NULLS_LAST)
                     VALUES
-                    (?1, ?2, ?3, ?4)
+                    (?1, ?2, ?3, ?4, ?5)
                 ON CONFLICT DO UPDATE SET 
                     TRASH = FALSE,
                     ORDERING = excluded.ORDERING,
-                    SORT_ASCENDING = excluded.SORT_ASCENDING
+                    SORT_ASCENDING = excluded.SORT_ASCENDING,
+                    NULLS_LAST = excluded.NULLS_LAST
                 ",
                 params![
                     self.oid,
                     order_by_column_oid,
                     order_by_column_ordering,
-                    order_by_column_ascending
+                    order_by_column_ascending,
+                    self.order_by_nulls_last.get(order_by_column_oid)
                 ],
             )?;
         }
@@ -609,6 +759,11 @@ impl FullMetadata {
 
     /// Emit signal to update schema related to the indicated schemas.
     pub fn emit_affected_schema(app: &AppHandle, schema_oids: Vec<i64>) -> Result<(), Error> {
+        // Tables in bulk mode are signalled once bulk mode ends
+        let schema_oids: Vec<i64> = view::defer_bulk_mode_signals(schema_oids);
+        if schema_oids.is_empty() {
+            return Ok(());
+        }
         let conn = db::open()?;
 
         let mut affected_schema: Vec<i64> = Vec::new();
@@ -676,10 +831,59 @@ impl FullMetadata {
             let affected_schema_oid: i64 = affected_schema_results?;
             affected_schema.push(affected_schema_oid);
         }
-        app.emit(UPDATE_SCHEMA_SIGNAL, affected_schema)?;
+        Self::emit_debounced(app, affected_schema);
         Ok(())
     }
 
+    /// Delays the signal to update each schema until no further signal for that schema has been received for the debounce window.
+    /// Signals for the same schema in quick succession are coalesced into a single signal, sent after the last of them.
+    fn emit_debounced(app: &AppHandle, affected_schema: Vec<i64>) {
+        // Restart the debounce window of each schema, remembering which signal this is
+        let signals: Vec<(i64, u64)> = {
+            let mut pending_signals = PENDING_SCHEMA_SIGNALS.lock().unwrap();
+            let pending_signals: &mut HashMap<i64, u64> = pending_signals.get_or_insert_with(HashMap::new);
+            affected_schema
+                .into_iter()
+                .collect::<HashSet<i64>>()
+                .into_iter()
+                .map(|schema_oid| {
+                    let signal_count: &mut u64 = pending_signals.entry(schema_oid).or_insert(0);
+                    *signal_count += 1;
+                    (schema_oid, *signal_count)
+                })
+                .collect()
+        };
+        if signals.is_empty() {
+            return;
+        }
+
+        // Wait out the debounce window, then emit the schemas that received no later signal during it
+        let app: AppHandle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            std::thread::sleep(SCHEMA_SIGNAL_DEBOUNCE);
+            let affected_schema: Vec<i64> = {
+                let mut pending_signals = PENDING_SCHEMA_SIGNALS.lock().unwrap();
+                let pending_signals: &mut HashMap<i64, u64> = pending_signals.get_or_insert_with(HashMap::new);
+                signals
+                    .into_iter()
+                    .filter_map(|(schema_oid, signal_count)| {
+                        if pending_signals.get(&schema_oid) != Some(&signal_count) {
+                            return None;
+                        }
+                        pending_signals.remove(&schema_oid);
+                        Some(schema_oid)
+                    })
+                    .collect()
+            };
+            if affected_schema.is_empty() {
+                return;
+            }
+            if let Err(e) = app.emit(UPDATE_SCHEMA_SIGNAL, &affected_schema) {
+                eprintln!("Unable to emit the signal to update schema {affected_schema:?}: {e}");
+            }
+        });
+    }
+
     /// Emits a signal to update every schema.
     pub fn emit_all_schema(app: &AppHandle) -> Result<(), Error> {
         let conn = db::open()?;
@@ -700,3 +904,65 @@ impl FullMetadata {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::testing;
+
+    #[test]
+    fn moving_a_schema_back_restores_the_display_order() {
+        let _db = testing::open_database();
+        let table_oids: Vec<i64> = ["A", "B", "C", "D"].iter().map(|name| testing::create_table(name, &[])).collect();
+        for (idx, table_oid) in table_oids.iter().enumerate() {
+            FullMetadata::set_display_order(*table_oid, Some(idx as i64 + 1)).unwrap();
+        }
+        let get_display_orders = || -> Vec<Option<i64>> {
+            table_oids.iter().map(|table_oid| FullMetadata::get_display_order(*table_oid).unwrap()).collect()
+        };
+
+        let old_position: Option<i64> = FullMetadata::reorder(table_oids[0], 3).unwrap();
+        assert_eq!(old_position, Some(1));
+        assert_eq!(get_display_orders(), vec![Some(3), Some(1), Some(2), Some(4)]);
+
+        FullMetadata::reorder(table_oids[0], old_position.unwrap()).unwrap();
+        assert_eq!(get_display_orders(), vec![Some(1), Some(2), Some(3), Some(4)]);
+    }
+
+    /// Checks whether a schema is in the trash.
+    fn is_trashed(oid: i64) -> bool {
+        testing::query_one(&format!("SELECT TRASH FROM METADATA_SCHEMA WHERE OID = {oid}"))
+    }
+
+    #[test]
+    fn trashing_a_master_table_is_blocked_by_its_inheritors() {
+        let _db = testing::open_database();
+        let creature_table_oid: i64 = testing::create_table("Creature", &[]);
+        testing::create_table("Goblin", &[creature_table_oid]);
+        testing::create_table("Troll", &[creature_table_oid]);
+
+        match FullMetadata::trash(creature_table_oid, false) {
+            Err(Error::InheritedSchemaTrash { schema_name, inheritor_names }) => {
+                assert_eq!(schema_name, "Creature");
+                assert_eq!(inheritor_names, vec![String::from("Goblin"), String::from("Troll")]);
+            },
+            result => panic!("Expected the inheritors to block the trash, got {result:?}"),
+        }
+        assert!(!is_trashed(creature_table_oid));
+    }
+
+    #[test]
+    fn trashing_a_master_table_with_force_trashes_its_inheritors() {
+        let _db = testing::open_database();
+        let creature_table_oid: i64 = testing::create_table("Creature", &[]);
+        let goblin_table_oid: i64 = testing::create_table("Goblin", &[creature_table_oid]);
+        let hobgoblin_table_oid: i64 = testing::create_table("Hobgoblin", &[goblin_table_oid]);
+        let unrelated_table_oid: i64 = testing::create_table("Item", &[]);
+
+        let mut trashed_oids: Vec<i64> = FullMetadata::trash(creature_table_oid, true).unwrap();
+        trashed_oids.sort();
+        assert_eq!(trashed_oids, vec![creature_table_oid, goblin_table_oid, hobgoblin_table_oid]);
+        assert!(is_trashed(creature_table_oid) && is_trashed(goblin_table_oid) && is_trashed(hobgoblin_table_oid));
+        assert!(!is_trashed(unrelated_table_oid));
+    }
+}
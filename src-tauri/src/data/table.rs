@@ -6,21 +6,275 @@ use crate::data::view::regenerate_schema_views;
 use crate::util::channel::Sender;
 use crate::util::db;
 use crate::util::error::Error;
+use regex::Regex;
 use rocket::serde::{Serialize as RocketSerialize};
-use rusqlite::{params, OptionalExtension, Transaction};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use tauri::Emitter;
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
-use std::sync::Mutex;
+use std::sync::{LazyLock, Mutex};
 
 /// Data structure representing the table metadata
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FullMetadata {
     pub schema: schema::FullMetadata,
+
+    /// If true, the creation and modification timestamps of each row are recorded.
+    #[serde(default)]
+    pub track_timestamps: bool,
+
+    /// The number of rows displayed per page when the table is opened, if it differs from the application default.
+    #[serde(default)]
+    pub default_page_size: Option<i64>,
+}
+
+/// The largest number of rows that can be displayed on a single page of a table.
+pub const MAX_PAGE_SIZE: i64 = 100000;
+
+/// The space used by a single column of a table.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnStorageStats {
+    pub column_oid: i64,
+    pub column_name: String,
+    pub non_null_count: i64,
+
+    /// The bytes used by the values of the column, including the content of stored files.
+    pub bytes: i64,
+}
+
+/// The space used by a table and each of its columns.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStats {
+    pub row_count: i64,
+
+    /// The bytes used by the table, including stored files and Multiselect selections.
+    pub total_bytes: i64,
+
+    /// True if the database could not report page sizes, so the total is summed from the lengths of values.
+    pub is_approximate: bool,
+    pub columns: Vec<ColumnStorageStats>,
+}
+
+/// A column of a table in which every row has the same value, or no value at all.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DegenerateColumn {
+    pub column_oid: i64,
+    pub column_name: String,
+
+    /// True if no row has a value in the column.
+    pub is_all_null: bool,
+
+    /// The label of the value shared by every row, if there is one.
+    pub shared_label: Option<String>,
+}
+
+/// The offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+/// The prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Mixes bytes into a 64-bit FNV-1a hash.
+/// FNV-1a is used because its output never changes between builds, unlike the hasher of the standard library.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Matches the root datasource of a path in a formula (e.g. "ROOT12").
+static ROOT_DATASOURCE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"ROOT\d+").unwrap());
+
+/// Matches a column in the path of a datasource in a formula (e.g. "_COLUMN34"), capturing the column OID.
+static COLUMN_DATASOURCE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"_COLUMN(\d+)").unwrap());
+
+/// The default for the maximum number of Object columns that can be nested inside one another, starting from any table.
+pub const DEFAULT_MAX_OBJECT_NESTING_DEPTH: i64 = 16;
+
+/// The maximum number of Object columns that can be nested inside one another, starting from any table.
+static MAX_OBJECT_NESTING_DEPTH: Mutex<i64> = Mutex::new(DEFAULT_MAX_OBJECT_NESTING_DEPTH);
+
+/// Sets the maximum number of Object columns that can be nested inside one another.
+/// Existing columns are not checked against the new maximum until another Object column is created or edited.
+pub fn set_max_object_nesting_depth(max_depth: i64) -> Result<(), Error> {
+    if max_depth < 1 {
+        return Err(Error::AdhocError("The maximum nesting depth of Object columns must be at least 1."));
+    }
+    *MAX_OBJECT_NESTING_DEPTH.lock().unwrap() = max_depth;
+    Ok(())
+}
+
+/// How deeply Object columns are nested inside a table.
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectNesting {
+    /// The length of the longest chain of Object columns starting from the table, not counting any loop back.
+    pub depth: i64,
+
+    /// True if a chain of Object columns starting from the table loops back to a table earlier in the chain.
+    pub is_cyclic: bool,
+}
+
+/// Loads the tables embedded by Object columns, keyed by the table that each Object column belongs to.
+/// If required_only is true, only Object columns that cannot be left blank are loaded.
+fn load_object_edges(conn: &Connection, required_only: bool) -> Result<HashMap<i64, Vec<i64>>, Error> {
+    let mut edges: HashMap<i64, Vec<i64>> = HashMap::new();
+    for row_result in conn
+        .prepare(
+            "
+            SELECT c.SCHEMA_OID, o.TABLE_OID
+            FROM METADATA_COLUMN_VIEW c
+            INNER JOIN METADATA_COLUMN_TYPE__OBJECT o ON o.OID = c.TYPE_OID
+            WHERE NOT (?1 AND c.IS_NULLABLE)
+            ",
+        )?
+        .query_map(params![required_only], |row| Ok((row.get::<_, i64>("SCHEMA_OID")?, row.get::<_, i64>("TABLE_OID")?)))?
+    {
+        let (schema_oid, object_table_oid) = row_result?;
+        edges.entry(schema_oid).or_insert_with(Vec::new).push(object_table_oid);
+    }
+    Ok(edges)
+}
+
+/// Measures the nesting of Object columns below a table, given the tables that have already been visited on the current chain.
+fn measure_object_nesting(edges: &HashMap<i64, Vec<i64>>, oid: i64, chain: &mut HashSet<i64>) -> ObjectNesting {
+    let mut nesting: ObjectNesting = ObjectNesting::default();
+    let Some(object_table_oids) = edges.get(&oid) else {
+        return nesting;
+    };
+
+    chain.insert(oid);
+    for object_table_oid in object_table_oids.iter() {
+        if chain.contains(object_table_oid) {
+            nesting.is_cyclic = true;
+            continue;
+        }
+        let object_nesting: ObjectNesting = measure_object_nesting(edges, *object_table_oid, chain);
+        nesting.depth = nesting.depth.max(object_nesting.depth + 1);
+        nesting.is_cyclic |= object_nesting.is_cyclic;
+    }
+    chain.remove(&oid);
+    nesting
+}
+
+/// A folder that tables are grouped into in the sidebar.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Group {
+    pub oid: i64,
+    pub name: String,
+    pub ordering: i64,
+}
+
+impl Group {
+    /// Queries for all groups, in the order they are displayed.
+    pub fn query_all(mut sender: Sender<Self>) -> Result<(), Error> {
+        let conn = db::open()?;
+        for row_result in conn
+            .prepare("SELECT OID, NAME, ORDERING FROM METADATA_TABLE_GROUP ORDER BY ORDERING, NAME")?
+            .query_map([], |row| {
+                Ok(Self {
+                    oid: row.get("OID")?,
+                    name: row.get("NAME")?,
+                    ordering: row.get("ORDERING")?,
+                })
+            })?
+        {
+            sender.send(row_result?)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a new group after all existing groups, returning its OID.
+    pub fn create(name: String) -> Result<i64, Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        trans.execute(
+            "INSERT INTO METADATA_TABLE_GROUP (NAME, ORDERING) VALUES (?1, (SELECT COALESCE(MAX(ORDERING) + 1, 0) FROM METADATA_TABLE_GROUP))",
+            params![name],
+        )?;
+        let oid: i64 = trans.last_insert_rowid();
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(oid)
+    }
+
+    /// Renames a group.
+    pub fn rename(oid: i64, name: String) -> Result<(), Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        trans.execute(
+            "UPDATE METADATA_TABLE_GROUP SET NAME = ?2 WHERE OID = ?1",
+            params![oid, name],
+        )?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(())
+    }
+
+    /// Deletes a group. The tables in the group are not deleted, but become ungrouped.
+    /// Returns the OIDs of the tables that were in the group.
+    pub fn delete(oid: i64) -> Result<Vec<i64>, Error> {
+        let table_oids: Vec<i64> = Self::get_table_oids(oid)?;
+
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        trans.execute(
+            "UPDATE METADATA_TABLE SET GROUP_OID = NULL WHERE GROUP_OID = ?1",
+            params![oid],
+        )?;
+        trans.execute("DELETE FROM METADATA_TABLE_GROUP WHERE OID = ?1", params![oid])?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(table_oids)
+    }
+
+    /// Gets the OIDs of the tables in a group.
+    pub fn get_table_oids(oid: i64) -> Result<Vec<i64>, Error> {
+        let conn = db::open()?;
+        let mut table_oids: Vec<i64> = Vec::new();
+        for row_result in conn
+            .prepare("SELECT OID FROM METADATA_TABLE WHERE GROUP_OID = ?1")?
+            .query_map(params![oid], |row| row.get::<_, i64>("OID"))?
+        {
+            table_oids.push(row_result?);
+        }
+        Ok(table_oids)
+    }
+
+    /// Moves a table into a group, or out of any group if None.
+    pub fn assign_table(table_oid: i64, group_oid: Option<i64>) -> Result<(), Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        trans.execute(
+            "UPDATE METADATA_TABLE SET GROUP_OID = ?2 WHERE OID = ?1",
+            params![table_oid, group_oid],
+        )?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(())
+    }
+}
+
+/// The metadata of a table, along with the number of columns displayed in it.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub metadata: FullMetadata,
+    pub column_count: i64,
 }
 
 impl Hash for FullMetadata {
@@ -39,21 +293,95 @@ impl FullMetadata {
     /// Gets the metadata for a table.
     pub fn get(oid: i64) -> Result<Self, Error> {
         let conn = db::open()?;
+        Self::get_transact(&conn, oid)
+    }
+
+    /// Gets the metadata for a table.
+    pub fn get_transact(conn: &Connection, oid: i64) -> Result<Self, Error> {
+        // Get the table metadata, failing clearly if the table does not exist
+        let Some((track_timestamps, default_page_size)) = conn
+            .query_one(
+                "SELECT TRACK_TIMESTAMPS, DEFAULT_PAGE_SIZE FROM METADATA_TABLE WHERE OID = ?1",
+                params![oid],
+                |row| Ok((row.get::<_, bool>("TRACK_TIMESTAMPS")?, row.get::<_, Option<i64>>("DEFAULT_PAGE_SIZE")?)),
+            )
+            .optional()?
+        else {
+            return Err(Error::TableNotFound { table_oid: oid });
+        };
 
         // Get the schema metadata
-        let schema_metadata = schema::FullMetadata::get(&conn, oid)?;
+        let schema_metadata = schema::FullMetadata::get(conn, oid)?;
 
         // Return the metadata
         Ok(Self {
             schema: schema_metadata,
+            track_timestamps,
+            default_page_size,
         })
     }
 
+    /// Gets the metadata for every table, along with the number of columns displayed in each.
+    pub fn get_all() -> Result<Vec<Summary>, Error> {
+        let conn = db::open()?;
+
+        // Get the table metadata and column count of each table in one pass
+        let mut table_statement = conn.prepare(
+            "
+            SELECT
+                tbl.OID,
+                tbl.TRACK_TIMESTAMPS,
+                tbl.DEFAULT_PAGE_SIZE,
+                (SELECT COUNT(*) FROM METADATA_SCHEMA_COLUMN_VIEW sc WHERE sc.SCHEMA_OID = tbl.OID) AS COLUMN_COUNT
+            FROM METADATA_TABLE tbl
+            INNER JOIN METADATA_SCHEMA s ON s.OID = tbl.OID
+            WHERE NOT s.TRASH
+            ORDER BY s.NAME
+            ",
+        )?;
+        let mut summaries: Vec<Summary> = Vec::new();
+        for row_result in table_statement.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>("OID")?,
+                row.get::<_, bool>("TRACK_TIMESTAMPS")?,
+                row.get::<_, Option<i64>>("DEFAULT_PAGE_SIZE")?,
+                row.get::<_, i64>("COLUMN_COUNT")?,
+            ))
+        })? {
+            let (oid, track_timestamps, default_page_size, column_count) = row_result?;
+
+            // Get the schema metadata
+            let schema_metadata = schema::FullMetadata::get(&conn, oid)?;
+            summaries.push(Summary {
+                metadata: Self {
+                    schema: schema_metadata,
+                    track_timestamps,
+                    default_page_size,
+                },
+                column_count,
+            });
+        }
+        Ok(summaries)
+    }
+
     /// Creates a new table.
     pub fn create(&mut self) -> Result<(), Error> {
         let mut conn = db::open()?;
         let trans = conn.transaction()?;
 
+        // Create the table
+        self.create_transact(&trans)?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(())
+    }
+
+    /// Creates a new table within a transaction.
+    pub fn create_transact(&mut self, trans: &Transaction) -> Result<(), Error> {
+        // Check the master tables before anything is created
+        self.schema.validate_master_tables_transact(trans)?;
+
         // Create schema
         self.schema.create(&trans)?;
 
@@ -62,7 +390,9 @@ impl FullMetadata {
             "
             CREATE TABLE TABLE{} (
                 OID INTEGER PRIMARY KEY, 
-                TRASH INTEGER NOT NULL DEFAULT 0
+                TRASH INTEGER NOT NULL DEFAULT 0,
+                VERSION INTEGER NOT NULL DEFAULT 0,
+                TRASHED_AT REAL
             ) STRICT;
             ",
             self.schema.oid
@@ -74,9 +404,12 @@ impl FullMetadata {
 
         // Create the table metadata
         trans.execute(
-            "INSERT INTO METADATA_TABLE (OID) VALUES (?1)",
-            params![self.schema.oid],
+            "INSERT INTO METADATA_TABLE (OID, TRACK_TIMESTAMPS, DEFAULT_PAGE_SIZE) VALUES (?1, ?2, ?3)",
+            params![self.schema.oid, self.track_timestamps, self.default_page_size],
         )?;
+        if self.track_timestamps {
+            Self::add_timestamp_columns(&trans, self.schema.oid)?;
+        }
         // Create a datasource for the table
         trans.execute(
             "INSERT INTO METADATA_DATASOURCE (TABLE_OID) VALUES (?1)",
@@ -85,9 +418,6 @@ impl FullMetadata {
 
         // Regenerate views related to the schema
         regenerate_schema_views(&trans, self.schema.oid)?;
-
-        // Commit the transaction
-        trans.commit()?;
         Ok(())
     }
 
@@ -97,8 +427,18 @@ impl FullMetadata {
         let trans = conn.transaction()?;
 
         // Overwrite the schema metadata
+        self.schema.validate_master_tables_transact(&trans)?;
         self.schema.set(&trans)?;
 
+        // Overwrite the table metadata
+        trans.execute(
+            "UPDATE METADATA_TABLE SET TRACK_TIMESTAMPS = ?1 WHERE OID = ?2",
+            params![self.track_timestamps, self.schema.oid],
+        )?;
+        if self.track_timestamps {
+            Self::add_timestamp_columns(&trans, self.schema.oid)?;
+        }
+
         // Regenerate views related to the schema
         regenerate_schema_views(&trans, self.schema.oid)?;
 
@@ -106,6 +446,503 @@ impl FullMetadata {
         trans.commit()?;
         Ok(())
     }
+
+    /// Sets the number of rows displayed per page when the table is opened.
+    /// If no page size is given, the application default is used.
+    pub fn set_page_size(table_oid: i64, page_size: Option<i64>) -> Result<(), Error> {
+        if let Some(page_size) = page_size {
+            if page_size < 1 || page_size > MAX_PAGE_SIZE {
+                return Err(Error::AdhocError("The page size must be between 1 and 100000."));
+            }
+        }
+
+        let conn = db::open()?;
+        let updated_count: usize = conn.execute(
+            "UPDATE METADATA_TABLE SET DEFAULT_PAGE_SIZE = ?1 WHERE OID = ?2",
+            params![page_size, table_oid],
+        )?;
+        if updated_count == 0 {
+            return Err(Error::TableNotFound { table_oid });
+        }
+        Ok(())
+    }
+
+    /// Creates a new table with the same inheritance, columns, and sort order as an existing table, but no rows.
+    /// Returns the OID of the new table.
+    pub fn clone_structure(source_table_oid: i64, new_name: String) -> Result<i64, Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        let (new_table_oid, _) = Self::clone_structure_transact(&trans, source_table_oid, new_name)?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(new_table_oid)
+    }
+
+    /// Creates a new table with the same inheritance, columns, and sort order as an existing table, but no rows.
+    /// Returns the OID of the new table, along with the OID of each copied column keyed by the OID of the column it was copied from.
+    pub fn clone_structure_transact(trans: &Transaction, source_table_oid: i64, new_name: String) -> Result<(i64, HashMap<i64, i64>), Error> {
+        Self::clone_structure_with_objects_transact(trans, source_table_oid, new_name, &mut HashMap::new())
+    }
+
+    /// Creates a new table with the same inheritance, columns, and sort order as an existing table, but no rows.
+    /// The table embedded by each Object column is cloned as well, so that the new table does not share embedded rows with the existing table.
+    /// Tables that have already been cloned are mapped to their clones in cloned_table_oids, so that a chain of Object columns looping back on itself is only cloned once.
+    fn clone_structure_with_objects_transact(trans: &Transaction, source_table_oid: i64, new_name: String, cloned_table_oids: &mut HashMap<i64, i64>) -> Result<(i64, HashMap<i64, i64>), Error> {
+        let source: Self = Self::get_transact(trans, source_table_oid)?;
+
+        // Step 1. Create the new table with the same inheritance
+        let mut metadata: Self = Self {
+            schema: schema::FullMetadata {
+                oid: 0,
+                name: new_name,
+                master_schema_oids: source.schema.master_schema_oids.clone(),
+                order_by_column_oids: Vec::new(),
+                order_by_nulls_last: HashMap::new(),
+            },
+            track_timestamps: source.track_timestamps,
+            default_page_size: source.default_page_size,
+        };
+        metadata.create_transact(trans)?;
+        cloned_table_oids.insert(source_table_oid, metadata.schema.oid);
+
+        // Step 2. Copy each column defined on the source table, except formula columns
+        let mut source_columns: Vec<column::FullMetadata> = Vec::new();
+        for column_oid_result in trans
+            .prepare("SELECT OID FROM METADATA_COLUMN WHERE SCHEMA_OID = ?1 AND NOT TRASH ORDER BY ORDERING")?
+            .query_map(params![source_table_oid], |row| row.get::<_, i64>("OID"))?
+        {
+            source_columns.push(column::FullMetadata::get_transact(trans, column_oid_result?)?);
+        }
+        let mut column_oid_map: HashMap<i64, i64> = HashMap::new();
+        for source_column in source_columns.iter() {
+            if let column_type::ColumnType::Formula { .. } = source_column.column_type {
+                continue;
+            }
+            let mut new_column: column::FullMetadata = source_column.clone();
+            new_column.oid = 0;
+            new_column.schema = metadata.schema.clone();
+            new_column.ordering = -1;
+
+            // Embed a clone of the table embedded by an Object column
+            if let column_type::ColumnType::Object { table_oid: object_table_oid, .. } = source_column.column_type {
+                let cloned_object_table_oid: i64 = match cloned_table_oids.get(&object_table_oid) {
+                    Some(cloned_object_table_oid) => *cloned_object_table_oid,
+                    None => {
+                        let object_table_name: String = Self::get_transact(trans, object_table_oid)?.schema.name;
+                        Self::clone_structure_with_objects_transact(trans, object_table_oid, object_table_name, cloned_table_oids)?.0
+                    }
+                };
+                new_column.column_type = column_type::ColumnType::Object { oid: 0, table_oid: cloned_object_table_oid };
+            }
+            new_column.create_transact(trans)?;
+            column_oid_map.insert(source_column.oid, new_column.oid);
+        }
+
+        // Step 3. Copy each formula column in place, with its references pointed at the new table
+        let source_datasource_alias: Option<String> =
+            Datasource::get_default_datasource_transact(trans, source_table_oid)?.map(|d| d.get_alias());
+        let new_datasource_alias: Option<String> =
+            Datasource::get_default_datasource_transact(trans, metadata.schema.oid)?.map(|d| d.get_alias());
+        let mut previous_column_oid: Option<i64> = None;
+        for source_column in source_columns.iter() {
+            let column_type::ColumnType::Formula { formula, .. } = &source_column.column_type else {
+                previous_column_oid = column_oid_map.get(&source_column.oid).copied();
+                continue;
+            };
+
+            // Point references to the source table's datasource and columns at the new table
+            let formula: String = ROOT_DATASOURCE_REGEX
+                .replace_all(formula, |caps: &regex::Captures| {
+                    match (&source_datasource_alias, &new_datasource_alias) {
+                        (Some(source_alias), Some(new_alias)) if &caps[0] == source_alias => new_alias.clone(),
+                        _ => caps[0].to_string(),
+                    }
+                })
+                .to_string();
+            let formula: String = COLUMN_DATASOURCE_REGEX
+                .replace_all(&formula, |caps: &regex::Captures| {
+                    match caps[1].parse::<i64>().ok().and_then(|oid| column_oid_map.get(&oid)) {
+                        Some(new_oid) => format!("_COLUMN{new_oid}"),
+                        None => caps[0].to_string(),
+                    }
+                })
+                .to_string();
+
+            // Place the column directly after the previously copied column, or before the first copied column
+            let next_to_column_oid: Option<(i64, i64)> = match previous_column_oid {
+                Some(oid) => Some((oid, 1)),
+                None => source_columns
+                    .iter()
+                    .find_map(|c| column_oid_map.get(&c.oid))
+                    .map(|oid| (*oid, 0)),
+            };
+            let mut new_column: column::FullMetadata = source_column.clone();
+            new_column.oid = 0;
+            new_column.schema = metadata.schema.clone();
+            new_column.column_type = column_type::ColumnType::Formula { oid: 0, formula };
+            new_column.ordering = match next_to_column_oid {
+                Some((oid, offset)) => {
+                    trans.query_one(
+                        "SELECT ORDERING FROM METADATA_COLUMN WHERE OID = ?1",
+                        params![oid],
+                        |row| row.get::<_, i64>("ORDERING"),
+                    )? + offset
+                }
+                None => -1,
+            };
+            new_column.create_transact(trans)?;
+            column_oid_map.insert(source_column.oid, new_column.oid);
+            previous_column_oid = Some(new_column.oid);
+        }
+
+        // Step 4. Copy the sort order, pointed at the copied columns
+        metadata.schema.order_by_column_oids = source
+            .schema
+            .order_by_column_oids
+            .iter()
+            .map(|(oid, ascending)| (*column_oid_map.get(oid).unwrap_or(oid), *ascending))
+            .collect();
+        metadata.schema.order_by_nulls_last = source
+            .schema
+            .order_by_nulls_last
+            .iter()
+            .map(|(oid, nulls_last)| (*column_oid_map.get(oid).unwrap_or(oid), *nulls_last))
+            .collect();
+        metadata.schema.set(trans)?;
+        regenerate_schema_views(trans, metadata.schema.oid)?;
+        Ok((metadata.schema.oid, column_oid_map))
+    }
+
+    /// Creates a new table with the same structure as an existing table, along with a copy of each of its rows that is not trashed.
+    /// Returns the OID of the new table.
+    pub fn clone_with_rows(source_table_oid: i64, new_name: String) -> Result<i64, Error> {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        let new_table_oid: i64 = Self::clone_with_rows_transact(&trans, source_table_oid, new_name)?;
+
+        // Commit the transaction
+        trans.commit()?;
+        Ok(new_table_oid)
+    }
+
+    /// Creates a new table with the same structure as an existing table, along with a copy of each of its rows that is not trashed.
+    /// The copied rows keep their OIDs, so that references to the rows of the existing table also identify the copies.
+    /// Object cells are left blank, so that the two tables do not share embedded rows, and File List cells share the same files.
+    /// Returns the OID of the new table.
+    pub fn clone_with_rows_transact(trans: &Transaction, source_table_oid: i64, new_name: String) -> Result<i64, Error> {
+        // Rows of an inheriting table are made of rows in each master table, which cannot be shared with the copy
+        let source: Self = Self::get_transact(trans, source_table_oid)?;
+        if source.schema.master_schema_oids.len() > 0 {
+            return Err(Error::AdhocError("Rows can only be copied from a table that does not inherit from other tables."));
+        }
+        let (new_table_oid, column_oid_map) = Self::clone_structure_transact(trans, source_table_oid, new_name)?;
+
+        // Sort the copied columns by where their values are stored
+        let mut stored_columns: Vec<(String, String)> = vec![(String::from("OID"), String::from("OID"))];
+        if source.track_timestamps {
+            stored_columns.push((String::from("CREATED_AT"), String::from("CREATED_AT")));
+            stored_columns.push((String::from("UPDATED_AT"), String::from("UPDATED_AT")));
+        }
+        let mut linked_columns: Vec<(String, String, String)> = Vec::new();
+        for (source_column_oid, new_column_oid) in column_oid_map.iter() {
+            match column::FullMetadata::get_transact(trans, *new_column_oid)?.column_type {
+                column_type::ColumnType::Primitive(column_type::Primitive::FileList) => {
+                    linked_columns.push((format!("FILELIST{source_column_oid}"), format!("FILELIST{new_column_oid}"), String::from("FILE_OID")));
+                }
+                column_type::ColumnType::Multiselect { table_oid, .. } => {
+                    linked_columns.push((format!("MULTISELECT{source_column_oid}"), format!("MULTISELECT{new_column_oid}"), format!("TABLE{table_oid}_OID")));
+                }
+                column_type::ColumnType::Primitive(column_type::Primitive::RowNumber)
+                | column_type::ColumnType::Object { .. }
+                | column_type::ColumnType::Formula { .. }
+                | column_type::ColumnType::Subreport { .. } => {}
+                column_type::ColumnType::Primitive(_)
+                | column_type::ColumnType::Select { .. } => {
+                    stored_columns.push((format!("COLUMN{source_column_oid}"), format!("COLUMN{new_column_oid}")));
+                }
+            }
+        }
+
+        // Copy the rows, then the links held by each row
+        let sql_copy_rows: String = format!(
+            "INSERT INTO TABLE{new_table_oid} ({}) SELECT {} FROM TABLE{source_table_oid} WHERE NOT TRASH ORDER BY OID",
+            stored_columns.iter().map(|(_, new_name)| new_name.clone()).collect::<Vec<String>>().join(", "),
+            stored_columns.iter().map(|(source_name, _)| source_name.clone()).collect::<Vec<String>>().join(", "),
+        );
+        trans.execute(&sql_copy_rows, [])?;
+        for (source_link_table, new_link_table, linked_oid_name) in linked_columns {
+            let sql_copy_links: String = format!(
+                "
+                INSERT INTO {new_link_table} (TABLE{new_table_oid}_OID, {linked_oid_name}, ORDERING)
+                SELECT l.TABLE{source_table_oid}_OID, l.{linked_oid_name}, l.ORDERING
+                FROM {source_link_table} l
+                INNER JOIN TABLE{new_table_oid} t ON t.OID = l.TABLE{source_table_oid}_OID
+                "
+            );
+            trans.execute(&sql_copy_links, [])?;
+        }
+        Ok(new_table_oid)
+    }
+
+    /// Measures how deeply Object columns are nested inside a table, and whether any chain of them loops back on itself.
+    pub fn get_object_nesting_transact(conn: &Connection, oid: i64) -> Result<ObjectNesting, Error> {
+        let edges: HashMap<i64, Vec<i64>> = load_object_edges(conn, false)?;
+        Ok(measure_object_nesting(&edges, oid, &mut HashSet::new()))
+    }
+
+    /// Measures how deeply Object columns are nested inside a table, and whether any chain of them loops back on itself.
+    pub fn get_object_nesting(oid: i64) -> Result<ObjectNesting, Error> {
+        let conn = db::open()?;
+        Self::get_object_nesting_transact(&conn, oid)
+    }
+
+    /// Checks that no chain of Object columns, starting from any table, is nested more deeply than the maximum.
+    /// Also checks that no chain of required Object columns loops back on itself, since inserting a row would then need to insert rows forever.
+    pub fn check_object_nesting_depth_transact(conn: &Connection) -> Result<(), Error> {
+        let max_depth: i64 = *MAX_OBJECT_NESTING_DEPTH.lock().unwrap();
+        let edges: HashMap<i64, Vec<i64>> = load_object_edges(conn, false)?;
+        for oid in edges.keys() {
+            if measure_object_nesting(&edges, *oid, &mut HashSet::new()).depth > max_depth {
+                return Err(Error::AdhocError("Object columns cannot be nested this deeply."));
+            }
+        }
+
+        let required_edges: HashMap<i64, Vec<i64>> = load_object_edges(conn, true)?;
+        for oid in required_edges.keys() {
+            if measure_object_nesting(&required_edges, *oid, &mut HashSet::new()).is_cyclic {
+                return Err(Error::AdhocError("Required Object columns cannot embed a table that embeds them in turn."));
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the space used by a table and each of the columns defined on it, including trashed rows.
+    pub fn get_storage_stats(oid: i64) -> Result<StorageStats, Error> {
+        let conn = db::open()?;
+
+        // Measures the pages used by an SQLite table, if the database can report it
+        let get_page_bytes = |name: String| -> Option<i64> {
+            conn.query_one(
+                "SELECT COALESCE(SUM(pgsize), 0) AS BYTES FROM dbstat WHERE name = ?1",
+                params![name],
+                |row| row.get::<_, i64>("BYTES"),
+            )
+            .ok()
+        };
+        let table_page_bytes: Option<i64> = get_page_bytes(format!("TABLE{oid}"));
+        let is_approximate: bool = table_page_bytes.is_none();
+
+        let sql_count: String = format!("SELECT COUNT(*) AS ROW_COUNT FROM TABLE{oid}");
+        let row_count: i64 = conn.query_one(&sql_count, [], |row| row.get("ROW_COUNT"))?;
+
+        // Measure each column defined on the table
+        let mut columns: Vec<ColumnStorageStats> = Vec::new();
+        let mut value_bytes: i64 = 0;
+        let mut external_bytes: i64 = 0;
+        for column_oid_result in conn
+            .prepare("SELECT OID FROM METADATA_COLUMN WHERE SCHEMA_OID = ?1 AND NOT TRASH ORDER BY ORDERING")?
+            .query_map(params![oid], |row| row.get::<_, i64>("OID"))?
+        {
+            let column: column::FullMetadata = column::FullMetadata::get_transact(&conn, column_oid_result?)?;
+            let (non_null_count, bytes): (i64, i64) = match &column.column_type {
+                column_type::ColumnType::Primitive(column_type::Primitive::File)
+                | column_type::ColumnType::Primitive(column_type::Primitive::Image) => {
+                    // The column holds file OIDs, so measure the stored content of each file
+                    let sql_select: String = format!(
+                        "
+                        SELECT 
+                            COUNT(t.COLUMN{0}) AS NON_NULL_COUNT, 
+                            COALESCE(SUM(LENGTH(t.COLUMN{0})), 0) AS VALUE_BYTES,
+                            COALESCE(SUM(LENGTH(b.CONTENT)), 0) AS FILE_BYTES 
+                        FROM TABLE{oid} t 
+                        LEFT JOIN METADATA_FILE__BLOB b ON b.OID = t.COLUMN{0}
+                        ",
+                        column.oid
+                    );
+                    let (non_null_count, column_value_bytes, file_bytes) = conn.query_one(&sql_select, [], |row| {
+                        Ok((
+                            row.get::<_, i64>("NON_NULL_COUNT")?,
+                            row.get::<_, i64>("VALUE_BYTES")?,
+                            row.get::<_, i64>("FILE_BYTES")?,
+                        ))
+                    })?;
+                    value_bytes += column_value_bytes;
+                    external_bytes += file_bytes;
+                    (non_null_count, column_value_bytes + file_bytes)
+                }
+                column_type::ColumnType::Primitive(column_type::Primitive::FileList) => {
+                    // Attachments are stored in a separate table, with a row for each attached file
+                    let sql_select: String = format!(
+                        "
+                        SELECT 
+                            COUNT(DISTINCT l.TABLE{oid}_OID) AS NON_NULL_COUNT, 
+                            COALESCE(SUM(LENGTH(b.CONTENT)), 0) AS FILE_BYTES 
+                        FROM FILELIST{} l 
+                        LEFT JOIN METADATA_FILE__BLOB b ON b.OID = l.FILE_OID
+                        ",
+                        column.oid
+                    );
+                    let (non_null_count, file_bytes) = conn.query_one(&sql_select, [], |row| {
+                        Ok((row.get::<_, i64>("NON_NULL_COUNT")?, row.get::<_, i64>("FILE_BYTES")?))
+                    })?;
+                    external_bytes += file_bytes;
+                    (non_null_count, file_bytes)
+                }
+                column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {
+                    // Row numbers are computed, so they use no space
+                    continue;
+                }
+                column_type::ColumnType::Primitive(_)
+                | column_type::ColumnType::Object { .. }
+                | column_type::ColumnType::Select { .. } => {
+                    let sql_select: String = format!(
+                        "SELECT COUNT(COLUMN{0}) AS NON_NULL_COUNT, COALESCE(SUM(LENGTH(COLUMN{0})), 0) AS BYTES FROM TABLE{oid}",
+                        column.oid
+                    );
+                    let (non_null_count, column_value_bytes) = conn.query_one(&sql_select, [], |row| {
+                        Ok((row.get::<_, i64>("NON_NULL_COUNT")?, row.get::<_, i64>("BYTES")?))
+                    })?;
+                    value_bytes += column_value_bytes;
+                    (non_null_count, column_value_bytes)
+                }
+                column_type::ColumnType::Multiselect { .. } => {
+                    // Selections are stored in a separate table, with a row for each selection
+                    let sql_count: String = format!("SELECT COUNT(*) AS SELECTION_COUNT FROM MULTISELECT{}", column.oid);
+                    let selection_count: i64 = conn.query_one(&sql_count, [], |row| row.get("SELECTION_COUNT"))?;
+                    let selection_bytes: i64 = get_page_bytes(format!("MULTISELECT{}", column.oid))
+                        .unwrap_or(selection_count * 16);
+                    external_bytes += selection_bytes;
+                    (selection_count, selection_bytes)
+                }
+                column_type::ColumnType::Formula { .. } | column_type::ColumnType::Subreport { .. } => {
+                    // Virtual columns are computed, so they use no space
+                    continue;
+                }
+            };
+            columns.push(ColumnStorageStats {
+                column_oid: column.oid,
+                column_name: column.name,
+                non_null_count,
+                bytes,
+            });
+        }
+
+        Ok(StorageStats {
+            row_count,
+            total_bytes: table_page_bytes.unwrap_or(value_bytes) + external_bytes,
+            is_approximate,
+            columns,
+        })
+    }
+
+    /// Finds the columns of a table in which every non-trashed row has the same value, or no value at all.
+    /// Tables with fewer than two rows are not checked, since their columns are trivially degenerate.
+    pub fn find_degenerate_columns(oid: i64) -> Result<Vec<DegenerateColumn>, Error> {
+        let conn = db::open()?;
+
+        let sql_count: String = format!("SELECT COUNT(*) AS ROW_COUNT FROM SCHEMA{oid}_VIEW");
+        let row_count: i64 = conn.query_one(&sql_count, [], |row| row.get("ROW_COUNT"))?;
+        if row_count < 2 {
+            return Ok(Vec::new());
+        }
+
+        // Count the distinct values of each stored column, using the same expressions as the view of the table
+        let mut columns: Vec<DegenerateColumn> = Vec::new();
+        for column_oid_result in conn
+            .prepare("SELECT OID FROM METADATA_COLUMN WHERE SCHEMA_OID = ?1 AND NOT TRASH ORDER BY ORDERING")?
+            .query_map(params![oid], |row| row.get::<_, i64>("OID"))?
+        {
+            let column: column::FullMetadata = column::FullMetadata::get_transact(&conn, column_oid_result?)?;
+            if let column_type::ColumnType::Formula { .. } | column_type::ColumnType::Subreport { .. } = &column.column_type {
+                // Virtual columns are computed, so there is nothing stored to clean up
+                continue;
+            }
+
+            let sql_select: String = format!(
+                "
+                SELECT 
+                    COUNT(DISTINCT COLUMN{0}_VALUE) AS DISTINCT_COUNT, 
+                    MIN(COLUMN{0}_LABEL) AS SHARED_LABEL 
+                FROM SCHEMA{oid}_VIEW
+                ",
+                column.oid
+            );
+            let (distinct_count, shared_label) = conn.query_one(&sql_select, [], |row| {
+                Ok((row.get::<_, i64>("DISTINCT_COUNT")?, row.get::<_, Option<String>>("SHARED_LABEL")?))
+            })?;
+            if distinct_count > 1 {
+                continue;
+            }
+
+            // A column that mixes a single value with blank cells still distinguishes rows
+            if distinct_count == 1 {
+                let sql_null_count: String = format!("SELECT COUNT(*) FROM SCHEMA{oid}_VIEW WHERE COLUMN{}_VALUE IS NULL", column.oid);
+                let null_count: i64 = conn.query_one(&sql_null_count, [], |row| row.get(0))?;
+                if null_count > 0 {
+                    continue;
+                }
+            }
+
+            columns.push(DegenerateColumn {
+                column_oid: column.oid,
+                column_name: column.name,
+                is_all_null: distinct_count == 0,
+                shared_label: if distinct_count == 0 { None } else { shared_label },
+            });
+        }
+        Ok(columns)
+    }
+
+    /// Computes a checksum over the values of the rows of a table, formatted as 16 hexadecimal digits.
+    /// The checksum is sensitive to the order of the rows, and excludes trashed rows and the values of Formula, Subreport, and Row Number columns.
+    pub fn get_checksum(oid: i64) -> Result<String, Error> {
+        let conn = db::open()?;
+
+        // Find the stored columns displayed in the table, including inherited columns
+        let mut value_exprs: Vec<String> = vec![String::from("QUOTE(OID)")];
+        for column_oid_result in conn
+            .prepare("SELECT COLUMN_OID FROM METADATA_SCHEMA_COLUMN_VIEW WHERE IS_REQUIRED AND SCHEMA_OID = ?1 ORDER BY ORDERING")?
+            .query_map(params![oid], |row| row.get::<_, i64>("COLUMN_OID"))?
+        {
+            let column: column::FullMetadata = column::FullMetadata::get_transact(&conn, column_oid_result?)?;
+            match &column.column_type {
+                column_type::ColumnType::Formula { .. }
+                | column_type::ColumnType::Subreport { .. }
+                | column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {}
+                _ => value_exprs.push(format!("QUOTE(COLUMN{}_VALUE)", column.oid)),
+            }
+        }
+
+        // Hash the values of each row in order, separating values so that adjacent values cannot run together
+        let sql_select: String = format!("SELECT {} FROM SCHEMA{oid}_VIEW ORDER BY ROW_INDEX", value_exprs.join(", "));
+        let mut select_stmt = conn.prepare(&sql_select)?;
+        let mut select_rows = select_stmt.query([])?;
+        let mut hash: u64 = FNV_OFFSET_BASIS;
+        while let Some(row) = select_rows.next()? {
+            for i in 0..value_exprs.len() {
+                let value: String = row.get(i)?;
+                hash = fnv1a(hash, value.as_bytes());
+                hash = fnv1a(hash, &[0x1f]);
+            }
+            hash = fnv1a(hash, &[0x1e]);
+        }
+        Ok(format!("{hash:016x}"))
+    }
+
+    /// Adds the columns that store row timestamps to the table, if they do not already exist.
+    /// Timestamps are stored as Julian day numbers, like Datetime columns.
+    fn add_timestamp_columns(trans: &Transaction, table_oid: i64) -> Result<(), Error> {
+        let table_name: String = format!("TABLE{table_oid}");
+        for timestamp_column_name in ["CREATED_AT", "UPDATED_AT"] {
+            if !trans.column_exists(Some("main"), &table_name, timestamp_column_name)? {
+                let sql_add_column: String =
+                    format!("ALTER TABLE {table_name} ADD COLUMN {timestamp_column_name} REAL");
+                trans.execute(&sql_add_column, [])?;
+            }
+        }
+        Ok(())
+    }
 }
 
 
@@ -126,10 +963,18 @@ pub struct DropdownValueEmit {
 const PUSH_DROPDOWN_VALUE_SIGNAL: &'static str = "table_row_label";
 
 impl DropdownValue {
-    pub fn emit_table_row_labels(app: AppHandle, processid: i64, table_oid: i64) -> Result<(), Error> {
+    pub fn emit_table_row_labels(app: AppHandle, processid: i64, table_oid: i64, column_oid: Option<i64>) -> Result<(), Error> {
         let conn = db::open()?;
         
-        let select_sql: String = format!("SELECT l.OID, COALESCE(l.PLAIN_LABEL, l.JSON_LABEL) AS LABEL FROM SCHEMA{table_oid}_VIEW l ORDER BY l.ROW_INDEX");
+        // Only offer rows of the subtype that the column is restricted to
+        let where_clause: String = match column_oid {
+            Some(column_oid) => match column::FullMetadata::get_reference_subtype_filter_sql_transact(&conn, column_oid)? {
+                Some(filter_sql) => format!("WHERE l.OID IN ({filter_sql})"),
+                None => String::new(),
+            },
+            None => String::new(),
+        };
+        let select_sql: String = format!("SELECT l.OID, COALESCE(l.PLAIN_LABEL, l.JSON_LABEL) AS LABEL FROM SCHEMA{table_oid}_VIEW l {where_clause} ORDER BY l.ROW_INDEX");
         println!("{select_sql}");
         let mut select_stmt = conn.prepare(&select_sql)?;
         let select_rows = select_stmt.query_and_then([], |row| Ok::<(i64, String), rusqlite::Error>((row.get::<_, i64>("OID")?, row.get::<_, String>("LABEL")?)))?;
@@ -147,4 +992,102 @@ impl DropdownValue {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::cell::DataCellValue;
+    use crate::data::{row, testing};
+
+    #[test]
+    fn clone_with_rows_copies_untrashed_rows_into_a_separate_table() {
+        let _db = testing::open_database();
+        let source_table_oid: i64 = testing::create_table("Colors", &[]);
+        let source_column_oid: i64 = testing::create_column(source_table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let red_row_oid: i64 = testing::insert_row(source_table_oid);
+        let trashed_row_oid: i64 = testing::insert_row(source_table_oid);
+        let blue_row_oid: i64 = testing::insert_row(source_table_oid);
+        testing::set_cell(source_table_oid, source_column_oid, red_row_oid, DataCellValue::Text(Some(String::from("Red"))));
+        testing::set_cell(source_table_oid, source_column_oid, blue_row_oid, DataCellValue::Text(Some(String::from("Blue"))));
+        row::trash(source_table_oid, trashed_row_oid).unwrap();
+
+        let copied_table_oid: i64 = FullMetadata::clone_with_rows(source_table_oid, String::from("Colors (Copy)")).unwrap();
+        assert_ne!(copied_table_oid, source_table_oid);
+        let copied_column_oid: i64 = testing::query_one(&format!("SELECT OID FROM METADATA_COLUMN WHERE SCHEMA_OID = {copied_table_oid}"));
+        let copied_rows: String = testing::query_one(&format!("SELECT GROUP_CONCAT(OID || ':' || COLUMN{copied_column_oid}, ',') FROM (SELECT * FROM TABLE{copied_table_oid} ORDER BY OID)"));
+        assert_eq!(copied_rows, format!("{red_row_oid}:Red,{blue_row_oid}:Blue"));
+
+        // Editing the copy leaves the source alone
+        testing::set_cell(copied_table_oid, copied_column_oid, red_row_oid, DataCellValue::Text(Some(String::from("Crimson"))));
+        let source_value: String = testing::query_one(&format!("SELECT COLUMN{source_column_oid} FROM TABLE{source_table_oid} WHERE OID = {red_row_oid}"));
+        assert_eq!(source_value, "Red");
+    }
+
+    #[test]
+    fn get_returns_metadata_for_a_table_and_an_error_for_a_missing_table() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Monsters", &[]);
+
+        let metadata: FullMetadata = FullMetadata::get(table_oid).unwrap();
+        assert_eq!(metadata.schema.oid, table_oid);
+        assert_eq!(metadata.schema.name, "Monsters");
+        let missing_table_oid: i64 = table_oid + 1000;
+        assert!(matches!(FullMetadata::get(missing_table_oid), Err(Error::TableNotFound { table_oid }) if table_oid == missing_table_oid));
+    }
+
+    #[test]
+    fn clone_structure_clones_embedded_object_tables() {
+        let _db = testing::open_database();
+        let stats_table_oid: i64 = testing::create_table("Stats", &[]);
+        testing::create_column(stats_table_oid, "Strength", column_type::ColumnType::Primitive(column_type::Primitive::Integer));
+        let monster_table_oid: i64 = testing::create_table("Monster", &[]);
+        testing::create_column(monster_table_oid, "Stats", column_type::ColumnType::Object { oid: 0, table_oid: stats_table_oid });
+
+        let cloned_table_oid: i64 = FullMetadata::clone_structure(monster_table_oid, String::from("Monster (Copy)")).unwrap();
+        let cloned_object_table_oid: i64 = testing::query_one(&format!(
+            "SELECT typ.TABLE_OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__OBJECT typ ON typ.OID = c.TYPE_OID WHERE c.SCHEMA_OID = {cloned_table_oid}"
+        ));
+        assert_ne!(cloned_object_table_oid, stats_table_oid);
+        let cloned_object_column_name: String = testing::query_one(&format!("SELECT NAME FROM METADATA_COLUMN WHERE SCHEMA_OID = {cloned_object_table_oid}"));
+        assert_eq!(cloned_object_column_name, "Strength");
+    }
+
+    #[test]
+    fn required_object_columns_cannot_embed_each_other() {
+        let _db = testing::open_database();
+        let room_table_oid: i64 = testing::create_table("Room", &[]);
+        let door_table_oid: i64 = testing::create_table("Door", &[]);
+        let mut door_column: column::FullMetadata = testing::column_metadata(room_table_oid, "Door", column_type::ColumnType::Object { oid: 0, table_oid: door_table_oid });
+        door_column.is_nullable = false;
+        door_column.create().unwrap();
+
+        // A nullable loop is allowed, since inserting a row does not insert a row for the loop
+        testing::create_column(door_table_oid, "Next Room", column_type::ColumnType::Object { oid: 0, table_oid: room_table_oid });
+        assert!(FullMetadata::get_object_nesting(room_table_oid).unwrap().is_cyclic);
+
+        let mut room_column: column::FullMetadata = testing::column_metadata(door_table_oid, "Room", column_type::ColumnType::Object { oid: 0, table_oid: room_table_oid });
+        room_column.is_nullable = false;
+        assert!(room_column.create().is_err());
+
+        // Inserting a row still terminates
+        testing::insert_row(room_table_oid);
+    }
+
+    #[test]
+    fn object_columns_cannot_be_nested_deeper_than_the_maximum() {
+        let _db = testing::open_database();
+        let inner_table_oid: i64 = testing::create_table("Inner", &[]);
+        let middle_table_oid: i64 = testing::create_table("Middle", &[]);
+        let outer_table_oid: i64 = testing::create_table("Outer", &[]);
+        testing::create_column(middle_table_oid, "Inner", column_type::ColumnType::Object { oid: 0, table_oid: inner_table_oid });
+
+        set_max_object_nesting_depth(1).unwrap();
+        let result = testing::column_metadata(outer_table_oid, "Middle", column_type::ColumnType::Object { oid: 0, table_oid: middle_table_oid }).create();
+        set_max_object_nesting_depth(DEFAULT_MAX_OBJECT_NESTING_DEPTH).unwrap();
+        assert!(result.is_err());
+
+        testing::create_column(outer_table_oid, "Middle", column_type::ColumnType::Object { oid: 0, table_oid: middle_table_oid });
+        assert_eq!(FullMetadata::get_object_nesting(outer_table_oid).unwrap().depth, 2);
+    }
+}
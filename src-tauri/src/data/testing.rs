@@ -0,0 +1,82 @@
+use crate::data::cell::{DataCellEntry, DataCellValue};
+use crate::data::{column, column_type, row, schema, table, view};
+use crate::util::db;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+
+/// Tests share the database opened by the application, so only one test may use it at a time.
+static TEST_DATABASE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Opens a new, empty database for a test.
+/// The database is reserved for the test until the returned guard is dropped.
+pub fn open_database() -> MutexGuard<'static, ()> {
+    let guard: MutexGuard<'static, ()> = TEST_DATABASE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    db::init_new(db::JournalMode::Delete).unwrap();
+    column::set_validation_enabled(true);
+    view::clear_bulk_mode();
+    guard
+}
+
+/// Creates a table with the given master tables, returning its OID.
+pub fn create_table(name: &str, master_table_oids: &[i64]) -> i64 {
+    let mut metadata: table::FullMetadata = table::FullMetadata {
+        schema: schema::FullMetadata {
+            oid: 0,
+            name: String::from(name),
+            master_schema_oids: HashSet::from_iter(master_table_oids.iter().cloned()),
+            order_by_column_oids: Vec::new(),
+            order_by_nulls_last: HashMap::new(),
+        },
+        track_timestamps: false,
+        default_page_size: None,
+    };
+    metadata.create().unwrap();
+    metadata.schema.oid
+}
+
+/// Builds the metadata of a new column of a table, which can be adjusted before it is created.
+pub fn column_metadata(table_oid: i64, name: &str, column_type: column_type::ColumnType) -> column::FullMetadata {
+    let conn = db::open().unwrap();
+    column::FullMetadata {
+        oid: 0,
+        hidden: false,
+        schema: schema::FullMetadata::get(&conn, table_oid).unwrap(),
+        name: String::from(name),
+        column_type,
+        style: String::new(),
+        ordering: -1,
+        default_value: None,
+        is_nullable: true,
+        is_primary_key: false,
+        case_insensitive_unique: false,
+        pretty_print_json: false,
+        empty_as_null: true,
+        reference_on_delete: column::ReferenceOnDelete::default(),
+        reference_subtype_filter: None,
+        integer_format: column::IntegerFormat::default(),
+        reference_display_column_oid: None,
+    }
+}
+
+/// Creates a column on a table, returning its OID.
+pub fn create_column(table_oid: i64, name: &str, column_type: column_type::ColumnType) -> i64 {
+    let mut metadata: column::FullMetadata = column_metadata(table_oid, name, column_type);
+    metadata.create().unwrap();
+    metadata.oid
+}
+
+/// Inserts a blank row at the end of a table, returning its OID.
+pub fn insert_row(table_oid: i64) -> i64 {
+    row::insert(table_oid, None, row::InsertMode::default(), None).unwrap()
+}
+
+/// Sets the value of a cell, returning the previous contents of the cell.
+pub fn set_cell(table_oid: i64, column_oid: i64, row_oid: i64, value: DataCellValue) -> DataCellEntry {
+    DataCellEntry::new(table_oid, column_oid, row_oid, value).set().unwrap()
+}
+
+/// Runs a query that returns a single value.
+pub fn query_one<T: rusqlite::types::FromSql>(sql: &str) -> T {
+    let conn = db::open().unwrap();
+    conn.query_one(sql, [], |row| row.get(0)).unwrap()
+}
@@ -1,10 +1,11 @@
 use crate::{
-    data::{column, column_type, datasource::Datasource, schema, table, view}, util::{error::Error, formula::Formula},
+    data::{column, column_type, datasource::Datasource, schema, table, view}, util::{db, error::Error, formula::Formula},
 };
 use bitflags::bitflags;
 use rusqlite::{params, Connection, OptionalExtension, Transaction};
-use std::{cell, collections::{HashMap, HashSet}, mem::transmute};
+use std::{cell, collections::{HashMap, HashSet}, mem::transmute, sync::Mutex};
 use regex::Regex;
+use serde::Serialize;
 
 
 /// Encodes a string to make it safe for inserting inside an SQL string.
@@ -76,18 +77,19 @@ impl DatasourceCteConstructor {
             format!(
                 ", {} AS {}_TABLE",
                 {
-                    let mut child_inheritor_datasources = self.child_datasources.iter()
+                    let child_inheritor_datasources: Vec<String> = self.child_datasources.iter()
                         .filter_map(|child_datasource| {
                             if let Datasource::InheritorTable { .. } = child_datasource {
                                 Some(format!("{}_TABLE", child_datasource.get_alias()))
                             } else {
                                 None
                             }
-                        });
-                    if child_inheritor_datasources.any(|_| true) {
+                        })
+                        .collect();
+                    if child_inheritor_datasources.len() > 0 {
                         format!(
                             "COALESCE({}, {})",
-                            child_inheritor_datasources.reduce(|acc, e| format!("{acc}, {e}")).unwrap(),
+                            child_inheritor_datasources.join(", "),
                             self.datasource.get_schema_oid()?
                         )
                     } else {
@@ -126,9 +128,10 @@ impl DatasourceCteConstructor {
                         }
                         column_type::ColumnType::Multiselect { table_oid, .. } => {
                             format!(
-                                ", m.TABLE{}_OID AS PARENT_{}_OID", 
+                                ", m.TABLE{}_OID AS PARENT_{}_OID, m.ORDERING AS {}_ORDERING", 
                                 parent_datasource.get_schema_oid()?, 
-                                parent_datasource.get_alias()
+                                parent_datasource.get_alias(),
+                                self.datasource.get_alias()
                             )
                         }
                         _ => {
@@ -233,15 +236,22 @@ impl DatasourceCteConstructor {
 
     /// Adds a primitive column to the CTE.
     /// Assumes that the column is owned by the schema of this datasource.
-    fn add_primitive_column(&mut self, column_oid: i64, prim: column_type::Primitive) -> DatasourceCteColumn {
+    fn add_primitive_column(&mut self, column_oid: i64, prim: column_type::Primitive) -> Result<DatasourceCteColumn, Error> {
         if !self.columns.contains_key(&column_oid) {
             let datasource_alias: String = self.datasource.get_alias();
+            let schema_oid: i64 = self.datasource.get_schema_oid()?;
             self.columns.insert(column_oid, DatasourceCteColumn {
-                value_expr: format!("t.COLUMN{column_oid}"),
+                value_expr: match prim {
+                    // File List columns are valued by the number of attached files
+                    column_type::Primitive::FileList => format!("(SELECT COUNT(*) FROM FILELIST{column_oid} f WHERE f.TABLE{schema_oid}_OID = t.OID)"),
+                    // Row Number columns are only numbered once the rows of the view are sorted
+                    column_type::Primitive::RowNumber => String::from("NULL"),
+                    _ => format!("t.COLUMN{column_oid}")
+                },
                 value_ord: format!("{datasource_alias}_COLUMN{column_oid}")
             });
         }
-        return self.columns[&column_oid].clone();
+        return Ok(self.columns[&column_oid].clone());
     }
 
     /// Adds an object column to the CTE.
@@ -273,7 +283,8 @@ impl DatasourceCteConstructor {
         if !self.columns.contains_key(&column_oid) {
             let datasource_alias: String = self.datasource.get_alias();
             self.columns.insert(column_oid, DatasourceCteColumn {
-                value_expr: format!("(GROUP_CONCAT(CAST({datasource_alias}_COLUMN{column_oid}_OID AS TEXT), ',') OVER (PARTITION BY t.OID ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING))"),
+                // The selected rows are listed in the order that they were selected
+                value_expr: format!("(GROUP_CONCAT(CAST({datasource_alias}_COLUMN{column_oid}_OID AS TEXT), ',') OVER (PARTITION BY t.OID ORDER BY {datasource_alias}_COLUMN{column_oid}_ORDERING ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING))"),
                 value_ord: format!("{datasource_alias}_COLUMN{column_oid}")
             });
         }
@@ -306,10 +317,12 @@ impl SelectParameterType {
                 ],
                 column_type::Primitive::PlainText => vec![
                     column_type::Primitive::JsonText, 
+                    column_type::Primitive::LongText, 
                     prim
                 ],
                 column_type::Primitive::Number => vec![
                     column_type::Primitive::Integer, 
+                    column_type::Primitive::Percentage, 
                     column_type::Primitive::Datetime, 
                     column_type::Primitive::Date, 
                     column_type::Primitive::Boolean,
@@ -357,12 +370,19 @@ impl SelectParameterType {
     /// Returns true if a value of this type can be numeric.
     fn is_numeric_type(&self) -> bool {
         return self.primitive_types.contains(&column_type::Primitive::Number)
+            || self.primitive_types.contains(&column_type::Primitive::Percentage)
             || self.primitive_types.contains(&column_type::Primitive::Datetime)
             || self.primitive_types.contains(&column_type::Primitive::Date)
             || self.primitive_types.contains(&column_type::Primitive::Integer)
             || self.primitive_types.contains(&column_type::Primitive::Boolean);
     }
 
+    /// Returns true if a value of this type can only be a geographic point.
+    fn is_point_type(&self) -> bool {
+        return self.primitive_types.len() == 1
+            && self.primitive_types.contains(&column_type::Primitive::Point);
+    }
+
     /// Returns true if a value of this type can be a file.
     fn is_file_type(&self) -> bool {
         return self.primitive_types.contains(&column_type::Primitive::File)
@@ -382,13 +402,20 @@ impl SelectParameterType {
         if self.is_text_type() && !self.is_file_type() && !self.is_numeric_type() {
             return value_expr.clone();
         }
+
+        // Check if pure point, which is stored as a JSON pair of [latitude, longitude]
+        if self.is_point_type() {
+            return format!("IF({value_expr} IS NULL, NULL, FORMAT('%.6f, %.6f', {value_expr} ->> '$[0]', {value_expr} ->> '$[1]'))");
+        }
         
         // Check if pure number
         if self.is_numeric_type() && !self.is_file_type() && !self.is_text_type() {
             if self.primitive_types.contains(&column_type::Primitive::Number) {
                 return format!("CAST({value_expr} AS TEXT)");
+            } else if self.primitive_types.contains(&column_type::Primitive::Percentage) {
+                return format!("IF({value_expr} IS NULL, NULL, FORMAT('%g%%', {value_expr} * 100.0))");
             } else if self.primitive_types.contains(&column_type::Primitive::Datetime) {
-                return format!("STRFTIME('%FT%TZ', {value_expr}, 'julianday')");
+                return format!("STRFTIME('%FT%H:%M:%fZ', {value_expr}, 'julianday')");
             } else if self.primitive_types.contains(&column_type::Primitive::Date) {
                 return format!("DATE({value_expr}, 'julianday')");
             } else if self.primitive_types.contains(&column_type::Primitive::Integer) {
@@ -419,12 +446,18 @@ impl SelectParameterType {
             }
         }
         
+        // Check if pure point, which is already stored as JSON
+        if self.is_point_type() {
+            return value_expr.clone();
+        }
+        
         // Check if pure number
         if self.is_numeric_type() && !self.is_file_type() && !self.is_text_type() {
-            if self.primitive_types.contains(&column_type::Primitive::Number) {
+            if self.primitive_types.contains(&column_type::Primitive::Number)
+                || self.primitive_types.contains(&column_type::Primitive::Percentage) {
                 return format!("CAST({value_expr} AS TEXT)");
             } else if self.primitive_types.contains(&column_type::Primitive::Datetime) {
-                return format!("'\"' || STRFTIME('%FT%TZ', {value_expr}, 'julianday') || '\"'");
+                return format!("'\"' || STRFTIME('%FT%H:%M:%fZ', {value_expr}, 'julianday') || '\"'");
             } else if self.primitive_types.contains(&column_type::Primitive::Date) {
                 return format!("'\"' || DATE({value_expr}, 'julianday') || '\"'");
             } else if self.primitive_types.contains(&column_type::Primitive::Integer) {
@@ -449,6 +482,7 @@ impl SelectParameterType {
         }
         if temp.contains(&column_type::Primitive::Number) {
             temp.remove(&column_type::Primitive::Integer);
+            temp.remove(&column_type::Primitive::Percentage);
         }
         if temp.contains(&column_type::Primitive::File) {
             temp.remove(&column_type::Primitive::Image);
@@ -653,6 +687,13 @@ enum SelectMainColumn {
 
         /// The ordinal for the full-reload dependencies.
         full_reload_dependencies_ord: String 
+    },
+    RowNumber {
+        /// The ordinal for the row number.
+        value_ord: String,
+
+        /// The ordinal for the label of the row number.
+        label_ord: String
     }
 }
 
@@ -697,7 +738,12 @@ enum SelectConstructorType {
         schema_oid: i64,
 
         /// The columns of the schema.
-        columns: Vec<SelectMainColumn>
+        columns: Vec<SelectMainColumn>,
+
+        /// The ordinals that rows are sorted by, in order of precedence.
+        /// The second item in each tuple is true if the order is ascending, and false if descending.
+        /// The third item in each tuple is true if NULL values are sorted after all other values, and false if before.
+        order_by: Vec<(String, bool, bool)>
     },
 
     SelectLabelConstructor {
@@ -717,26 +763,50 @@ enum SelectConstructorType {
 impl SelectConstructorType {
     fn build(&self, trans: &Transaction, cte_list: Vec<String>, oid_list: Vec<String>) -> Result<String, Error> {
         Ok(match self {
-            Self::SelectMainConstructor { schema_oid, columns } => {
+            Self::SelectMainConstructor { schema_oid, columns, order_by } => {
+                // ORDER BY expressions
+                // Each column is preceded by a sort key that places NULL values either first or last
+                let order_by_expr: String = match order_by.iter()
+                    .map(|(order_ord, order_asc, order_nulls_last)| format!(
+                        "CASE WHEN m.{order_ord} IS NULL THEN 1 ELSE 0 END {}, m.{order_ord} {}",
+                        if *order_nulls_last { "ASC" } else { "DESC" },
+                        if *order_asc { "ASC" } else { "DESC" }
+                    ))
+                    .reduce(|acc, e| format!("{acc}, {e}")) {
+                    Some(order_exprs) => format!("ORDER BY {order_exprs}"),
+                    None => String::from("")
+                };
+
                 format!(
                     "
                     WITH {} 
                     SELECT 
-                        ROW_NUMBER() OVER ({}) AS ROW_INDEX,
-                        l.PLAIN_LABEL, 
-                        l.JSON_LABEL, 
+                        ROW_NUMBER() OVER ({order_by_expr}) AS ROW_INDEX,
                         {}
-                        {} 
-                    FROM WRAPPER w 
-                    INNER JOIN SCHEMA{schema_oid}_LABEL_VIEW l {}
+                        m.*
+                    FROM (
+                        SELECT
+                            l.PLAIN_LABEL, 
+                            l.JSON_LABEL, 
+                            {}
+                            {} 
+                        FROM WRAPPER w 
+                        INNER JOIN SCHEMA{schema_oid}_LABEL_VIEW l {}
+                    ) m
                     ",
                     
                     // All of the CTEs, including the wrapper
                     cte_list.join(", "),
 
-                    // ORDER BY expressions
-                    // For now, leaving blank
-                    String::from(""),
+                    // Row Number columns display the same index as ROW_INDEX
+                    columns.iter()
+                        .filter_map(|col| match col {
+                            SelectMainColumn::RowNumber { value_ord, label_ord } => Some(format!(
+                                "ROW_NUMBER() OVER ({order_by_expr}) AS {value_ord}, CAST(ROW_NUMBER() OVER ({order_by_expr}) AS TEXT) AS {label_ord}, "
+                            )),
+                            _ => None
+                        })
+                        .collect::<String>(),
 
                     // Include OBJECT_LABEL and ROOT{schema_oid}_SCHEMA columns if the schema is of type table
                     if let Some(root_datasource) = Datasource::get_default_datasource_transact(trans, schema_oid.clone())? {
@@ -763,11 +833,12 @@ impl SelectConstructorType {
                     // Select each column from the wrapper
                     oid_list.iter().map(|oid| format!("w.{oid}"))
                         .chain(
-                            columns.iter().map(|col| match col {
+                            columns.iter().filter_map(|col| match col {
                                 SelectMainColumn::Cell { value_expr, value_ord, label_expr, label_ord } => 
-                                    format!("{value_expr} AS {value_ord}, {label_expr} AS {label_ord}"),
+                                    Some(format!("{value_expr} AS {value_ord}, {label_expr} AS {label_ord}")),
                                 SelectMainColumn::Formula { value_expr, value_ord, label_expr, label_ord, cell_expr, cell_ord, isolated_dependencies_expr, isolated_dependencies_ord, full_reload_dependencies_expr, full_reload_dependencies_ord } => 
-                                    format!("{value_expr} AS {value_ord}, {label_expr} AS {label_ord}, {cell_expr} AS {cell_ord}, {isolated_dependencies_expr} AS {isolated_dependencies_ord}, {full_reload_dependencies_expr} AS {full_reload_dependencies_ord}")
+                                    Some(format!("{value_expr} AS {value_ord}, {label_expr} AS {label_ord}, {cell_expr} AS {cell_ord}, {isolated_dependencies_expr} AS {isolated_dependencies_ord}, {full_reload_dependencies_expr} AS {full_reload_dependencies_ord}")),
+                                SelectMainColumn::RowNumber { .. } => None // Numbered after the rows are sorted
                             })
                         )
                         .reduce(|acc, e| format!("{acc}, {e}"))
@@ -1078,7 +1149,8 @@ impl SelectConstructor {
             cte_datasource: HashMap::new(),
             constructor_type: SelectConstructorType::SelectMainConstructor { 
                 schema_oid: schema_oid.clone(),
-                columns: Vec::new() 
+                columns: Vec::new(),
+                order_by: Vec::new()
             }
         };
 
@@ -1098,10 +1170,26 @@ impl SelectConstructor {
             }
         }
 
+        let mut sort_ords: HashMap<i64, String> = HashMap::new();
         for row_result in trans.prepare("SELECT COLUMN_OID FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 ORDER BY IS_SUBREPORT ASC")?.query_map(params![schema_oid], |row| row.get::<_, i64>("COLUMN_OID"))? {
             let column_oid = row_result?;
             let column: column::FullMetadata = column::FullMetadata::get_transact(trans, column_oid.clone())?;
             let column_type: column_type::ColumnType = column.column_type.clone();
+            let pretty_print_json: bool = column.pretty_print_json 
+                && column_type == column_type::ColumnType::Primitive(column_type::Primitive::JsonText);
+
+            // Primitive columns are sorted by their raw value, while Object, Select, Multiselect, and Formula columns are sorted by their displayed label
+            match &column_type {
+                column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {} // Row numbers already follow the sort order
+                column_type::ColumnType::Primitive(_) => {
+                    sort_ords.insert(column_oid, format!("COLUMN{column_oid}_VALUE"));
+                }
+                column_type::ColumnType::Subreport { .. } => {} // Subreports cannot be sorted
+                _ => {
+                    sort_ords.insert(column_oid, format!("COLUMN{column_oid}_LABEL"));
+                }
+            }
+
             let param: SelectParameter = match &root_datasource {
                 Some(root_datasource) => {
                     let root_datasource: SelectDatasource = SelectDatasource::new_norecursion(root_datasource.clone(), schema_oid.clone());
@@ -1120,8 +1208,20 @@ impl SelectConstructor {
                 } else {
                     param.json_label_expr_norecursion
                 };
+                // Display valid JSON with consistent indentation, passing through anything malformed as-is
+                let label_expr: String = if pretty_print_json {
+                    format!("IIF(JSON_VALID({label_expr}), JSON_PRETTY({label_expr}), {label_expr})")
+                } else {
+                    label_expr
+                };
                 let label_ord: String = format!("COLUMN{column_oid}_LABEL");
                 match column_type {
+                    column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => {
+                        columns.push(SelectMainColumn::RowNumber { 
+                            value_ord,
+                            label_ord
+                        });
+                    }
                     column_type::ColumnType::Formula { .. } => {
                         columns.push(SelectMainColumn::Formula { 
                             value_expr,
@@ -1161,6 +1261,16 @@ impl SelectConstructor {
             }
         }
 
+        // Sort the rows by the ORDER BY columns of the schema
+        if let SelectConstructorType::SelectMainConstructor { order_by, .. } = &mut select_constructor.constructor_type {
+            for row_result in trans.prepare("SELECT COLUMN_OID, SORT_ASCENDING, NULLS_LAST FROM METADATA_SCHEMA_ORDERBY_VIEW WHERE SCHEMA_OID = ?1 ORDER BY ORDERING")?.query_map(params![schema_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, bool>("SORT_ASCENDING")?, row.get::<_, bool>("NULLS_LAST")?)))? {
+                let (column_oid, sort_ascending, nulls_last) = row_result?;
+                if let Some(sort_ord) = sort_ords.get(&column_oid) {
+                    order_by.push((sort_ord.clone(), sort_ascending, nulls_last));
+                }
+            }
+        }
+
         Ok(select_constructor)
     }
 
@@ -1387,11 +1497,21 @@ impl SelectConstructor {
         match column.column_type {
             column_type::ColumnType::Primitive(prim) => {
                 if let Some(cte) = self.cte_datasource.get_mut(&datasource.datasource.get_alias()) {
-                    let cte_column = cte.add_primitive_column(column.oid, prim.clone());
+                    let cte_column = cte.add_primitive_column(column.oid, prim.clone())?;
+                    let is_integer: bool = prim == column_type::Primitive::Integer;
+                    let is_file_list: bool = prim == column_type::Primitive::FileList;
                     let scalar_type = SelectParameterType::from(prim);
                     
                     let value_expr: String = format!("w.{}", cte_column.value_ord);
-                    let plain_label_expr: String = scalar_type.construct_plain_label_expr(&value_expr);
+                    let plain_label_expr: String = if is_integer {
+                        // Integer columns are displayed in the format chosen for the column
+                        column.integer_format.construct_label_expr(&value_expr)
+                    } else if is_file_list {
+                        // File List columns are displayed as the number of attached files
+                        format!("IIF(COALESCE({value_expr}, 0) = 0, NULL, {value_expr} || IIF({value_expr} = 1, ' file', ' files'))")
+                    } else {
+                        scalar_type.construct_plain_label_expr(&value_expr)
+                    };
                     let json_label_expr: String = scalar_type.construct_json_label_expr(&value_expr);
 
                     return Ok(SelectParameter::new_norecursion(
@@ -1460,7 +1580,11 @@ impl SelectConstructor {
             }
             column_type::ColumnType::Multiselect { table_oid, .. } => {
                 // Add the datasource for the OIDs of the Multiselect column
-                let multiselect_datasource = datasource.datasource.append_path(format!("_COLUMN{}", column.oid))?;
+                // The datasource is built from the column metadata already in hand, since the column may not have been committed yet
+                let multiselect_datasource = Datasource::Column {
+                    parent_datasource: Box::new(datasource.datasource.clone()),
+                    column: column.clone(),
+                };
                 let multiselect_datasource_oid: String = format!("{}_OID", multiselect_datasource.get_alias());
                 self.add_datasource(multiselect_datasource, true);
 
@@ -1511,6 +1635,7 @@ impl SelectConstructor {
                     SelectConstructorType::SelectMainConstructor { .. } => {
                         // Examine the schema of SCHEMA{report_oid}_LABEL_VIEW to see what filters are applicable to the report
                         let mut filtered_columns: Vec<(String, String)> = Vec::new();
+                        let mut child_oid_columns: Vec<String> = Vec::new();
                         let oid_regex = Regex::new(r"ROOT\d+(?:_MASTER\d+|_INHERITOR\d+|_COLUMN\d+)*_OID").unwrap();
                         let pragma_sql: String = format!("PRAGMA table_info(SCHEMA{report_oid}_LABEL_VIEW)");
                         for row_result in trans.prepare(&pragma_sql)?.query_map([], |row| row.get("NAME"))? {
@@ -1528,6 +1653,9 @@ impl SelectConstructor {
                                         oid_column_name,
                                         format!("w.{modified_datasource_alias}_OID")
                                     ));
+                                } else {
+                                    // The OIDs that are not filtered identify the child rows listed in the cell
+                                    child_oid_columns.push(oid_column_name);
                                 }
                             }
                         }
@@ -1541,13 +1669,14 @@ impl SelectConstructor {
                             ))
                             .reduce(|acc, e| format!("({acc} || '&' || {e})"))
                             .unwrap_or(String::from("''"));
+                        // The child rows are listed in the order that they were created
                         let json_label_expr: String = format!(
-                            "NULLIF('[ ' || GROUP_CONCAT((SELECT l.JSON_LABEL FROM SCHEMA{report_oid}_LABEL_VIEW l {}), ', ') OVER ({}) || ' ]', '[  ]')",
-                            if filtered_columns.len() > 0 {
+                            "NULLIF('[ ' || (SELECT GROUP_CONCAT(l.JSON_LABEL, ', ' {}) FROM SCHEMA{report_oid}_LABEL_VIEW l {}) || ' ]', '[  ]')",
+                            if child_oid_columns.len() > 0 {
                                 format!(
-                                    "WHERE {}",
-                                    filtered_columns.iter().map(|(filtered_oid_ord, filtered_oid_value)| format!("l.{filtered_oid_ord} IS {filtered_oid_value}"))
-                                        .reduce(|acc, e| format!("{acc} AND {e}"))
+                                    "ORDER BY {}",
+                                    child_oid_columns.iter().map(|child_oid_ord| format!("l.{child_oid_ord}"))
+                                        .reduce(|acc, e| format!("{acc}, {e}"))
                                         .unwrap()
                                 )
                             } else {
@@ -1555,9 +1684,9 @@ impl SelectConstructor {
                             },
                             if filtered_columns.len() > 0 {
                                 format!(
-                                    "PARTITION BY {}",
-                                    filtered_columns.iter().map(|(_, filtered_oid_value)| filtered_oid_value.clone())
-                                        .reduce(|acc, e| format!("{acc}, {e}"))
+                                    "WHERE {}",
+                                    filtered_columns.iter().map(|(filtered_oid_ord, filtered_oid_value)| format!("l.{filtered_oid_ord} IS {filtered_oid_value}"))
+                                        .reduce(|acc, e| format!("{acc} AND {e}"))
                                         .unwrap()
                                 )
                             } else {
@@ -2071,8 +2200,31 @@ impl SelectConstructor {
     /// The third item of the returned tuple is the non-recursive JSON label.
     /// The fourth item of the returned tuple is the recursive JSON label.
     fn construct_select_label(&mut self, trans: &Transaction, datasource: SelectDatasource, object_column_oid: i64, object_table_oid: i64, value_expr: &String, is_collection: bool) -> Result<(String, String, String, String), Error> {
+        // The label is made from the key columns of the referenced table, unless the Select/Multiselect column chooses a column to display instead
+        let display_column: Option<(i64, i64)> = trans
+            .query_one(
+                "
+                SELECT sc.COLUMN_OID, sc.ORDERING 
+                FROM METADATA_COLUMN c 
+                INNER JOIN METADATA_SCHEMA_COLUMN_VIEW sc ON sc.COLUMN_OID = c.REFERENCE_DISPLAY_COLUMN_OID 
+                WHERE c.OID = ?1 AND sc.SCHEMA_OID = ?2
+                ", 
+                params![object_column_oid, object_table_oid], 
+                |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, i64>("ORDERING")?))
+            )
+            .optional()?;
+        let mut label_columns: Vec<(i64, i64)> = Vec::new();
+        match display_column {
+            Some(display_column) => label_columns.push(display_column),
+            None => {
+                for row_result in trans.prepare("SELECT COLUMN_OID, ORDERING FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND IS_PRIMARY_KEY ORDER BY IS_SUBREPORT ASC")?.query_map(params![object_table_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, i64>("ORDERING")?)))? {
+                    label_columns.push(row_result?);
+                }
+            }
+        }
+
         match &mut self.constructor_type {
-            SelectConstructorType::SelectMainConstructor { .. } => {
+            SelectConstructorType::SelectMainConstructor { .. } if display_column.is_none() => {
                 // MAIN views are allowed to select the label from the LABEL view
                 return Ok((
                     format!("(SELECT l.PLAIN_LABEL FROM SCHEMA{object_table_oid}_LABEL_VIEW l WHERE l.OID = {value_expr})"),
@@ -2086,6 +2238,7 @@ impl SelectConstructor {
                 //
                 // First, we need to check if the label for this Object column would induce recursion
                 // We do this by checking each parent datasource to see if it has the same table_oid as the table the Object column points to
+                // The root datasource is included, so a Select or Multiselect column referencing its own table (e.g. a "parent" column) is caught on the first pass
                 //
                 
                 for looped_datasource in datasource.datasource.linearize() {
@@ -2095,6 +2248,18 @@ impl SelectConstructor {
                         // This meets the condition set above, so we have confirmed the Select/Multiselect column induces recursion in the label
                         // 
 
+                        // If this column is itself part of the label it points to, following it would build the same label forever
+                        // Only note whether a row is referenced, as the other key columns already identify the row
+                        if label_columns.iter().any(|(column_oid, _)| *column_oid == object_column_oid) {
+                            let json_label_expr: String = format!("IF({value_expr} IS NOT NULL, 'null', NULL)");
+                            return Ok((
+                                String::from("NULL"),
+                                String::from("NULL"),
+                                json_label_expr.clone(),
+                                json_label_expr
+                            ));
+                        }
+
                         // First, we note where the recursion occurred, and where it should loop backwards to
                         let recursive_datasource: SelectDatasource = SelectDatasource::new_recursion(
                             looped_datasource, 
@@ -2109,8 +2274,7 @@ impl SelectConstructor {
 
                         // Construct labels for each key column on table referenced by Object, including non-required columns
                         let mut key_columns: Vec<(String, SelectParameter, i64)> = Vec::new();
-                        for row_result in trans.prepare("SELECT COLUMN_OID, ORDERING FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND IS_PRIMARY_KEY ORDER BY IS_SUBREPORT ASC")?.query_map(params![object_table_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, i64>("ORDERING")?)))? {
-                            let (column_oid, ordering) = row_result?;
+                        for (column_oid, ordering) in label_columns.iter().cloned() {
                             let column: column::FullMetadata = column::FullMetadata::get_transact(trans, column_oid)?;
                             let json_safe_column_name: String = json_encode_string(&column.name);
                             
@@ -2174,121 +2338,124 @@ impl SelectConstructor {
                         ));
                     }
                 }
+            }
 
-                //
-                // We have now confirmed that the Object column does not induce recursion.
-                // To construct the label for the Object column, we follow a similar procedure to the above
-                // 
+            SelectConstructorType::SelectMainConstructor { .. } => {
+                // MAIN views build the label from the display column in the same way as LABEL views
+            }
+        }
 
-                let object_datasource = SelectDatasource {
-                    datasource: datasource.datasource.append_path(format!("_COLUMN{object_column_oid}"))?,
-                    replace_root: if let Some(Datasource::Table { oid, .. }) = Datasource::get_default_datasource_transact(trans, object_table_oid)? {
-                        oid
-                    } else {
-                        return Err(Error::AdhocError("No default datasource for table."));
-                    },
-                    alias: datasource.alias 
-                };
+        //
+        // We have now confirmed that the Object column does not induce recursion.
+        // To construct the label for the Object column, we follow a similar procedure to the above
+        // 
 
-                // Add datasource for each inheritor table
-                for row_result in trans.prepare("SELECT INHERITOR_DATASOURCE_PATH FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW WHERE MASTER_SCHEMA_OID = ?1")?.query_map(params![object_table_oid], |row| row.get("INHERITOR_DATASOURCE_PATH"))? {
-                    let inheritor_datasource_path: String = row_result?;
-                    self.add_datasource(
-                        datasource.datasource.append_path(inheritor_datasource_path)?, 
-                        is_collection
-                    );
-                }
+        let object_datasource = SelectDatasource {
+            datasource: datasource.datasource.append_path(format!("_COLUMN{object_column_oid}"))?,
+            replace_root: if let Some(Datasource::Table { oid, .. }) = Datasource::get_default_datasource_transact(trans, object_table_oid)? {
+                oid
+            } else {
+                return Err(Error::AdhocError("No default datasource for table."));
+            },
+            alias: datasource.alias 
+        };
 
-                // Construct labels for each key column on table referenced by Object, including non-required columns
-                let mut key_columns: Vec<(String, SelectParameter, i64)> = Vec::new();
-                for row_result in trans.prepare("SELECT COLUMN_OID, ORDERING FROM METADATA_SCHEMA_COLUMN_VIEW WHERE SCHEMA_OID = ?1 AND IS_PRIMARY_KEY ORDER BY IS_SUBREPORT ASC")?.query_map(params![object_table_oid], |row| Ok((row.get::<_, i64>("COLUMN_OID")?, row.get::<_, i64>("ORDERING")?)))? {
-                    let (column_oid, ordering) = row_result?;
-                    let column: column::FullMetadata = column::FullMetadata::get_transact(trans, column_oid)?;
-                    let json_safe_column_name: String = json_encode_string(&column.name);
-                            
-                    let param = self.add_concrete_parameter(trans, object_datasource.clone(), column, SelectParameterContext::Scalar)?;
-                    key_columns.push((json_safe_column_name, param, ordering));
-                }
-                key_columns.sort_by_key(|(_, _, ordering)| *ordering);
+        // Add datasource for each inheritor table
+        for row_result in trans.prepare("SELECT INHERITOR_DATASOURCE_PATH FROM METADATA_SCHEMA_INHERITANCE_PATH_VIEW WHERE MASTER_SCHEMA_OID = ?1")?.query_map(params![object_table_oid], |row| row.get("INHERITOR_DATASOURCE_PATH"))? {
+            let inheritor_datasource_path: String = row_result?;
+            self.add_datasource(
+                datasource.datasource.append_path(inheritor_datasource_path)?, 
+                is_collection
+            );
+        }
 
-                // Construct the Object label
-                return Ok((
-                    // Non-recursive plain label
-                    if key_columns.len() == 1 {
-                        key_columns.iter()
-                            .map(|(_, param, _)| param.plain_label_expr_norecursion.clone())
-                            .next()
-                            .unwrap()
-                    } else {
-                        String::from("NULL")
-                    },
+        // Construct labels for each key column on table referenced by Object, including non-required columns
+        let mut key_columns: Vec<(String, SelectParameter, i64)> = Vec::new();
+        for (column_oid, ordering) in label_columns.into_iter() {
+            let column: column::FullMetadata = column::FullMetadata::get_transact(trans, column_oid)?;
+            let json_safe_column_name: String = json_encode_string(&column.name);
+                    
+            let param = self.add_concrete_parameter(trans, object_datasource.clone(), column, SelectParameterContext::Scalar)?;
+            key_columns.push((json_safe_column_name, param, ordering));
+        }
+        key_columns.sort_by_key(|(_, _, ordering)| *ordering);
+
+        // Construct the Object label
+        return Ok((
+            // Non-recursive plain label
+            if key_columns.len() == 1 {
+                key_columns.iter()
+                    .map(|(_, param, _)| param.plain_label_expr_norecursion.clone())
+                    .next()
+                    .unwrap()
+            } else {
+                String::from("NULL")
+            },
 
-                    // Recursive plain label
-                    if key_columns.len() == 1 {
-                        key_columns.iter()
-                            .map(|(_, param, _)| param.plain_label_expr_recursion.clone())
-                            .next()
-                            .unwrap()
-                    } else {
-                        String::from("NULL")
-                    },
-                            
-                    // Non-recursive JSON label
-                    if key_columns.len() == 1 {
-                        format!(
-                            "
-                            COALESCE({}, 'null') 
-                            ",
+            // Recursive plain label
+            if key_columns.len() == 1 {
+                key_columns.iter()
+                    .map(|(_, param, _)| param.plain_label_expr_recursion.clone())
+                    .next()
+                    .unwrap()
+            } else {
+                String::from("NULL")
+            },
+                    
+            // Non-recursive JSON label
+            if key_columns.len() == 1 {
+                format!(
+                    "
+                    COALESCE({}, 'null') 
+                    ",
 
-                            // The key columns of the schema
-                            key_columns.iter()
-                                .map(|(_, param, _)| param.json_label_expr_norecursion.clone())
-                                .next()
-                                .unwrap()
-                        )
-                    } else {
-                        format!(
-                            "
-                            COALESCE('{{ ' || GROUP_CONCAT(({}), ', ') || ' }}', '{{ }}') 
-                            ",
+                    // The key columns of the schema
+                    key_columns.iter()
+                        .map(|(_, param, _)| param.json_label_expr_norecursion.clone())
+                        .next()
+                        .unwrap()
+                )
+            } else {
+                format!(
+                    "
+                    COALESCE('{{ ' || GROUP_CONCAT(({}), ', ') || ' }}', '{{ }}') 
+                    ",
 
-                            // The key columns of the schema
-                            key_columns.iter()
-                                .map(|(json_safe_column_name, param, _)| format!("SELECT '\"{json_safe_column_name}\": ' || {}", param.json_label_expr_norecursion))
-                                .reduce(|acc, e| format!("{acc} UNION ALL {e}"))
-                                .unwrap()
-                        )
-                    },
+                    // The key columns of the schema
+                    key_columns.iter()
+                        .map(|(json_safe_column_name, param, _)| format!("SELECT '\"{json_safe_column_name}\": ' || {}", param.json_label_expr_norecursion))
+                        .reduce(|acc, e| format!("{acc} UNION ALL {e}"))
+                        .unwrap()
+                )
+            },
 
-                    // Recursive JSON label
-                    if key_columns.len() == 1 {
-                        format!(
-                            "
-                            COALESCE({}, 'null') 
-                            ",
+            // Recursive JSON label
+            if key_columns.len() == 1 {
+                format!(
+                    "
+                    COALESCE({}, 'null') 
+                    ",
 
-                            // The key columns of the schema
-                            key_columns.iter()
-                                .map(|(_, param, _)| param.json_label_expr_recursion.clone())
-                                .next()
-                                .unwrap()
-                        )
-                    } else {
-                        format!(
-                            "
-                            COALESCE('{{ ' || GROUP_CONCAT(({}), ', ') || ' }}', '{{ }}') 
-                            ",
+                    // The key columns of the schema
+                    key_columns.iter()
+                        .map(|(_, param, _)| param.json_label_expr_recursion.clone())
+                        .next()
+                        .unwrap()
+                )
+            } else {
+                format!(
+                    "
+                    COALESCE('{{ ' || GROUP_CONCAT(({}), ', ') || ' }}', '{{ }}') 
+                    ",
 
-                            // The key columns of the schema
-                            key_columns.iter()
-                                .map(|(json_safe_column_name, param, _)| format!("SELECT '\"{json_safe_column_name}\": ' || {}", param.json_label_expr_recursion))
-                                .reduce(|acc, e| format!("{acc} UNION ALL {e}"))
-                                .unwrap()
-                        )
-                    }
-                ));
+                    // The key columns of the schema
+                    key_columns.iter()
+                        .map(|(json_safe_column_name, param, _)| format!("SELECT '\"{json_safe_column_name}\": ' || {}", param.json_label_expr_recursion))
+                        .reduce(|acc, e| format!("{acc} UNION ALL {e}"))
+                        .unwrap()
+                )
             }
-        }
+        ));
     }
 
     /// Constructs the SQL expression corresponding to a Formula object.
@@ -4476,10 +4643,28 @@ fn drop_views(
 
 /// Create the views associated with a schema.
 pub fn regenerate_schema_views(trans: &Transaction, schema_oid: i64) -> Result<(), Error> {
+    // Tables in bulk mode have their views regenerated once bulk mode ends
+    {
+        let mut bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+        if let Some(bulk_mode_table) = bulk_mode_tables.iter_mut().find(|bulk_mode_table| bulk_mode_table.table_oid == schema_oid) {
+            bulk_mode_table.has_stale_views = true;
+            return Ok(());
+        }
+    }
+    regenerate_schema_views_now(trans, schema_oid)
+}
+
+/// Create the views associated with a schema, even if the schema is a table in bulk mode.
+fn regenerate_schema_views_now(trans: &Transaction, schema_oid: i64) -> Result<(), Error> {
     // Drop existing views that are dependent on these
     let mut views_to_create: HashMap<i64, ViewsToCreate> = HashMap::new();
     drop_views(trans, schema_oid, true, true, &mut views_to_create)?;
 
+    create_views(trans, &views_to_create)
+}
+
+/// Create the views that were dropped by drop_views.
+fn create_views(trans: &Transaction, views_to_create: &HashMap<i64, ViewsToCreate>) -> Result<(), Error> {
     // Create all of the label views
     for (view_schema_oid, view_to_create) in views_to_create.iter() {
         if view_to_create.create_label_view {
@@ -4488,7 +4673,6 @@ pub fn regenerate_schema_views(trans: &Transaction, schema_oid: i64) -> Result<(
                 "CREATE VIEW SCHEMA{view_schema_oid}_LABEL_VIEW AS {}",
                 select_constructor.build(trans)?
             );
-            println!("{sql_create}");
             trans.execute(&sql_create, [])?;
         }
     }
@@ -4501,9 +4685,313 @@ pub fn regenerate_schema_views(trans: &Transaction, schema_oid: i64) -> Result<(
                 "CREATE VIEW SCHEMA{view_schema_oid}_VIEW AS {}",
                 select_constructor.build(trans)?
             );
-            println!("{sql_create}");
             trans.execute(&sql_create, [])?;
         }
     }
     Ok(())
 }
+
+/// Recreate the views of every schema that has not been trashed, repairing any views that have fallen out of sync with the metadata.
+pub fn rebuild_all_schema_views() -> Result<(), Error> {
+    let mut conn = db::open()?;
+    let trans = conn.transaction()?;
+
+    // Drop the views of every schema, collecting the views that need to be recreated
+    let mut views_to_create: HashMap<i64, ViewsToCreate> = HashMap::new();
+    let schema_oids: Vec<i64> = trans
+        .prepare("SELECT OID FROM METADATA_SCHEMA WHERE NOT TRASH")?
+        .query_map([], |row| row.get::<_, i64>("OID"))?
+        .collect::<Result<Vec<i64>, _>>()?;
+    for schema_oid in schema_oids {
+        drop_views(&trans, schema_oid, true, true, &mut views_to_create)?;
+    }
+
+    create_views(&trans, &views_to_create)?;
+    trans.commit()?;
+    Ok(())
+}
+
+/// A table in bulk mode, with the work that has been deferred until bulk mode ends.
+struct BulkModeTable {
+    table_oid: i64,
+
+    /// True if the columns of the table changed, so its views need to be regenerated.
+    has_stale_views: bool,
+
+    /// True if the contents of the table changed without a signal to update the table being sent.
+    has_unsignalled_changes: bool,
+}
+
+/// The tables in bulk mode.
+static BULK_MODE_TABLES: Mutex<Vec<BulkModeTable>> = Mutex::new(Vec::new());
+
+/// Puts a table into bulk mode, which defers the work otherwise repeated for every row inserted or edited:
+/// the views of the table are not regenerated when its columns change, signals to update the table are not sent,
+/// and values written to the table are not checked for case-insensitive duplicates.
+/// Until bulk mode is ended, the views (and so the displayed values and references) of the table and of the schemas depending on it may be out of date.
+/// Data tables have no indexes of their own, so no indexes are dropped.
+pub fn begin_bulk_mode(table_oid: i64) {
+    let mut bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+    if !bulk_mode_tables.iter().any(|bulk_mode_table| bulk_mode_table.table_oid == table_oid) {
+        bulk_mode_tables.push(BulkModeTable {
+            table_oid,
+            has_stale_views: false,
+            has_unsignalled_changes: false,
+        });
+    }
+}
+
+/// Checks whether a table is in bulk mode.
+pub fn is_in_bulk_mode(table_oid: i64) -> bool {
+    let bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+    bulk_mode_tables.iter().any(|bulk_mode_table| bulk_mode_table.table_oid == table_oid)
+}
+
+/// Removes the tables in bulk mode from the schemas that a signal to update is about to be sent for, noting that each has a signal pending.
+/// Returns the schemas that the signal should still be sent for.
+pub fn defer_bulk_mode_signals(schema_oids: Vec<i64>) -> Vec<i64> {
+    let mut bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+    schema_oids
+        .into_iter()
+        .filter(|schema_oid| match bulk_mode_tables.iter_mut().find(|bulk_mode_table| bulk_mode_table.table_oid == *schema_oid) {
+            Some(bulk_mode_table) => {
+                bulk_mode_table.has_unsignalled_changes = true;
+                false
+            }
+            None => true,
+        })
+        .collect()
+}
+
+/// Takes a table out of bulk mode, regenerating its views once if any changes to them were deferred.
+/// Returns true if the table changed while in bulk mode, so that a signal to update it should be sent.
+pub fn end_bulk_mode(table_oid: i64) -> Result<bool, Error> {
+    let bulk_mode_table: BulkModeTable = {
+        let mut bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+        let Some(index) = bulk_mode_tables.iter().position(|bulk_mode_table| bulk_mode_table.table_oid == table_oid) else {
+            return Err(Error::AdhocError("The table is not in bulk mode."));
+        };
+        bulk_mode_tables.remove(index)
+    };
+    if bulk_mode_table.has_stale_views {
+        let mut conn = db::open()?;
+        let trans = conn.transaction()?;
+        regenerate_schema_views(&trans, table_oid)?;
+        trans.commit()?;
+    }
+    Ok(bulk_mode_table.has_stale_views || bulk_mode_table.has_unsignalled_changes)
+}
+
+/// Takes every table out of bulk mode without regenerating any views, for when a different database is opened.
+pub fn clear_bulk_mode() {
+    let mut bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+    *bulk_mode_tables = Vec::new();
+}
+
+/// The outcome of repairing the views of a single schema.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaViewRepair {
+    /// The error raised when selecting from the views before they were repaired, if any.
+    pub previous_error: Option<String>,
+
+    /// The names of columns whose data no longer exists in the database, which prevent the views from being recreated.
+    pub unresolved_columns: Vec<String>,
+
+    /// True if the views were recreated.
+    pub repaired: bool,
+}
+
+/// Drops and recreates the views of a single schema, along with the views that depend on them.
+/// Columns whose data no longer exists are reported instead, since the views cannot be recreated until they are removed.
+pub fn repair_schema_views(schema_oid: i64) -> Result<SchemaViewRepair, Error> {
+    let mut conn = db::open()?;
+    let trans = conn.transaction()?;
+
+    // Check whether the current views can be selected from
+    let mut previous_error: Option<String> = None;
+    for view_name in [format!("SCHEMA{schema_oid}_LABEL_VIEW"), format!("SCHEMA{schema_oid}_VIEW")] {
+        if let Err(e) = trans.prepare(&format!("SELECT * FROM {view_name} LIMIT 0")) {
+            previous_error = Some(format!("{view_name}: {e}"));
+            break;
+        }
+    }
+
+    // Find the columns of the schema whose data is missing
+    let mut unresolved_columns: Vec<String> = Vec::new();
+    let column_oids: Vec<i64> = trans
+        .prepare("SELECT OID FROM METADATA_COLUMN_VIEW WHERE SCHEMA_OID = ?1")?
+        .query_map(params![schema_oid], |row| row.get::<_, i64>("OID"))?
+        .collect::<Result<Vec<i64>, _>>()?;
+    for column_oid in column_oids {
+        let column: column::FullMetadata = column::FullMetadata::get_transact(&trans, column_oid)?;
+        let is_resolved: bool = match &column.column_type {
+            column_type::ColumnType::Primitive(column_type::Primitive::FileList) => trans.query_one(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_schema WHERE type = 'table' AND name = ?1)",
+                params![format!("FILELIST{column_oid}")],
+                |row| row.get(0),
+            )?,
+            column_type::ColumnType::Multiselect { .. } => trans.query_one(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_schema WHERE type = 'table' AND name = ?1)",
+                params![format!("MULTISELECT{column_oid}")],
+                |row| row.get(0),
+            )?,
+            column_type::ColumnType::Primitive(column_type::Primitive::RowNumber) => true, // Row numbers have no data of their own
+            column_type::ColumnType::Primitive(_)
+            | column_type::ColumnType::Object { .. }
+            | column_type::ColumnType::Select { .. } => trans.query_one(
+                "SELECT EXISTS (SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+                params![format!("TABLE{schema_oid}"), format!("COLUMN{column_oid}")],
+                |row| row.get(0),
+            )?,
+            _ => true, // Virtual columns have no data of their own
+        };
+        if !is_resolved {
+            unresolved_columns.push(column.name);
+        }
+    }
+    if unresolved_columns.len() > 0 {
+        return Ok(SchemaViewRepair {
+            previous_error,
+            unresolved_columns,
+            repaired: false,
+        });
+    }
+
+    // Recreate the views, including those of a table in bulk mode, which are then no longer stale
+    regenerate_schema_views_now(&trans, schema_oid)?;
+    trans.commit()?;
+    {
+        let mut bulk_mode_tables = BULK_MODE_TABLES.lock().unwrap();
+        if let Some(bulk_mode_table) = bulk_mode_tables.iter_mut().find(|bulk_mode_table| bulk_mode_table.table_oid == schema_oid) {
+            bulk_mode_table.has_stale_views = false;
+        }
+    }
+    Ok(SchemaViewRepair {
+        previous_error,
+        unresolved_columns,
+        repaired: true,
+    })
+}
+
+/// The SQL definitions of the views associated with a schema.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaViewSql {
+    pub main_view_sql: Option<String>,
+    pub label_view_sql: Option<String>,
+}
+
+/// Gets the current SQL definitions of the views associated with a schema, for debugging how its rows are displayed.
+pub fn get_schema_view_sql(schema_oid: i64) -> Result<SchemaViewSql, Error> {
+    let conn = db::open()?;
+    let get_view_sql = |view_name: String| {
+        conn.query_one(
+            "SELECT sql FROM sqlite_schema WHERE type = 'view' AND name = ?1",
+            params![view_name],
+            |row| row.get::<_, String>("sql"),
+        )
+        .optional()
+    };
+    Ok(SchemaViewSql {
+        main_view_sql: get_view_sql(format!("SCHEMA{schema_oid}_VIEW"))?,
+        label_view_sql: get_view_sql(format!("SCHEMA{schema_oid}_LABEL_VIEW"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::cell::{DataCellEntry, DataCellValue};
+    use crate::data::{column, column_type, testing};
+
+    #[test]
+    fn self_referencing_select_columns_display_a_chain_of_rows() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let mut name_column = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        name_column.is_primary_key = true;
+        name_column.create().unwrap();
+        let parent_column_oid: i64 = testing::create_column(table_oid, "Parent", column_type::ColumnType::Select { oid: 0, table_oid });
+
+        // Build the chain Root <- Branch <- Leaf
+        let mut previous_row_oid: Option<i64> = None;
+        let mut row_oids: Vec<i64> = Vec::new();
+        for name in ["Root", "Branch", "Leaf"] {
+            let row_oid: i64 = testing::insert_row(table_oid);
+            testing::set_cell(table_oid, name_column.oid, row_oid, DataCellValue::Text(Some(String::from(name))));
+            testing::set_cell(table_oid, parent_column_oid, row_oid, DataCellValue::Select { linked_row_oid: previous_row_oid });
+            previous_row_oid = Some(row_oid);
+            row_oids.push(row_oid);
+        }
+
+        let parent_labels: String = testing::query_one(&format!(
+            "SELECT GROUP_CONCAT(COALESCE(COLUMN{parent_column_oid}_LABEL, ''), '|') FROM (SELECT * FROM SCHEMA{table_oid}_VIEW ORDER BY OID)"
+        ));
+        assert_eq!(parent_labels, "|Root|Branch");
+        let parent_value: i64 = testing::query_one(&format!("SELECT COLUMN{parent_column_oid}_VALUE FROM SCHEMA{table_oid}_VIEW WHERE OID = {}", row_oids[2]));
+        assert_eq!(parent_value, row_oids[1]);
+    }
+
+    #[test]
+    fn self_referencing_key_columns_stop_recursing_in_labels() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let mut name_column = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        name_column.is_primary_key = true;
+        name_column.create().unwrap();
+        let mut parent_column = testing::column_metadata(table_oid, "Parent", column_type::ColumnType::Select { oid: 0, table_oid });
+        parent_column.is_primary_key = true;
+        parent_column.is_nullable = true;
+        parent_column.create().unwrap();
+
+        let root_row_oid: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, name_column.oid, root_row_oid, DataCellValue::Text(Some(String::from("Root"))));
+        let leaf_row_oid: i64 = testing::insert_row(table_oid);
+        testing::set_cell(table_oid, name_column.oid, leaf_row_oid, DataCellValue::Text(Some(String::from("Leaf"))));
+        testing::set_cell(table_oid, parent_column.oid, leaf_row_oid, DataCellValue::Select { linked_row_oid: Some(root_row_oid) });
+
+        // The label of the parent is built from its own key columns, without following its parent again
+        let parent_label: String = testing::query_one(&format!("SELECT COLUMN{}_LABEL FROM SCHEMA{table_oid}_VIEW WHERE OID = {leaf_row_oid}", parent_column.oid));
+        assert!(parent_label.contains("Root"));
+    }
+
+    #[test]
+    fn bulk_mode_defers_duplicate_checks_until_it_ends() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        let mut name_column = testing::column_metadata(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        name_column.case_insensitive_unique = true;
+        name_column.create().unwrap();
+
+        super::begin_bulk_mode(table_oid);
+        let row_oids: Vec<i64> = (0..2).map(|_| testing::insert_row(table_oid)).collect();
+        testing::set_cell(table_oid, name_column.oid, row_oids[0], DataCellValue::Text(Some(String::from("Goblin"))));
+        testing::set_cell(table_oid, name_column.oid, row_oids[1], DataCellValue::Text(Some(String::from("GOBLIN"))));
+        assert_eq!(column::find_invalid_cells(Some(table_oid)).unwrap().len(), 2);
+        assert!(!super::end_bulk_mode(table_oid).unwrap());
+
+        // Once bulk mode has ended, duplicates are rejected as they are written
+        let row_oid: i64 = testing::insert_row(table_oid);
+        let cell = DataCellEntry::new(table_oid, name_column.oid, row_oid, DataCellValue::Text(Some(String::from("goblin"))));
+        assert!(cell.set().is_err());
+    }
+
+    #[test]
+    fn repairing_the_views_of_a_table_in_bulk_mode_recreates_them() {
+        let _db = testing::open_database();
+        let table_oid: i64 = testing::create_table("Item", &[]);
+        super::begin_bulk_mode(table_oid);
+        let column_oid: i64 = testing::create_column(table_oid, "Name", column_type::ColumnType::Primitive(column_type::Primitive::PlainText));
+        let view_has_column = || -> bool {
+            testing::query_one(&format!("SELECT EXISTS (SELECT 1 FROM pragma_table_info('SCHEMA{table_oid}_VIEW') WHERE name = 'COLUMN{column_oid}_VALUE')"))
+        };
+        assert!(!view_has_column());
+
+        let repair: super::SchemaViewRepair = super::repair_schema_views(table_oid).unwrap();
+        assert!(repair.repaired);
+        assert!(view_has_column());
+
+        // The views are no longer stale, so ending bulk mode has nothing to regenerate
+        assert!(!super::end_bulk_mode(table_oid).unwrap());
+    }
+}
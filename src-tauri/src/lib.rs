@@ -14,22 +14,94 @@ pub fn run() {
             data::save,
             data::save_as,
             data::load,
+            data::get_undo_history,
             data::undo,
             data::redo,
+            data::export_action_log,
+            data::create_checkpoint,
+            data::rollback_to_checkpoint,
+            data::discard_session,
+            data::set_max_base64_file_size,
+            data::set_max_object_nesting_depth,
+            data::set_validation_enabled,
+            data::begin_bulk_mode,
+            data::end_bulk_mode,
+            data::list_checkpoints,
+            data::checkpoint_wal,
+            data::probe_database,
             util::dialog::dialog_open,
             util::dialog::dialog_close,
             data::query,
             data::get_table_metadata,
+            data::set_table_page_size,
+            data::find_reports_using_table,
+            data::get_all_table_metadata,
+            data::get_table_storage_stats,
+            data::find_degenerate_columns,
+            data::get_table_checksum,
             data::get_report_metadata,
             data::get_schema_metadata,
             data::get_column,
+            data::get_column_max_display_length,
+            data::get_column_default,
+            data::get_column_sources,
+            data::get_column_reference_target,
+            data::get_column_styles,
+            data::normalize_column_ordering,
+            data::get_column_format_rules,
+            data::add_column_format_rule,
+            data::remove_column_format_rule,
+            data::count_dropdown_value_usage,
+            data::rename_dropdown_value,
+            data::get_schema_view_sql,
+            data::rebuild_all_schema_views,
+            data::repair_schema_views,
+            data::validate_formula_columns,
+            data::get_object_nesting,
+            data::create_table_group,
+            data::rename_table_group,
+            data::delete_table_group,
+            data::assign_table_group,
+            data::get_column_validations,
+            data::parse_integer_cell_value,
+            data::find_columns_of_type,
             data::get_cell,
+            data::get_cell_true_value,
+            data::get_row_timestamps,
+            data::get_changed_rows,
+            data::find_duplicate_rows,
+            data::get_subtype_counts,
+            data::get_retype_options,
+            data::get_reference_display,
+            data::get_reference_key_parts,
+            data::get_next_oid,
+            data::get_oid_range,
+            data::import_table_json,
+            data::sniff_csv,
+            data::export_report,
+            data::export_table_ndjson,
+            data::export_row,
             data::get_processid,
+            data::cancel_import,
             data::get_table_row_labels,
             data::get_image_src,
             data::download_file,
+            data::get_file_size,
             data::upload_file,
-            data::execute
+            data::execute,
+            data::execute_dry_run,
+            data::set_autosave_action_interval,
+            data::set_trash_retention_days,
+            data::purge_expired_trash,
+            data::copy_dropdown_values,
+            data::clone_table_schema,
+            data::set_column_not_null,
+            data::reassign_references,
+            data::set_reference_by_display,
+            data::reorder_multiselect_selection,
+            data::list_cell_files,
+            data::add_file_to_cell,
+            data::remove_file_from_cell
         ])
         .on_window_event(|window, event| {
             match event {
@@ -1,5 +1,6 @@
 use crate::util::error;
-use rusqlite::{Connection, Result};
+use rusqlite::{Connection, MAIN_DB, OpenFlags, Result};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 use std::sync::Mutex;
@@ -8,21 +9,121 @@ use tempfile::NamedTempFile;
 
 static DATABASE_PATH: Mutex<Option<String>> = Mutex::new(None);
 static DATABASE_AUTOSAVE_PATH: Mutex<Option<NamedTempFile>> = Mutex::new(None);
+static CHECKPOINTS: Mutex<Vec<Checkpoint>> = Mutex::new(Vec::new());
+static JOURNAL_MODE: Mutex<JournalMode> = Mutex::new(JournalMode::Wal);
+
+/// The version of the metadata schema applied by this build, recorded in the user_version of every file it opens.
+/// Files created before the schema was versioned have a version of 0.
+const SCHEMA_VERSION: i64 = 2;
+
+/// How SQLite journals changes to the database before they are committed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum JournalMode {
+    /// Changes are appended to a separate write-ahead log, and folded into the database at checkpoints.
+    /// This is faster and lets reads proceed during writes, but requires shared memory between connections,
+    /// which is unreliable on network filesystems, and leaves -wal and -shm files next to the database while it is open.
+    #[default]
+    Wal,
+
+    /// The original contents of changed pages are copied to a rollback journal, which is deleted when the transaction commits.
+    /// This is slower, but works on any filesystem and keeps the database contained in a single file.
+    Delete,
+}
 
-/// Applies the metadata schema to the database at the given path.
-fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
+impl JournalMode {
+    /// Gets the value of the journal_mode pragma for this mode.
+    fn to_pragma(&self) -> &'static str {
+        match self {
+            Self::Wal => "WAL",
+            Self::Delete => "DELETE",
+        }
+    }
+}
+
+/// Opens a connection to the database at the given path, with foreign keys enforced and the configured journal mode.
+fn connect<P: AsRef<Path>>(path: P) -> Result<Connection, error::Error> {
+    let journal_mode: JournalMode = *JOURNAL_MODE.lock().unwrap();
     let conn = Connection::open(path)?;
-    conn.execute_batch("
+    conn.execute_batch(&format!(
+        "
     PRAGMA foreign_keys = ON;
-    PRAGMA journal_mode = WAL;
+    PRAGMA journal_mode = {};
+    ",
+        journal_mode.to_pragma()
+    ))?;
+    Ok(conn)
+}
+
+/// Gets the journal mode used by connections to the database.
+pub fn get_journal_mode() -> JournalMode {
+    *JOURNAL_MODE.lock().unwrap()
+}
+
+/// A named snapshot of the database, which can be rolled back to.
+struct Checkpoint {
+    /// The name of the checkpoint.
+    name: String,
+
+    /// The number of undoable actions that had been performed when the checkpoint was created.
+    action_count: usize,
+
+    /// The file containing the snapshot of the database.
+    snapshot: NamedTempFile,
+}
 
+/// The outcome of folding the write-ahead log back into the database.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WalCheckpoint {
+    /// True if the checkpoint could not complete because another connection was using the database.
+    pub busy: bool,
+
+    /// The number of frames in the write-ahead log before it was truncated.
+    pub log_frames: i64,
+
+    /// The number of frames moved into the database.
+    pub checkpointed_frames: i64,
+}
+
+/// Applies the metadata schema to the database at the given path.
+fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
+    let conn = connect(path)?;
+
+    // Files created before these metadata columns were added do not have them yet.
+    // They are added before the schema below is applied, since the views it rebuilds select them.
+    for (table_name, column_name, column_definition) in [
+        ("METADATA_SCHEMA", "DISPLAY_ORDER", "INTEGER"),
+        ("METADATA_TABLE", "TRACK_TIMESTAMPS", "BOOLEAN NOT NULL DEFAULT FALSE"),
+        ("METADATA_TABLE", "GROUP_OID", "INTEGER REFERENCES METADATA_TABLE_GROUP (OID) ON UPDATE CASCADE ON DELETE SET NULL"),
+        ("METADATA_TABLE", "DEFAULT_PAGE_SIZE", "INTEGER"),
+        ("METADATA_TABLE", "ROW_VERSION", "INTEGER NOT NULL DEFAULT 0"),
+        ("METADATA_COLUMN", "CASE_INSENSITIVE_UNIQUE", "BOOLEAN NOT NULL DEFAULT FALSE"),
+        ("METADATA_COLUMN", "PRETTY_PRINT_JSON", "BOOLEAN NOT NULL DEFAULT FALSE"),
+        ("METADATA_COLUMN", "EMPTY_AS_NULL", "BOOLEAN NOT NULL DEFAULT TRUE"),
+        ("METADATA_COLUMN", "REFERENCE_ON_DELETE", "TEXT NOT NULL DEFAULT 'SET NULL' CHECK (REFERENCE_ON_DELETE IN ('SET NULL', 'CASCADE', 'RESTRICT'))"),
+        ("METADATA_COLUMN", "REFERENCE_SUBTYPE_FILTER", "INTEGER REFERENCES METADATA_TABLE (OID) ON UPDATE CASCADE ON DELETE SET NULL"),
+        ("METADATA_COLUMN", "INTEGER_FORMAT", "TEXT NOT NULL DEFAULT 'PLAIN'"),
+        ("METADATA_COLUMN", "REFERENCE_DISPLAY_COLUMN_OID", "INTEGER REFERENCES METADATA_COLUMN (OID) ON UPDATE CASCADE ON DELETE SET NULL"),
+        ("METADATA_SCHEMA_ORDERBY", "NULLS_LAST", "BOOLEAN"),
+    ] {
+        if conn.table_exists(Some("main"), table_name)? && !conn.column_exists(Some("main"), table_name, column_name)? {
+            let sql_add_column: String = format!("ALTER TABLE {table_name} ADD COLUMN {column_name} {column_definition}");
+            conn.execute(&sql_add_column, [])?;
+        }
+    }
+
+    conn.execute_batch("
     BEGIN;
 
     -- METADATA_SCHEMA is associated with all column definitions.
     CREATE TABLE IF NOT EXISTS METADATA_SCHEMA (
         OID INTEGER PRIMARY KEY,
         TRASH BOOLEAN NOT NULL DEFAULT FALSE,
-        NAME TEXT NOT NULL
+        NAME TEXT NOT NULL,
+        DISPLAY_ORDER INTEGER
+            -- The user-defined ordering of the schema among its siblings in hierarchical lists
+            -- If NULL, the schema is ordered by name after all schemas with a defined ordering
     );
 
     -- METADATA_SCHEMA_INHERITANCE records the inheritance of columns between tables.
@@ -98,12 +199,29 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
     );
     CREATE INDEX IF NOT EXISTS METADATA_SCHEMA_VALIDATION_INDEX_BY_SCHEMA_OID ON METADATA_SCHEMA_VALIDATION (SCHEMA_OID);
     
+    -- METADATA_TABLE_GROUP stores the folders that tables are grouped into in the sidebar.
+    CREATE TABLE IF NOT EXISTS METADATA_TABLE_GROUP (
+        OID INTEGER PRIMARY KEY,
+        NAME TEXT NOT NULL,
+        ORDERING INTEGER NOT NULL DEFAULT 0
+    );
+
     -- METADATA_TABLE stores all user-defined schemas that store data.
     -- A table can additionally be associated with storage types.
     CREATE TABLE IF NOT EXISTS METADATA_TABLE (
         OID INTEGER PRIMARY KEY REFERENCES METADATA_SCHEMA (OID) 
             ON UPDATE CASCADE
-            ON DELETE CASCADE
+            ON DELETE CASCADE,
+        -- If true, the table records the time at which each row was created and last modified.
+        TRACK_TIMESTAMPS BOOLEAN NOT NULL DEFAULT FALSE,
+        -- The folder that the table is displayed in, if any.
+        GROUP_OID INTEGER REFERENCES METADATA_TABLE_GROUP (OID)
+            ON UPDATE CASCADE
+            ON DELETE SET NULL,
+        -- The number of rows displayed per page when the table is opened, or NULL to use the application default.
+        DEFAULT_PAGE_SIZE INTEGER,
+        -- The version given to the most recently changed row of the table.
+        ROW_VERSION INTEGER NOT NULL DEFAULT 0
     );
 
     -- METADATA_REPORT stores all user-defined schemas that do not store data, but rather pull data from one or more tables (and/or array literals?).
@@ -311,12 +429,32 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
             -- The ordering of columns as displayed in the table
         IS_NULLABLE BOOLEAN NOT NULL DEFAULT TRUE,
         IS_PRIMARY_KEY BOOLEAN NOT NULL DEFAULT FALSE,
-        DEFAULT_VALUE TEXT
+        CASE_INSENSITIVE_UNIQUE BOOLEAN NOT NULL DEFAULT FALSE,
+            -- Whether text values in the column must be unique, ignoring case
+        PRETTY_PRINT_JSON BOOLEAN NOT NULL DEFAULT FALSE,
+            -- Whether JSON values in the column are validated and displayed with consistent indentation
+        EMPTY_AS_NULL BOOLEAN NOT NULL DEFAULT TRUE,
+            -- Whether empty text values written to the column are stored as NULL
+        REFERENCE_ON_DELETE TEXT NOT NULL DEFAULT 'SET NULL' CHECK (REFERENCE_ON_DELETE IN ('SET NULL', 'CASCADE', 'RESTRICT')),
+            -- What happens to a Select or Multiselect column when the row it references is trashed
+        REFERENCE_SUBTYPE_FILTER INTEGER REFERENCES METADATA_TABLE (OID)
+            ON UPDATE CASCADE
+            ON DELETE SET NULL,
+            -- If set, a Select or Multiselect column may only reference rows of this inheritor of the referenced table
+        INTEGER_FORMAT TEXT NOT NULL DEFAULT 'PLAIN',
+            -- How the values of an Integer column are displayed (PLAIN, THOUSANDS, HEX, or ZEROPADDED followed by the width)
+        DEFAULT_VALUE TEXT,
+        REFERENCE_DISPLAY_COLUMN_OID INTEGER REFERENCES METADATA_COLUMN (OID)
+            ON UPDATE CASCADE
+            ON DELETE SET NULL
+            -- If set, an Object, Select, or Multiselect column displays this column of the referenced row instead of its primary key
     );
     CREATE INDEX IF NOT EXISTS METADATA_COLUMN_INDEX_BY_SCHEMA_OID ON METADATA_COLUMN (SCHEMA_OID);
 
     -- METADATA_COLUMN_VIEW filters out all trashed columns.
-    CREATE VIEW IF NOT EXISTS METADATA_COLUMN_VIEW AS 
+    -- It is rebuilt every time, so that files created with an older definition select the current set of columns.
+    DROP VIEW IF EXISTS METADATA_COLUMN_VIEW;
+    CREATE VIEW METADATA_COLUMN_VIEW AS 
         SELECT 
             c.OID,
             c.HIDDEN,
@@ -327,6 +465,12 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
             c.ORDERING,
             c.IS_NULLABLE,
             c.IS_PRIMARY_KEY,
+            c.CASE_INSENSITIVE_UNIQUE,
+            c.PRETTY_PRINT_JSON,
+            c.EMPTY_AS_NULL,
+            c.REFERENCE_ON_DELETE,
+            c.REFERENCE_SUBTYPE_FILTER,
+            c.INTEGER_FORMAT,
             c.DEFAULT_VALUE,
             (ct.TYPE IS 'Subreport') AS IS_SUBREPORT
         FROM METADATA_COLUMN c 
@@ -374,6 +518,19 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
         INNER JOIN METADATA_COLUMN_VIEW c ON c.SCHEMA_OID = inh.INHERITOR_SCHEMA_OID
     ;
 
+    -- METADATA_COLUMN_FORMAT_RULE stores the conditional formatting rules of each column.
+    CREATE TABLE IF NOT EXISTS METADATA_COLUMN_FORMAT_RULE (
+        OID INTEGER PRIMARY KEY,
+        COLUMN_OID INTEGER NOT NULL REFERENCES METADATA_COLUMN (OID)
+            ON UPDATE CASCADE
+            ON DELETE CASCADE,
+        CONDITION TEXT NOT NULL,
+            -- The condition on the value of a cell, serialized as JSON
+        STYLE TEXT NOT NULL
+            -- CSS style applied to each cell that meets the condition
+    );
+    CREATE INDEX IF NOT EXISTS METADATA_COLUMN_FORMAT_RULE_INDEX_BY_COLUMN_OID ON METADATA_COLUMN_FORMAT_RULE (COLUMN_OID);
+
 
     -- METADATA_DATASOURCE stores root datasources for a schema.
     CREATE TABLE IF NOT EXISTS METADATA_DATASOURCE (
@@ -398,17 +555,23 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
         TRASH BOOLEAN NOT NULL DEFAULT FALSE,
         ORDERING INTEGER NOT NULL DEFAULT 0,
         SORT_ASCENDING BOOLEAN NOT NULL DEFAULT FALSE,
+        NULLS_LAST BOOLEAN,
+            -- Whether empty values are sorted after all other values
+            -- If NULL, empty values are sorted last when ascending and first when descending
         PRIMARY KEY (SCHEMA_OID, COLUMN_OID)
     );
 
     -- METADATA_SCHEMA_ORDERBY_VIEW is a view that filters out any trashed METADATA_SCHEMA_ORDERBY rows.
-    CREATE VIEW IF NOT EXISTS METADATA_SCHEMA_ORDERBY_VIEW AS
+    -- It is rebuilt every time, so that files created with an older definition select the current set of columns.
+    DROP VIEW IF EXISTS METADATA_SCHEMA_ORDERBY_VIEW;
+    CREATE VIEW METADATA_SCHEMA_ORDERBY_VIEW AS
         SELECT 
             so.SCHEMA_OID,
             sc.DATASOURCE_PATH,
             so.COLUMN_OID,
             so.ORDERING,
-            so.SORT_ASCENDING
+            so.SORT_ASCENDING,
+            COALESCE(so.NULLS_LAST, so.SORT_ASCENDING) AS NULLS_LAST
         FROM METADATA_SCHEMA_ORDERBY so 
         INNER JOIN METADATA_SCHEMA_COLUMN_VIEW sc 
             ON sc.SCHEMA_OID = so.SCHEMA_OID
@@ -444,6 +607,15 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
     
 
 
+    -- METADATA_ACTION_LOG records every action executed, undone, or redone through the undo history, in order, as JSON.
+    -- It is a history of those actions, and does not include changes made outside of actions.
+    CREATE TABLE IF NOT EXISTS METADATA_ACTION_LOG (
+        OID INTEGER PRIMARY KEY,
+        ACTION TEXT NOT NULL
+    );
+
+
+
     INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-1);
     INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-1, 'text');
     INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-2);
@@ -462,19 +634,115 @@ fn setup_db_at_path<P: AsRef<Path>>(path: P) -> Result<(), error::Error> {
     INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-8, 'image');
     INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-9);
     INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-9, 'JSON');
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-12);
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-12, 'percentage');
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-13);
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-13, 'longText');
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-14);
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-14, 'fileList');
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-15);
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-15, 'point');
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE (OID) VALUES (-16);
+    INSERT OR IGNORE INTO METADATA_COLUMN_TYPE__PRIMITIVE (OID, MODE) VALUES (-16, 'rowNumber');
 
     COMMIT;
     ")?;
+
+    // Multiselect columns created before selections were ordered do not have the ordering column yet
+    let multiselect_column_oids: Vec<i64> = conn
+        .prepare("SELECT c.OID FROM METADATA_COLUMN c INNER JOIN METADATA_COLUMN_TYPE__MULTISELECT typ ON typ.OID = c.TYPE_OID")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<i64>>>()?;
+    for column_oid in multiselect_column_oids {
+        let table_name: String = format!("MULTISELECT{column_oid}");
+        if conn.table_exists(Some("main"), &table_name)? && !conn.column_exists(Some("main"), &table_name, "ORDERING")? {
+            let sql_add_column: String = format!("ALTER TABLE {table_name} ADD COLUMN ORDERING INTEGER NOT NULL DEFAULT 0");
+            conn.execute(&sql_add_column, [])?;
+        }
+    }
+
+    // Tables created before rows were versioned, or before trashed rows were timestamped, do not have the columns yet
+    let table_oids: Vec<i64> = conn
+        .prepare("SELECT OID FROM METADATA_TABLE")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<i64>>>()?;
+    for table_oid in table_oids {
+        let table_name: String = format!("TABLE{table_oid}");
+        if !conn.table_exists(Some("main"), &table_name)? {
+            continue;
+        }
+        if !conn.column_exists(Some("main"), &table_name, "VERSION")? {
+            let sql_add_column: String = format!("ALTER TABLE {table_name} ADD COLUMN VERSION INTEGER NOT NULL DEFAULT 0");
+            conn.execute(&sql_add_column, [])?;
+        }
+        if !conn.column_exists(Some("main"), &table_name, "TRASHED_AT")? {
+            let sql_add_column: String = format!("ALTER TABLE {table_name} ADD COLUMN TRASHED_AT REAL");
+            conn.execute(&sql_add_column, [])?;
+        }
+
+        // Continue the version counter from the rows versioned before the counter was kept
+        let sql_update: String = format!(
+            "UPDATE METADATA_TABLE SET ROW_VERSION = (SELECT COALESCE(MAX(VERSION), 0) FROM {table_name}) WHERE OID = ?1 AND ROW_VERSION = 0"
+        );
+        conn.execute(&sql_update, [table_oid])?;
+    }
+
+    // Record that the file has been migrated to the current schema
+    conn.pragma_update(Some("main"), "user_version", SCHEMA_VERSION)?;
     return Ok(());
 }
 
+/// A summary of a database file, read without opening it as the current file.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseProbe {
+    /// The number of tables in the database, excluding those in the trash.
+    pub table_count: i64,
+
+    /// The version of the metadata schema that the file was last opened with, or 0 if it was last opened before the schema was versioned.
+    pub schema_version: i64,
+}
+
+/// Checks that a file is a DungeonDB database by opening a separate read-only connection to it.
+/// The currently open database is not affected.
+pub fn probe_database(path: String) -> Result<DatabaseProbe, error::Error> {
+    let Ok(conn) = Connection::open_with_flags(
+        &path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    ) else {
+        return Err(error::Error::AdhocError("Unable to open the file."));
+    };
+
+    // Every DungeonDB file records its tables in METADATA_TABLE
+    let is_dungeon_db: bool = match conn.table_exists(Some("main"), "METADATA_TABLE") {
+        Ok(exists) => exists,
+        Err(_) => false, // The file is not an SQLite database
+    };
+    if !is_dungeon_db {
+        return Err(error::Error::AdhocError("The file is not a DungeonDB database."));
+    }
+
+    let schema_version: i64 = conn.query_one("PRAGMA user_version", [], |row| row.get(0))?;
+    let table_count: i64 = conn.query_one(
+        "SELECT COUNT(*) FROM METADATA_TABLE t INNER JOIN METADATA_SCHEMA s ON s.OID = t.OID WHERE NOT s.TRASH",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(DatabaseProbe {
+        table_count,
+        schema_version,
+    })
+}
+
 /// Closes any previous database connection, and opens
-pub fn init_new() -> Result<(), error::Error> {
+pub fn init_new(journal_mode: JournalMode) -> Result<(), error::Error> {
     // Reset static variables
     let mut database_path = DATABASE_PATH.lock().unwrap();
     let mut database_autosave_tempfile = DATABASE_AUTOSAVE_PATH.lock().unwrap();
     *database_path = None;
     *database_autosave_tempfile = None;
+    CHECKPOINTS.lock().unwrap().clear();
+    *JOURNAL_MODE.lock().unwrap() = journal_mode;
 
     // Create new autosave file
     let Ok(tempfile) = NamedTempFile::new() else {
@@ -491,12 +759,14 @@ pub fn init_new() -> Result<(), error::Error> {
 }
 
 /// Closes any previous database connection, and opens a new one.
-pub fn init_existing(path: String) -> Result<(), error::Error> {
+pub fn init_existing(path: String, journal_mode: JournalMode) -> Result<(), error::Error> {
     // Reset static variables
     let mut database_path = DATABASE_PATH.lock().unwrap();
     let mut database_autosave_tempfile = DATABASE_AUTOSAVE_PATH.lock().unwrap();
     *database_path = None;
     *database_autosave_tempfile = None;
+    CHECKPOINTS.lock().unwrap().clear();
+    *JOURNAL_MODE.lock().unwrap() = journal_mode;
 
     // Make a new autosave file
     let Ok(tempfile) = NamedTempFile::new() else {
@@ -524,13 +794,7 @@ pub fn open() -> Result<Connection, error::Error> {
     let database_autosave_tempfile = DATABASE_AUTOSAVE_PATH.lock().unwrap();
     match *database_autosave_tempfile {
         Some(ref tempfile) => {
-            let conn = Connection::open(tempfile.path())?;
-            conn.execute_batch(
-                "
-            PRAGMA foreign_keys = ON;
-            PRAGMA journal_mode = WAL;
-            ",
-            )?;
+            let conn = connect(tempfile.path())?;
             rusqlite::vtab::array::load_module(&conn)?;
             return Ok(conn);
         }
@@ -540,6 +804,107 @@ pub fn open() -> Result<Connection, error::Error> {
     }
 }
 
+/// Copies the current state of the database into a temporary file.
+pub fn create_snapshot() -> Result<NamedTempFile, error::Error> {
+    let Ok(snapshot) = NamedTempFile::new() else {
+        return Err(error::Error::AdhocError("Unable to make a snapshot file."));
+    };
+    let conn = open()?;
+    conn.backup(MAIN_DB, snapshot.path(), None)?;
+    Ok(snapshot)
+}
+
+/// Overwrites the database with a snapshot made by create_snapshot.
+pub fn restore_snapshot(snapshot: &NamedTempFile) -> Result<(), error::Error> {
+    let mut conn = open()?;
+    conn.restore(MAIN_DB, snapshot.path(), None::<fn(rusqlite::backup::Progress)>)?;
+    Ok(())
+}
+
+/// Creates a named checkpoint holding a snapshot of the current state of the database.
+/// The number of undoable actions performed so far is recorded so that the undo history can be truncated on rollback.
+pub fn create_checkpoint(name: String, action_count: usize) -> Result<(), error::Error> {
+    let mut checkpoints = CHECKPOINTS.lock().unwrap();
+    if checkpoints.iter().any(|checkpoint| checkpoint.name == name) {
+        return Err(error::Error::AdhocError(
+            "A checkpoint with that name already exists.",
+        ));
+    }
+
+    // Copy the current state of the database into a snapshot
+    let snapshot: NamedTempFile = create_snapshot()?;
+
+    checkpoints.push(Checkpoint {
+        name,
+        action_count,
+        snapshot,
+    });
+    Ok(())
+}
+
+/// Restores the database to the state it was in when the named checkpoint was created.
+/// Any checkpoints created after the named checkpoint are discarded.
+/// Returns the number of undoable actions that had been performed when the checkpoint was created.
+pub fn rollback_to_checkpoint(name: String) -> Result<usize, error::Error> {
+    let mut checkpoints = CHECKPOINTS.lock().unwrap();
+    let Some(checkpoint_idx) = checkpoints
+        .iter()
+        .position(|checkpoint| checkpoint.name == name)
+    else {
+        return Err(error::Error::AdhocError("No checkpoint with that name exists."));
+    };
+
+    // Overwrite the database with the snapshot
+    restore_snapshot(&checkpoints[checkpoint_idx].snapshot)?;
+
+    // Discard all later checkpoints
+    checkpoints.truncate(checkpoint_idx + 1);
+    Ok(checkpoints[checkpoint_idx].action_count)
+}
+
+/// Restores the database to the state of the main file, discarding every change made since it was last saved.
+/// If the database has never been saved, it is restored to a new, empty database.
+pub fn discard_session() -> Result<(), error::Error> {
+    let database_path: Option<String> = DATABASE_PATH.lock().unwrap().clone();
+    let Some(path) = database_path else {
+        return init_new(get_journal_mode());
+    };
+
+    // Overwrite the autosave with the contents of the main file
+    {
+        let mut conn = open()?;
+        conn.restore(MAIN_DB, &path, None::<fn(rusqlite::backup::Progress)>)?;
+    }
+    if let Some(ref tempfile) = *DATABASE_AUTOSAVE_PATH.lock().unwrap() {
+        setup_db_at_path(tempfile.path())?;
+    }
+
+    // Checkpoints refer to changes that no longer exist
+    CHECKPOINTS.lock().unwrap().clear();
+    Ok(())
+}
+
+/// Moves all changes from the write-ahead log into the database, then truncates the log to free up space.
+pub fn checkpoint_wal() -> Result<WalCheckpoint, error::Error> {
+    let conn = open()?;
+    Ok(conn.query_one("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| {
+        Ok(WalCheckpoint {
+            busy: row.get::<_, i64>(0)? != 0,
+            log_frames: row.get(1)?,
+            checkpointed_frames: row.get(2)?,
+        })
+    })?)
+}
+
+/// Lists the names of all checkpoints, from oldest to newest.
+pub fn list_checkpoints() -> Vec<String> {
+    let checkpoints = CHECKPOINTS.lock().unwrap();
+    checkpoints
+        .iter()
+        .map(|checkpoint| checkpoint.name.clone())
+        .collect()
+}
+
 /// Copies the data from the autosave file to the main file, then open a connection to the main file for cleaning purposes.
 /// Returns false if the file was not saved due to the user cancelling the save prompt, and returns true otherwise.
 pub fn save_to_current_file(app: &AppHandle) -> Result<bool, error::Error> {
@@ -557,6 +922,19 @@ pub fn save_to_current_file(app: &AppHandle) -> Result<bool, error::Error> {
     save_to_prompted_file(app)
 }
 
+/// Copies the data from the autosave file to the main file, if there is one, without prompting for a file.
+/// Returns false if the database has never been saved to a main file, and returns true otherwise.
+pub fn save_to_existing_file(app: &AppHandle) -> Result<bool, error::Error> {
+    let database_path = DATABASE_PATH.lock().unwrap();
+    match *database_path {
+        Some(ref save_path) => {
+            save(app, save_path)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 /// Copies the data from the autosave file to a prompted main file, then open a connection to the main file for cleaning purposes.
 /// Returns false if the file was not saved due to the user cancelling the save prompt, and returns true otherwise.
 pub fn save_to_prompted_file(app: &AppHandle) -> Result<bool, error::Error> {
@@ -590,13 +968,7 @@ fn save(app: &AppHandle, save_path: &String) -> Result<(), error::Error> {
             };
 
             // Open connection to the main file
-            let mut conn = Connection::open(save_path)?;
-            conn.execute_batch(
-                "
-            PRAGMA foreign_keys = ON;
-            PRAGMA journal_mode = WAL;
-            ",
-            )?;
+            let mut conn = connect(save_path)?;
             rusqlite::vtab::array::load_module(&conn)?;
 
             // Start transaction to clean database
@@ -611,8 +983,12 @@ fn save(app: &AppHandle, save_path: &String) -> Result<(), error::Error> {
                 let drop_multiselect_sql: String = format!("DROP TABLE IF EXISTS MULTISELECT{column_oid}");
                 trans.execute(&drop_multiselect_sql, [])?;
 
-                // Drop the column from its host table
-                if trans.table_exists(Some("main"), &table_name)? {
+                // Drop the file list attachment table
+                let drop_file_list_sql: String = format!("DROP TABLE IF EXISTS FILELIST{column_oid}");
+                trans.execute(&drop_file_list_sql, [])?;
+
+                // Drop the column from its host table, if it is stored there
+                if trans.table_exists(Some("main"), &table_name)? && trans.column_exists(Some("main"), &table_name, &format!("COLUMN{column_oid}"))? {
                     let drop_sql: String = format!("ALTER TABLE {table_name} DROP COLUMN COLUMN{column_oid}");
                     trans.execute(&drop_sql, [])?;
                 }
@@ -682,3 +1058,55 @@ fn save(app: &AppHandle, save_path: &String) -> Result<(), error::Error> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::testing;
+
+    #[test]
+    fn files_created_with_the_baseline_schema_are_migrated_when_opened() {
+        let _db = testing::open_database();
+        let file: NamedTempFile = NamedTempFile::new().unwrap();
+        let conn = Connection::open(file.path()).unwrap();
+        conn.execute_batch(include_str!("testdata/baseline_schema.sql")).unwrap();
+        conn.execute_batch(
+            "
+            INSERT INTO METADATA_SCHEMA (OID, NAME) VALUES (1, 'Monster');
+            INSERT INTO METADATA_TABLE (OID) VALUES (1);
+            INSERT INTO METADATA_COLUMN (OID, SCHEMA_OID, NAME, TYPE_OID, STYLE, ORDERING) VALUES (2, 1, 'Name', -1, '', 0);
+            INSERT INTO METADATA_SCHEMA_ORDERBY (SCHEMA_OID, COLUMN_OID, SORT_ASCENDING) VALUES (1, 2, TRUE);
+            CREATE TABLE TABLE1 (OID INTEGER PRIMARY KEY, TRASH INTEGER NOT NULL DEFAULT 0, COLUMN2 TEXT) STRICT;
+            INSERT INTO TABLE1 (OID, COLUMN2) VALUES (1, 'Goblin');
+            ",
+        )
+        .unwrap();
+        drop(conn);
+
+        assert_eq!(probe_database(file.path().to_string_lossy().into_owned()).unwrap().schema_version, 0);
+        init_existing(file.path().to_string_lossy().into_owned(), JournalMode::Delete).unwrap();
+        assert_eq!(testing::query_one::<i64>("PRAGMA user_version"), SCHEMA_VERSION);
+
+        // The existing column reads back with the defaults of the options added since
+        let (case_insensitive_unique, empty_as_null, reference_on_delete, integer_format): (bool, bool, String, String) = open()
+            .unwrap()
+            .query_one(
+                "SELECT CASE_INSENSITIVE_UNIQUE, EMPTY_AS_NULL, REFERENCE_ON_DELETE, INTEGER_FORMAT FROM METADATA_COLUMN_VIEW WHERE OID = 2",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert!(!case_insensitive_unique);
+        assert!(empty_as_null);
+        assert_eq!(reference_on_delete, "SET NULL");
+        assert_eq!(integer_format, "PLAIN");
+        assert!(testing::query_one::<bool>("SELECT NULLS_LAST FROM METADATA_SCHEMA_ORDERBY_VIEW WHERE SCHEMA_OID = 1"));
+        assert!(!testing::query_one::<bool>("SELECT TRACK_TIMESTAMPS FROM METADATA_TABLE WHERE OID = 1"));
+        assert_eq!(testing::query_one::<i64>("SELECT COUNT(*) FROM TABLE1 WHERE VERSION = 0 AND TRASHED_AT IS NULL"), 1);
+
+        // New tables can be created and filled alongside the existing one
+        let table_oid: i64 = testing::create_table("Treasure", &[]);
+        testing::insert_row(table_oid);
+        assert_eq!(testing::query_one::<i64>("SELECT COUNT(*) FROM METADATA_TABLE"), 2);
+    }
+}
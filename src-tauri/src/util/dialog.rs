@@ -20,6 +20,10 @@ pub enum Dialog {
     CreateColumn {
         schema_oid: i64,
         column_ordering: Option<i64>,
+        #[serde(default)]
+        reference_column_oid: Option<i64>,
+        #[serde(default)]
+        position: Option<String>,
     },
     EditColumn {
         column_oid: i64,
@@ -101,16 +105,22 @@ impl Dialog {
             Self::CreateColumn {
                 schema_oid,
                 column_ordering,
+                reference_column_oid,
+                position,
             } => {
                 WebviewWindowBuilder::new(
                     app,
                     label,
                     WebviewUrl::App(
                         format!(
-                            "/src/dialog/column.html?schema_oid={schema_oid}{}",
+                            "/src/dialog/column.html?schema_oid={schema_oid}{}{}",
                             match column_ordering {
                                 Some(o) => format!("&column_ordering={o}"),
                                 None => String::from(""),
+                            },
+                            match (reference_column_oid, position) {
+                                (Some(c), Some(p)) => format!("&reference_column_oid={c}&position={p}"),
+                                _ => String::from(""),
                             }
                         )
                         .into(),
@@ -4,6 +4,7 @@ use serde::Serialize;
 use tauri::ipc::InvokeError;
 use tauri::{ipc::Invoke, Error as TauriError};
 
+#[derive(Debug)]
 pub enum Error {
     AdhocError(&'static str),
 
@@ -12,6 +13,51 @@ pub enum Error {
         column_name: String,
     },
 
+    /// Error for when a value is duplicated in a column that requires unique values.
+    DuplicateValue {
+        column_name: String,
+        value: String,
+    },
+
+    /// Error for when a file is too large to be encoded as base64.
+    FileTooLarge {
+        size: i64,
+        max_size: i64,
+    },
+
+    /// Error for when reading or writing a file fails.
+    FileIoError {
+        action: &'static str,
+        path: Option<String>,
+        error: std::io::Error,
+    },
+
+    /// Error for when a row cannot be trashed because it is referenced by a column that restricts deletion.
+    RestrictedDelete {
+        column_name: String,
+    },
+
+    /// Error for when a table cannot be trashed because other tables inherit its columns.
+    InheritedSchemaTrash {
+        schema_name: String,
+        inheritor_names: Vec<String>,
+    },
+
+    /// Error for when a table is given master tables that do not exist, have been trashed, or are not tables.
+    InvalidMasterTables {
+        schema_oids: Vec<i64>,
+    },
+
+    /// Error for when a table is looked up by an OID that does not belong to any table.
+    TableNotFound {
+        table_oid: i64,
+    },
+
+    /// Error for when a long-running process is stopped at the user's request.
+    ProcessCancelled {
+        processid: i64,
+    },
+
     /// Error for when a column with type Primitive, Object, Select, or Multiselect does not belong to a table.
     OrphanedDataColumn {
         column_oid: i64,
@@ -93,6 +139,56 @@ impl Into<String> for Error {
                 return format!("Multiple columns in table with the name \"{}\"!", column_name.replace("\\", "\\\\").replace("\"", "\\\""))
             }
 
+            Self::DuplicateValue { column_name, value } => {
+                return format!("The value \"{}\" already exists in column \"{}\"!", value.replace("\\", "\\\\").replace("\"", "\\\""), column_name.replace("\\", "\\\\").replace("\"", "\\\""));
+            }
+
+            Self::FileTooLarge { size, max_size } => {
+                return format!("The file is {size} bytes, which is larger than the limit of {max_size} bytes for displaying files. Download the file instead.");
+            }
+
+            Self::FileIoError { action, path, error } => {
+                return match path {
+                    Some(path) => format!("Unable to {action} \"{}\" ({}): {error}", path.replace("\\", "\\\\").replace("\"", "\\\""), error.kind()),
+                    None => format!("Unable to {action} ({}): {error}", error.kind()),
+                };
+            }
+
+            Self::RestrictedDelete { column_name } => {
+                return format!("The row cannot be deleted because it is referenced in column \"{}\"!", column_name.replace("\\", "\\\\").replace("\"", "\\\""));
+            }
+
+            Self::InheritedSchemaTrash { schema_name, inheritor_names } => {
+                return format!(
+                    "The table \"{}\" cannot be deleted because it is inherited by {}!",
+                    schema_name.replace("\\", "\\\\").replace("\"", "\\\""),
+                    inheritor_names
+                        .iter()
+                        .map(|inheritor_name| format!("\"{}\"", inheritor_name.replace("\\", "\\\\").replace("\"", "\\\"")))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                );
+            }
+
+            Self::InvalidMasterTables { schema_oids } => {
+                return format!(
+                    "The table cannot inherit from the schemas with IDs {}, because they are not existing tables!",
+                    schema_oids
+                        .iter()
+                        .map(|schema_oid| schema_oid.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                );
+            }
+
+            Self::TableNotFound { table_oid } => {
+                return format!("The table with ID {table_oid} does not exist!");
+            }
+
+            Self::ProcessCancelled { processid } => {
+                return format!("Process {processid} was cancelled.");
+            }
+
             Self::OrphanedDataColumn { column_oid, column_name } => {
                 return format!("Data column \"{}\" (ID {column_oid}) does not belong to a table!", column_name.replace("\\", "\\\\").replace("\"", "\\\""));
             }
@@ -1191,6 +1191,20 @@ impl Formula {
         });
     }
 
+    /// Finds every parameter referenced in a formula string.
+    /// Returns the text of each reference, along with the datasource alias and column OID that it refers to.
+    pub fn find_params(str: &str) -> Vec<(String, String, i64)> {
+        let param_regex: Regex = Regex::new(r#"@\{(ROOT\d+(?:_MASTER\d+|_INHERITOR\d+|_COLUMN\d+)*)_COLUMN(\d+)\}"#).unwrap();
+        param_regex
+            .captures_iter(str)
+            .filter_map(|param_cap| {
+                let (param_str, [datasource_alias, column_oid_content]) = param_cap.extract();
+                let column_oid: i64 = column_oid_content.parse::<i64>().ok()?;
+                Some((String::from(param_str), String::from(datasource_alias), column_oid))
+            })
+            .collect()
+    }
+
     /// Parse a formula from a string.
     pub fn parse(str: String) -> Result<Self, error::Error> {
         // Parse the formula
@@ -1,10 +1,75 @@
+use crate::util::channel::Sender;
+use crate::util::error::Error;
+use serde::Serialize;
 use std::sync::Mutex;
 
 static CUR_PROCESSID: Mutex<i64> = Mutex::new(0);
+static CANCELLED_PROCESSIDS: Mutex<Vec<i64>> = Mutex::new(Vec::new());
 
 /// Gets a unique processid.
 pub fn get_processid() -> i64 {
     let mut cur_processid = CUR_PROCESSID.lock().unwrap();
     *cur_processid += 1;
     (*cur_processid).clone()
-}
\ No newline at end of file
+}
+
+/// Requests that the process with the given processid stops at its next opportunity.
+pub fn cancel_process(processid: i64) {
+    let mut cancelled_processids = CANCELLED_PROCESSIDS.lock().unwrap();
+    if !cancelled_processids.contains(&processid) {
+        cancelled_processids.push(processid);
+    }
+}
+
+/// Checks whether the process with the given processid has been asked to stop.
+pub fn is_cancelled(processid: i64) -> bool {
+    let cancelled_processids = CANCELLED_PROCESSIDS.lock().unwrap();
+    cancelled_processids.contains(&processid)
+}
+
+/// Forgets any cancellation of the process with the given processid, once the process has stopped.
+pub fn end_process(processid: i64) {
+    let mut cancelled_processids = CANCELLED_PROCESSIDS.lock().unwrap();
+    cancelled_processids.retain(|cancelled_processid| *cancelled_processid != processid);
+}
+
+/// The number of items processed between each progress update.
+const PROGRESS_INTERVAL: i64 = 100;
+
+/// The progress of a long-running operation, such as an import or export.
+#[derive(Serialize, Clone)]
+pub struct Progress {
+    pub processed: i64,
+    pub total: i64,
+}
+
+/// Tracks the progress of a long-running operation, sending updates at regular intervals.
+pub struct ProgressTracker<'a> {
+    sender: Sender<'a, Progress>,
+    processed: i64,
+    total: i64,
+}
+
+impl<'a> ProgressTracker<'a> {
+    /// Starts tracking an operation over the given total number of items.
+    pub fn new(sender: Sender<'a, Progress>, total: i64) -> Self {
+        Self {
+            sender,
+            processed: 0,
+            total,
+        }
+    }
+
+    /// Records that an item was processed.
+    /// An update is sent after every few items, and after the last item.
+    pub fn increment(&mut self) -> Result<(), Error> {
+        self.processed += 1;
+        if self.processed % PROGRESS_INTERVAL == 0 || self.processed == self.total {
+            self.sender.send(Progress {
+                processed: self.processed,
+                total: self.total,
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 import { message } from "@tauri-apps/plugin-dialog";
 import { Channel } from "@tauri-apps/api/core";
-import { DropdownValue, getColumnAsync, getSchemaMetadataAsync, HierarchicalListItemMetadata, queryAsync } from "../util/query";
-import { FullMetadata as ColumnFullMetadata, ColumnType, Primitive } from "../util/column";
+import { DropdownValue, getColumnAsync, getColumnDefaultAsync, getSchemaMetadataAsync, HierarchicalListItemMetadata, queryAsync } from "../util/query";
+import { FullMetadata as ColumnFullMetadata, ColumnType, IntegerFormat, Primitive } from "../util/column";
 import { closeDialogAsync, openDialogAsync } from "../util/dialog";
-import { executeAsync } from "../util/action";
+import { ColumnPosition, executeAsync } from "../util/action";
 import { Schema } from "../util/schema";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
 
@@ -12,8 +12,13 @@ const urlParamSchemaOid: string | null = urlParams.get('schema_oid');
 let schemaOid: number | null = urlParamSchemaOid ? parseInt(urlParamSchemaOid) : null;
 const urlParamColumnOrdering: string | null = urlParams.get('column_ordering');
 let columnOrdering: number | null = urlParamColumnOrdering ? parseInt(urlParamColumnOrdering) : null;
+const urlParamReferenceColumnOid: string | null = urlParams.get('reference_column_oid');
+const referenceColumnOid: number | null = urlParamReferenceColumnOid ? parseInt(urlParamReferenceColumnOid) : null;
+const columnPosition: ColumnPosition = urlParams.get('position') == 'after' ? 'after' : 'before';
 const urlParamColumnOid: string | null = urlParams.get('column_oid');
 const columnOid: number | null = urlParamColumnOid ? parseInt(urlParamColumnOid) : null;
+let preexistingColumnType: ColumnType | null = null;
+let preexistingReferenceDisplayColumnOid: number | null = null;
 
 /**
  * Populate fields in preparation for a new column.
@@ -21,6 +26,7 @@ const columnOid: number | null = urlParamColumnOid ? parseInt(urlParamColumnOid)
 function populateNewColumnMetadata() {    
     // Populate tables that can be referenced by Object/Select/Multiselect column type
     const associatedTableOption: HTMLSelectElement = document.getElementById('column-associated-table') as HTMLSelectElement;
+    const referenceSubtypeFilterOption: HTMLSelectElement = document.getElementById('column-reference-subtype-filter') as HTMLSelectElement;
     queryAsync({
         columnAssociatedTables: {
             channel: new Channel<DropdownValue>((table) => {
@@ -31,6 +37,12 @@ function populateNewColumnMetadata() {
 
                 // Add option to the dropdown
                 associatedTableOption.appendChild(opt);
+
+                // Add option to the subtype filter dropdown
+                const subtypeOpt: HTMLOptionElement = document.createElement('option');
+                subtypeOpt.value = table.value.toString();
+                subtypeOpt.label = table.label;
+                referenceSubtypeFilterOption.appendChild(subtypeOpt);
             })
         }
     });
@@ -59,6 +71,8 @@ function populateNewColumnMetadata() {
 function populatePreexistingColumnMetadata(column: ColumnFullMetadata) {
     schemaOid = column.schema.oid;
     columnOrdering = column.ordering;
+    preexistingColumnType = column.columnType;
+    preexistingReferenceDisplayColumnOid = column.referenceDisplayColumnOid;
 
     // Populate name
     const columnNameElem: HTMLInputElement = document.getElementById('column-name') as HTMLInputElement;
@@ -101,12 +115,42 @@ function populatePreexistingColumnMetadata(column: ColumnFullMetadata) {
     const primaryKeyElem: HTMLInputElement = document.getElementById('column-is-primary-key') as HTMLInputElement;
     primaryKeyElem.checked = column.isPrimaryKey;
 
+    // Populate whether column values must be unique regardless of case
+    const caseInsensitiveUniqueElem: HTMLInputElement = document.getElementById('column-case-insensitive-unique') as HTMLInputElement;
+    caseInsensitiveUniqueElem.checked = column.caseInsensitiveUnique;
+
+    // Populate whether empty text values are stored as NULL
+    const emptyAsNullElem: HTMLInputElement = document.getElementById('column-empty-as-null') as HTMLInputElement;
+    emptyAsNullElem.checked = column.emptyAsNull;
+
+    // Populate whether JSON values are validated and pretty-printed
+    const prettyPrintJsonElem: HTMLInputElement = document.getElementById('column-pretty-print-json') as HTMLInputElement;
+    prettyPrintJsonElem.checked = column.prettyPrintJson;
+
+    // Populate the behavior when a referenced row is deleted
+    const referenceOnDeleteElem: HTMLSelectElement = document.getElementById('column-reference-on-delete') as HTMLSelectElement;
+    referenceOnDeleteElem.value = column.referenceOnDelete;
+
+    // Populate the display format of integer values
+    const integerFormatElem: HTMLSelectElement = document.getElementById('column-integer-format') as HTMLSelectElement;
+    const integerFormatWidthElem: HTMLInputElement = document.getElementById('column-integer-format-width') as HTMLInputElement;
+    if (typeof column.integerFormat === 'string') {
+        integerFormatElem.value = column.integerFormat;
+    } else {
+        integerFormatElem.value = 'zeroPadded';
+        integerFormatWidthElem.value = column.integerFormat.zeroPadded.width.toString();
+    }
+
     // Populate default value
     const defaultValueElem: HTMLInputElement = document.getElementById('column-default-value') as HTMLInputElement;
     defaultValueElem.value = column.defaultValue ?? '';
+    getColumnDefaultAsync(column.oid)
+        .then((defaultDisplay) => defaultValueElem.value = defaultDisplay ?? '')
+        .catch(() => {});
 
     // Populate tables that can be referenced by Object/Select/Multiselect column type
     const associatedTableOption: HTMLSelectElement = document.getElementById('column-associated-table') as HTMLSelectElement;
+    const referenceSubtypeFilterOption: HTMLSelectElement = document.getElementById('column-reference-subtype-filter') as HTMLSelectElement;
     queryAsync({
         columnAssociatedTables: {
             channel: new Channel<DropdownValue>((table) => {
@@ -122,6 +166,15 @@ function populatePreexistingColumnMetadata(column: ColumnFullMetadata) {
 
                 // Add option to the dropdown
                 associatedTableOption.appendChild(opt);
+
+                // Add option to the subtype filter dropdown, auto-selecting the current filter
+                const subtypeOpt: HTMLOptionElement = document.createElement('option');
+                subtypeOpt.value = table.value.toString();
+                subtypeOpt.label = table.label;
+                if (table.value == column.referenceSubtypeFilter) {
+                    subtypeOpt.selected = true;
+                }
+                referenceSubtypeFilterOption.appendChild(subtypeOpt);
             })
         }
     });
@@ -244,6 +297,43 @@ function compileColumn(): ColumnFullMetadata {
     const primaryKeyElem: HTMLInputElement | null = document.getElementById('column-is-primary-key') as HTMLInputElement;
     const isPrimaryKey: boolean = primaryKeyElem?.checked ?? false;
 
+    // Extract whether column values must be unique regardless of case
+    const caseInsensitiveUniqueElem: HTMLInputElement | null = document.getElementById('column-case-insensitive-unique') as HTMLInputElement;
+    const caseInsensitiveUnique: boolean = caseInsensitiveUniqueElem?.checked ?? false;
+
+    // Extract whether empty text values are stored as NULL
+    const emptyAsNullElem: HTMLInputElement | null = document.getElementById('column-empty-as-null') as HTMLInputElement;
+    const emptyAsNull: boolean = emptyAsNullElem?.checked ?? true;
+
+    // Extract whether JSON values are validated and pretty-printed
+    const prettyPrintJsonElem: HTMLInputElement | null = document.getElementById('column-pretty-print-json') as HTMLInputElement;
+    const prettyPrintJson: boolean = prettyPrintJsonElem?.checked ?? false;
+
+    // Extract the behavior when a referenced row is deleted
+    const referenceOnDeleteElem: HTMLSelectElement | null = document.getElementById('column-reference-on-delete') as HTMLSelectElement;
+    const referenceOnDelete: 'setNull' | 'cascade' | 'restrict' = (referenceOnDeleteElem?.value ?? 'setNull') as 'setNull' | 'cascade' | 'restrict';
+
+    // Extract the subtype that references are restricted to
+    const referenceSubtypeFilterElem: HTMLSelectElement | null = document.getElementById('column-reference-subtype-filter') as HTMLSelectElement;
+    const referenceSubtypeFilter: number | null = referenceSubtypeFilterElem?.value ? parseInt(referenceSubtypeFilterElem.value) : null;
+
+    // Keep the column displayed by references only if the referenced table has not changed
+    let referenceDisplayColumnOid: number | null = null;
+    if (preexistingColumnType) {
+        if ('select' in columnType && 'select' in preexistingColumnType && columnType.select.tableOid == preexistingColumnType.select.tableOid) {
+            referenceDisplayColumnOid = preexistingReferenceDisplayColumnOid;
+        } else if ('multiselect' in columnType && 'multiselect' in preexistingColumnType && columnType.multiselect.tableOid == preexistingColumnType.multiselect.tableOid) {
+            referenceDisplayColumnOid = preexistingReferenceDisplayColumnOid;
+        }
+    }
+
+    // Extract the display format of integer values
+    const integerFormatElem: HTMLSelectElement | null = document.getElementById('column-integer-format') as HTMLSelectElement;
+    const integerFormatWidthElem: HTMLInputElement | null = document.getElementById('column-integer-format-width') as HTMLInputElement;
+    const integerFormat: IntegerFormat = integerFormatElem?.value == 'zeroPadded' 
+        ? { zeroPadded: { width: parseInt(integerFormatWidthElem?.value ?? '8') || 8 } }
+        : (integerFormatElem?.value ?? 'plain') as 'plain' | 'thousands' | 'hex';
+
     // Extract default value
     const defaultValueElem: HTMLInputElement = document.getElementById('column-default-value') as HTMLInputElement;
     const defaultValue: string = defaultValueElem.value;
@@ -259,7 +349,15 @@ function compileColumn(): ColumnFullMetadata {
         columnType: columnType,
         hidden: hidden,
         ordering: columnOrdering ?? -1,
+        isNullable: !isPrimaryKey, // Key columns cannot be left blank
         isPrimaryKey: isPrimaryKey,
+        caseInsensitiveUnique: caseInsensitiveUnique,
+        prettyPrintJson: prettyPrintJson,
+        emptyAsNull: emptyAsNull,
+        referenceOnDelete: referenceOnDelete,
+        referenceSubtypeFilter: referenceSubtypeFilter,
+        referenceDisplayColumnOid: referenceDisplayColumnOid,
+        integerFormat: integerFormat,
         defaultValue: defaultValue,
         style: columnStyle,
         schema: {
@@ -382,8 +480,14 @@ window.addEventListener("DOMContentLoaded", () => {
                 });
             });
         } else {
-            // Create a new column
-            await executeAsync({
+            // Create a new column, next to the reference column if there is one
+            await executeAsync(referenceColumnOid != null ? {
+                createColumnRelative: {
+                    metadata: column,
+                    referenceColumnOid: referenceColumnOid,
+                    position: columnPosition
+                }
+            } : {
                 createColumn: column
             })
             .then(closeDialogAsync)
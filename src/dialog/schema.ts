@@ -135,6 +135,11 @@ function populateSchemaMetadata() {
         if (mode == 'table') {
             getTableMetadataAsync(schemaOid).then((table) => {
                 let callbackFns: ((dropdownValue: DropdownValue) => void)[] = populatePreexistingSchemaMetadata(table.schema);
+
+                // Populate in the timestamp tracking flag
+                const trackTimestampsElem: HTMLInputElement = document.getElementById('schema-track-timestamps') as HTMLInputElement;
+                trackTimestampsElem.checked = table.trackTimestamps;
+
                 loadColumns(callbackFns);
             });
         } else {
@@ -166,6 +171,12 @@ function populateSchemaMetadata() {
         populateNewSchemaMetadata();
     }
 
+    // Hide table-only fields for reports
+    if (mode != 'table') {
+        const trackTimestampsRow: HTMLElement = document.getElementById('schema-track-timestamps-row') as HTMLElement;
+        trackTimestampsRow.style.display = 'none';
+    }
+
     // Hide tabs if necessary
     if (!schemaOid) {
         // Hide "Sort By" tab
@@ -227,8 +238,11 @@ function compileSchema(): SchemaFullMetadata {
  * Compiles the inputted metadata for a table.
  */
 function compileTable(): TableFullMetadata {
+    const trackTimestampsElem: HTMLInputElement = document.getElementById('schema-track-timestamps') as HTMLInputElement;
+
     return {
-        schema: compileSchema()
+        schema: compileSchema(),
+        trackTimestamps: trackTimestampsElem.checked
     };
 }
 
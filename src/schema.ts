@@ -1,5 +1,5 @@
 import { message } from "@tauri-apps/plugin-dialog";
-import { queryAsync } from "./util/query";
+import { getTableMetadataAsync, queryAsync, setTablePageSizeAsync } from "./util/query";
 import { Channel } from "@tauri-apps/api/core";
 import { FullMetadata as ColumnFullMetadata } from "./util/column";
 import { CellContent, CellStream } from "./util/cell";
@@ -21,7 +21,7 @@ if (urlParamSchemaOid) {
     pageNumInput.value = `${pageNum}`;
 
     // Update page size
-    const pageSize: number = urlParamPageSize ? parseInt(urlParamPageSize) : 2000;
+    let pageSize: number = urlParamPageSize ? parseInt(urlParamPageSize) : 2000;
     const pageSizeInput: HTMLInputElement = document.getElementById('page-size-input') as HTMLInputElement;
     pageSizeInput.value = `${pageSize}`;
 
@@ -62,11 +62,26 @@ if (urlParamSchemaOid) {
             const newPageSize: number = parseInt(pageSizeInput.value);
             if (newPageSize > 0 && isFinite(newPageSize)) {
                 urlParams.set('page_size', newPageSize.toString());
-                reload();
+
+                // Remember the page size for the next time the table is opened
+                setTablePageSizeAsync(schemaOid, newPageSize)
+                    .catch(() => {}) // Reports do not have a preferred page size
+                    .finally(reload);
             } else {
                 pageSizeInput.value = `${pageSize}`;
             }
         });
+
+        // Use the table's preferred page size, unless a page size was requested
+        if (!urlParamPageSize) {
+            const defaultPageSize: number | null = await getTableMetadataAsync(schemaOid)
+                .then((table) => table.defaultPageSize ?? null)
+                .catch(() => null); // Reports do not have a preferred page size
+            if (defaultPageSize) {
+                pageSize = defaultPageSize;
+                pageSizeInput.value = `${pageSize}`;
+            }
+        }
         
         // Construct the grid
         const grid: Grid = new Grid({
@@ -152,7 +167,11 @@ if (urlParamSchemaOid) {
             } else if ('addNewRowButton' in streamedCellContent) {
                 // Construct an "Add New Row" button
                 grid.addNewRowButton(streamedCellContent.addNewRowButton);
-            } else {
+            } else if ('conditionalStyle' in streamedCellContent) {
+                // Apply conditional formatting to a cell of the last row
+                const { columnOid, styles } = streamedCellContent.conditionalStyle;
+                grid.addConditionalStyleToRow(columnOid, styles);
+            } else if ('cell' in streamedCellContent) {
                 console.debug(streamedCellContent.cell);
                 try {
                     // Add cell to last row of grid
@@ -4,6 +4,12 @@ import { FullMetadata as ReportFullMetadata } from "./report";
 import { FullMetadata as ColumnFullMetadata } from "./column";
 import { CellContent, DataCellEntry } from "./cell";
 
+/** Where a new column is placed relative to an existing column. */
+export type ColumnPosition = 'before' | 'after';
+
+/** How a new row is positioned when the OID it is inserted at already belongs to another row. */
+export type InsertMode = 'shiftDown' | 'before' | 'after' | 'fail';
+
 export type Action = {
     createTable: TableFullMetadata
 } | {
@@ -14,10 +20,34 @@ export type Action = {
     editReport: ReportFullMetadata
 } | {
     trashSchema: number
+} | {
+    trashSchemaCascade: number
+} | {
+    untrashSchemas: number[]
+} | {
+    editSchemaDisplayOrder: {
+        schemaOid: number,
+        displayOrder: number | null
+    }
+} | {
+    reorderSchema: {
+        schemaOid: number,
+        newPosition: number
+    }
 } | {
     createColumn: ColumnFullMetadata
+} | {
+    createColumnRelative: {
+        metadata: ColumnFullMetadata,
+        referenceColumnOid: number,
+        position: ColumnPosition
+    }
 } | {
     editColumn: ColumnFullMetadata
+} | {
+    promoteObjectTable: {
+        tableOid: number
+    }
 } | {
     editColumnStyle: {
         metadata: ColumnFullMetadata,
@@ -28,6 +58,12 @@ export type Action = {
         metadata: ColumnFullMetadata,
         newColumnOrdering: number | null
     }
+} | {
+    swapColumnOrdering: {
+        schemaOid: number,
+        columnOidA: number,
+        columnOidB: number
+    }
 } | {
     trashColumn: {
         schemaOid: number,
@@ -37,6 +73,7 @@ export type Action = {
     createRow: {
         tableOid: number,
         rowOid: number | null,
+        insertMode?: InsertMode,
         fixedParentDatasource: [number, number, ColumnFullMetadata] | null
     }
 } | {
@@ -58,6 +95,18 @@ export type Action = {
     }
 } | {
     editCellContents: DataCellEntry
+} | {
+    clearCellContents: {
+        tableOid: number,
+        columnOid: number,
+        rowOid: number
+    }
+} | {
+    editRowContents: {
+        tableOid: number,
+        rowOid: number,
+        values: [number, DataCellEntry['value']][]
+    }
 };
 
 /**
@@ -69,4 +118,14 @@ export async function executeAsync(action: Action): Promise<void> {
     console.debug(action);
     console.trace();
     return await invoke('execute', { action: action });
-}
\ No newline at end of file
+}
+
+/** A description of what an action would change, if it were performed. */
+export type DryRunResult = {
+    /** The action that would undo the change, identifying anything the action would create. */
+    undoAction: Action | null
+};
+
+export async function executeDryRunAsync(action: Action): Promise<DryRunResult> {
+    return await invoke('execute_dry_run', { action: action });
+}
@@ -3,7 +3,7 @@ import { FullMetadata as ColumnFullMetadata, Primitive, ColumnType } from "./col
 import { openDialogAsync } from "./dialog";
 import { executeAsync } from "./action";
 import { open, save, message, ask } from "@tauri-apps/plugin-dialog";
-import { DropdownValue, getCellAsync, getImageSrcAsync, getProcessidAsync, queryAsync, SelectedHierarchicalListItemMetadata, uploadFileAsync } from "./query";
+import { DropdownValue, getCellAsync, getImageSrcAsync, getProcessidAsync, parseIntegerCellValueAsync, queryAsync, SelectedHierarchicalListItemMetadata, uploadFileAsync } from "./query";
 import { fileTypeFromBuffer, FileTypeResult } from "file-type";
 import { Channel } from "@tauri-apps/api/core";
 import { Menu, MenuItem } from "@tauri-apps/api/menu";
@@ -45,6 +45,13 @@ export type CellIdentifier = {
     queryFilter: string
 };
 
+/** The cells of a table row that were updated together. */
+export type RowUpdate = {
+    tableOid: number,
+    rowOid: number,
+    columnOids: number[]
+};
+
 export type File = {
     path: {
         oid: number,
@@ -56,6 +63,43 @@ export type File = {
     }
 };
 
+export type RowTimestamps = {
+    createdAt: string | null,
+    updatedAt: string | null
+}
+
+/** A file attached to a cell of a File List column. */
+export type AttachedFile = {
+    fileOid: number,
+    label: string | null
+}
+
+export type ChangedRows = {
+    version: number,
+    rowOids: number[],
+    trashedRowOids: number[]
+}
+
+export type ReferenceKeyPart = {
+    columnOid: number,
+    columnName: string,
+    label: string | null
+}
+
+export type SubtypeCount = {
+    tableOid: number,
+    tableName: string,
+    rowCount: number
+}
+
+export type RetypeOption = {
+    tableOid: number,
+    tableName: string,
+    isCurrent: boolean,
+    columnsLost: number,
+    columnsGained: number
+}
+
 export type SchemaRow = {
     rowIdentifier: {
         tableRow: {
@@ -69,7 +113,10 @@ export type SchemaRow = {
     },
     index: number,
     fixedParentDatasource: [number, number, ColumnFullMetadata] | null,
-    validationFailures: ValidationFailures
+    validationFailures: ValidationFailures,
+    hasErrors: boolean,
+    errorCount: number,
+    timestamps: RowTimestamps | null
 };
 export type AddNewRowButton = {
     tableOid: number,
@@ -78,7 +125,7 @@ export type AddNewRowButton = {
 };
 
 
-type CellContentTextFormat = 'plain' | 'jSON';
+type CellContentTextFormat = 'plain' | 'long' | 'point' | 'jSON';
 
 type ReadonlyCellContent = {
     cellIdentifier: CellIdentifier,
@@ -105,6 +152,7 @@ type IntegerEntryCellContent = {
     dataColumnOid: number,
     dataRowOid: number,
     value: number | null,
+    label: string | null,
     isolatedCellDependencies: CellDependency[],
     fullReloadCellDependencies: CellDependency[],
     validationFailures: ValidationFailures 
@@ -119,6 +167,17 @@ type NumberEntryCellContent = {
     fullReloadCellDependencies: CellDependency[],
     validationFailures: ValidationFailures 
 };
+type PercentageEntryCellContent = {
+    cellIdentifier: CellIdentifier,
+    dataTableOid: number,
+    dataColumnOid: number,
+    dataRowOid: number,
+    value: number | null,
+    label: string | null,
+    isolatedCellDependencies: CellDependency[],
+    fullReloadCellDependencies: CellDependency[],
+    validationFailures: ValidationFailures 
+};
 type DateEntryCellContent = {
     cellIdentifier: CellIdentifier,
     dataTableOid: number,
@@ -224,6 +283,7 @@ export type CellContent = { readonly: ReadonlyCellContent }
 | { textEntry: TextEntryCellContent }
 | { integerEntry: IntegerEntryCellContent }
 | { numberEntry: NumberEntryCellContent }
+| { percentageEntry: PercentageEntryCellContent }
 | { dateEntry: DateEntryCellContent }
 | { datetimeEntry: DatetimeEntryCellContent }
 | { checkboxEntry: CheckboxEntryCellContent } 
@@ -235,7 +295,9 @@ export type CellContent = { readonly: ReadonlyCellContent }
 | { multiSelectDropdown: MultiSelectDropdownCellContent };
 export type CellStream = { cell: CellContent }
 | { maxIndex: number }
+| { targetIndex: number | null }
 | { row: SchemaRow } 
+| { conditionalStyle: { columnOid: number, styles: string[] } }
 | { addNewRowButton: AddNewRowButton };
 
 
@@ -249,6 +311,10 @@ export type DataCellEntry = {
         integer: number | null 
     } | {
         number: number | null 
+    } | {
+        percentage: {
+            label: string | null 
+        }
     } | {
         boolean: boolean | null 
     } | {
@@ -259,10 +325,18 @@ export type DataCellEntry = {
         datetime: {
             label: string | null 
         }
+    } | {
+        point: {
+            label: string | null 
+        }
     } | {
         file: {
             fileOid: number | null 
         }
+    } | {
+        fileList: {
+            fileOid: number[]
+        }
     } | {
         object: {
             linkedRowOid: 'new' | 'delete' | {
@@ -329,6 +403,11 @@ function isDataCellEntry(obj: any): boolean {
     if (hasNumberProperty(value, 'number') || hasNullProperty(value, 'number'))
         return true;
 
+    // Check for percentage
+    if ('percentage' in value) {
+        return (hasStringProperty(value.percentage, 'label') || hasNullProperty(value.percentage, 'label'));
+    }
+
     // Check for boolean
     if ('boolean' in value && (value.boolean === null || typeof value.boolean === 'boolean'))
         return true;
@@ -603,9 +682,9 @@ export class Cell {
                         tableOid: content.textEntry.dataTableOid,
                         columnOid: content.textEntry.dataColumnOid,
                         rowOid: content.textEntry.dataRowOid,
-                        value: {
-                            text: clipboardAsText(data)
-                        }
+                        value: content.textEntry.format == 'point'
+                            ? { point: { label: clipboardAsText(data) } }
+                            : { text: clipboardAsText(data) }
                     }
                 })
                 .catch(async (e) => {
@@ -634,7 +713,7 @@ export class Cell {
                         columnOid: content.integerEntry.dataColumnOid,
                         rowOid: content.integerEntry.dataRowOid,
                         value: {
-                            integer: numericValue !== null && !Number.isNaN(numericValue) ? Math.floor(numericValue) : null
+                            integer: numericValue !== null && Number.isFinite(numericValue) ? Math.floor(numericValue) : null
                         }
                     }
                 })
@@ -677,6 +756,36 @@ export class Cell {
             };
 
 
+        } else if ('percentageEntry' in content) {
+            this.cellIdentifier = content.percentageEntry.cellIdentifier;
+            this.elem = this.#constructPercentageEntryCell(cwd, content.percentageEntry);
+
+            this.clip = {
+                columnOid: this.cellIdentifier.columnOid,
+                value: content.percentageEntry.label ? { text: content.percentageEntry.label } : null
+            };
+            this.setAsync = async (data) => {
+                await executeAsync({
+                    editCellContents: {
+                        tableOid: content.percentageEntry.dataTableOid,
+                        columnOid: content.percentageEntry.dataColumnOid,
+                        rowOid: content.percentageEntry.dataRowOid,
+                        value: {
+                            percentage: {
+                                label: clipboardAsText(data)
+                            }
+                        }
+                    }
+                })
+                .catch(async (e) => {
+                    await message(e, {
+                        title: 'Error while editing contents of cell.',
+                        kind: 'error'
+                    });
+                });
+            };
+
+
         } else if ('dateEntry' in content) {
             this.cellIdentifier = content.dateEntry.cellIdentifier;
             this.elem = this.#constructDateEntryCell(cwd, content.dateEntry);
@@ -1037,40 +1146,52 @@ export class Cell {
      * Applies callback functions when either the cell needs to be hot reloaded or the entire schema needs to be reloaded.
      */
     async startListeningForReloadAsync({ hotReloadCallbackFn, fullReloadCallbackFn } : { hotReloadCallbackFn?: (newCell: Cell) => Promise<void>, fullReloadCallbackFn?: () => Promise<void> }): Promise<void> {
-        this.#unlistenForReload = await listen<CellIdentifier>('cell', async (event) => {
-            const cellIdentifier = event.payload;
-            if ('tableOid' in cellIdentifier) {
-                console.debug(cellIdentifier);
-
-                console.debug(this.fullReloadDependencies);
-                console.debug(this.hotReloadDependencies);
-
-                if (this.fullReloadDependencies.some(cellDependency => (
-                    cellIdentifier.tableOid == cellDependency.tableOid
-                    && cellIdentifier.columnOid == cellDependency.columnOid
-                    && (cellDependency.rowOid === null || cellIdentifier.rowOid == cellDependency.rowOid)
-                ))) {
-                    console.debug(`Full reload is required.`);
-                    // Requires a full reload of the schema
-                    if (fullReloadCallbackFn) {
-                        await fullReloadCallbackFn();
-                    }
-                } else if (this.hotReloadDependencies.some(cellDependency => (
-                    cellIdentifier.tableOid == cellDependency.tableOid
-                    && cellIdentifier.columnOid == cellDependency.columnOid
-                    && (cellDependency.rowOid === null || cellIdentifier.rowOid == cellDependency.rowOid)
-                ))) {
-                    console.debug(`Hot reload is required.`);
-                    // Only a hot reload of this cell is required
-                    if (hotReloadCallbackFn) {
-                        const newCell: Cell = await this.getReloadedCellAsync();
-                        this.elem.replaceWith(newCell.elem);
-                        this.destroy();
-                        await hotReloadCallbackFn(newCell);
-                    }
+        const reloadAsync = async (cellIdentifiers: CellIdentifier[]) => {
+            const updatedCells = cellIdentifiers.filter(cellIdentifier => 'tableOid' in cellIdentifier);
+            if (updatedCells.length == 0) {
+                return;
+            }
+            console.debug(updatedCells);
+
+            console.debug(this.fullReloadDependencies);
+            console.debug(this.hotReloadDependencies);
+
+            if (this.fullReloadDependencies.some(cellDependency => updatedCells.some(cellIdentifier => (
+                cellIdentifier.tableOid == cellDependency.tableOid
+                && cellIdentifier.columnOid == cellDependency.columnOid
+                && (cellDependency.rowOid === null || cellIdentifier.rowOid == cellDependency.rowOid)
+            )))) {
+                console.debug(`Full reload is required.`);
+                // Requires a full reload of the schema
+                if (fullReloadCallbackFn) {
+                    await fullReloadCallbackFn();
+                }
+            } else if (this.hotReloadDependencies.some(cellDependency => updatedCells.some(cellIdentifier => (
+                cellIdentifier.tableOid == cellDependency.tableOid
+                && cellIdentifier.columnOid == cellDependency.columnOid
+                && (cellDependency.rowOid === null || cellIdentifier.rowOid == cellDependency.rowOid)
+            )))) {
+                console.debug(`Hot reload is required.`);
+                // Only a hot reload of this cell is required
+                if (hotReloadCallbackFn) {
+                    const newCell: Cell = await this.getReloadedCellAsync();
+                    this.elem.replaceWith(newCell.elem);
+                    this.destroy();
+                    await hotReloadCallbackFn(newCell);
                 }
             }
+        };
+
+        // Listen for updates to a single cell, or to several cells of a row at once
+        const unlistenForCell: UnlistenFn = await listen<CellIdentifier>('cell', (event) => reloadAsync([event.payload]));
+        const unlistenForRow: UnlistenFn = await listen<RowUpdate>('update-table-row', (event) => {
+            const { tableOid, rowOid, columnOids } = event.payload;
+            return reloadAsync(columnOids.map(columnOid => ({ tableOid, columnOid, rowOid })));
         });
+        this.#unlistenForReload = () => {
+            unlistenForCell();
+            unlistenForRow();
+        };
     }
 
 
@@ -1115,6 +1236,12 @@ export class Cell {
         if (format == 'plain') {
             elem.classList.add('plain');
             div.innerText = label;
+        } else if (format == 'long') {
+            elem.classList.add('long');
+            div.innerText = label;
+        } else if (format == 'point') {
+            elem.classList.add('point');
+            div.innerText = label;
         } else if (format == 'jSON') {
             elem.classList.add('json');
             if (label) {
@@ -1239,7 +1366,8 @@ export class Cell {
             navigator.locks.request(editingLock, async () => {
                 if (!editing) {
                     editing = true;
-                    input.value = content.label || '';
+                    // Datetime inputs do not accept the UTC designator
+                    input.value = (content.label || '').replace(/Z$/, '');
 
                     // Remove the readonly text, insert the input
                     elem.removeChild(readonly);
@@ -1275,9 +1403,9 @@ export class Cell {
                                 tableOid: content.dataTableOid,
                                 columnOid: content.dataColumnOid,
                                 rowOid: content.dataRowOid,
-                                value: {
-                                    text: label
-                                }
+                                value: content.format == 'point'
+                                    ? { point: { label: label } }
+                                    : { text: label }
                             }
                         }).catch(async (e) => {
                             await message(e, {
@@ -1320,12 +1448,12 @@ export class Cell {
         this.fullReloadDependencies = content.fullReloadCellDependencies;
         
         const elem: HTMLTableCellElement = cwd.createElement('td');
-        elem.setAttribute('label', content.value?.toString() || '');
+        elem.setAttribute('label', content.label ?? content.value?.toString() ?? '');
 
-        const readonly: HTMLDivElement = this.#constructLabel(cwd, elem, content.value?.toString() || '', 'plain');
+        const readonly: HTMLDivElement = this.#constructLabel(cwd, elem, content.label ?? content.value?.toString() ?? '', 'plain');
         const input: HTMLInputElement = cwd.createElement('input');
-        input.type = 'number';
-        input.step = '1';
+        input.type = 'text';
+        input.inputMode = 'numeric';
         input.addEventListener('blur', async () => { await this.stopEditingAsync() });
         input.addEventListener('keydown', async (e) => {
             if (e.key == 'Enter' && !e.ctrlKey && !e.shiftKey && !e.metaKey) {
@@ -1342,7 +1470,7 @@ export class Cell {
             navigator.locks.request(editingLock, async () => {
                 if (!editing) {
                     editing = true;
-                    input.value = content.value?.toString() || '';
+                    input.value = content.label ?? content.value?.toString() ?? '';
 
                     // Remove the readonly text, insert the input
                     elem.removeChild(readonly);
@@ -1359,16 +1487,17 @@ export class Cell {
                 if (editing) {
                     editing = false;
 
-                    // Validate the entered value
-                    const parsedValue: number = parseInt(input.value);
-                    if (Number.isNaN(parsedValue) && input.value) {
+                    // Validate the entered value, parsing it back from the column's display format
+                    let value: number | null;
+                    try {
+                        value = await parseIntegerCellValueAsync(content.dataColumnOid, input.value);
+                    } catch (e) {
                         await message(`Entered value is not an integer!`, {
                             kind: 'warning'
                         });
                         return;
                     }
 
-                    const value: number | null = Number.isNaN(parsedValue) ? null : parsedValue;
                     if (value !== content.value) {
                         // Update the cell contents in the database
                         await executeAsync({
@@ -1513,6 +1642,99 @@ export class Cell {
 
 
 
+    /**
+     * Construct a cell for percentage entry.
+     * @param cwd 
+     * @param content The content of a percentage entry cell.
+     */
+    #constructPercentageEntryCell(cwd: Document, content: PercentageEntryCellContent): HTMLTableCellElement {
+        this.hotReloadDependencies = content.isolatedCellDependencies;
+        this.fullReloadDependencies = content.fullReloadCellDependencies;
+        
+        const elem: HTMLTableCellElement = cwd.createElement('td');
+        elem.setAttribute('label', content.label || '');
+
+        const readonly: HTMLDivElement = this.#constructLabel(cwd, elem, content.label || '', 'plain');
+        const input: HTMLInputElement = cwd.createElement('input');
+        input.type = 'text';
+        input.addEventListener('blur', async () => { await this.stopEditingAsync() });
+        input.addEventListener('keydown', async (e) => {
+            if (e.key == 'Enter' && !e.ctrlKey && !e.shiftKey && !e.metaKey) {
+                e.preventDefault();
+                await this.stopEditingAsync();
+            }
+        });
+
+        const editingLock: string = JSON.stringify(this.cellIdentifier);
+        let editing: boolean = false;
+
+        // When you start editing, swap the readonly DIV for an editable INPUT
+        this.#startEditingAsync = async () => {
+            navigator.locks.request(editingLock, async () => {
+                if (!editing) {
+                    editing = true;
+                    input.value = content.label || '';
+
+                    // Remove the readonly text, insert the input
+                    elem.removeChild(readonly);
+                    elem.appendChild(input);
+                    input.focus();
+                    input.select();
+                }
+            });
+        };
+
+        // When you stop editing, update the database and swap the editable INPUT for the readonly DIV
+        this.#stopEditingAsync = async () => {
+            navigator.locks.request(editingLock, async () => {
+                if (editing) {
+                    editing = false;
+
+                    const label: string | null = input.value || null;
+                    if (label !== content.label) {
+                        // Update the cell contents in the database
+                        await executeAsync({
+                            editCellContents: {
+                                tableOid: content.dataTableOid,
+                                columnOid: content.dataColumnOid,
+                                rowOid: content.dataRowOid,
+                                value: {
+                                    percentage: {
+                                        label: label
+                                    }
+                                }
+                            }
+                        }).catch(async (e) => {
+                            await message(e, {
+                                title: 'Unable to update cell contents.',
+                                kind: 'error'
+                            });
+                        });
+                    }
+
+                    // Remove the input, restore the readonly text
+                    elem.removeChild(input);
+                    elem.appendChild(readonly);
+                }
+            });
+        };
+
+        // When you stop editing, discard changes
+        this.#revertEditAsync = async () => {
+            navigator.locks.request(editingLock, async () => {
+                if (editing) {
+                    editing = false;
+
+                    // Remove the input, restore the readonly text
+                    elem.removeChild(input);
+                    elem.appendChild(readonly);
+                }
+            });
+        };
+
+        return elem;
+    }
+
     /**
      * Construct a cell for date entry.
      * @param cwd 
@@ -1621,6 +1843,7 @@ export class Cell {
         const readonly: HTMLDivElement = this.#constructLabel(cwd, elem, content.label || '', 'plain');
         const input: HTMLInputElement = cwd.createElement('input');
         input.type = 'datetime-local';
+        input.step = '0.001';
         input.addEventListener('blur', async () => { await this.stopEditingAsync() });
         input.addEventListener('keydown', async (e) => {
             if (e.key == 'Enter' && !e.ctrlKey && !e.shiftKey && !e.metaKey) {
@@ -2255,7 +2478,8 @@ export class Cell {
                     await queryAsync({
                         tableRowLabels: {
                             processid: processid, 
-                            tableOid: content.dropdownTableOid
+                            tableOid: content.dropdownTableOid,
+                            columnOid: content.dataColumnOid
                         }
                     });
                     
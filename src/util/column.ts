@@ -10,7 +10,7 @@ import '@interactjs/actions/resize';
 import interact from '@interactjs/interact';
 import { ResizeEvent } from '@interactjs/actions/resize/plugin';
 
-export type Primitive = 'plainText' | 'markdownText' | 'jsonText' | 'xmlText' | 'integer' | 'number' | 'boolean' | 'date' | 'datetime' | 'file' | 'image';
+export type Primitive = 'plainText' | 'longText' | 'markdownText' | 'jsonText' | 'xmlText' | 'integer' | 'number' | 'percentage' | 'boolean' | 'date' | 'datetime' | 'point' | 'file' | 'image' | 'fileList' | 'rowNumber';
 
 export type ColumnType = {
     primitive: Primitive
@@ -50,7 +50,83 @@ export type FullMetadata = {
     style: string,
     ordering: number,
     defaultValue: string | null,
-    isPrimaryKey: boolean
+    isNullable: boolean,
+    isPrimaryKey: boolean,
+    caseInsensitiveUnique: boolean,
+    prettyPrintJson: boolean,
+    emptyAsNull: boolean,
+    referenceOnDelete: 'setNull' | 'cascade' | 'restrict',
+    referenceSubtypeFilter: number | null,
+    referenceDisplayColumnOid: number | null,
+    integerFormat: IntegerFormat
+};
+
+export type ColumnStyle = {
+    columnOid: number,
+    style: string
+};
+
+export type FormatCondition = 'isEmpty' | 'isNotEmpty' | {
+    equals: { value: string }
+} | {
+    notEquals: { value: string }
+} | {
+    contains: { value: string }
+} | {
+    lessThan: { value: number }
+} | {
+    lessThanOrEqual: { value: number }
+} | {
+    greaterThan: { value: number }
+} | {
+    greaterThanOrEqual: { value: number }
+};
+
+export type FormatRule = {
+    oid: number,
+    columnOid: number,
+    condition: FormatCondition,
+    style: string
+};
+
+export type ReferenceTarget = {
+    tableOid: number,
+    tableName: string
+};
+
+export type ColumnSource = {
+    columnOid: number,
+    sourceTableOid: number,
+    isInherited: boolean
+};
+
+export type ColumnValidations = {
+    columnOid: number,
+    isNullable: boolean,
+    isPrimaryKey: boolean,
+    caseInsensitiveUnique: boolean,
+    emptyAsNull: boolean,
+    prettyPrintJson: boolean,
+    referenceSubtypeFilter: number | null
+};
+
+export type IntegerFormat = 'plain' | 'thousands' | 'hex' | { zeroPadded: { width: number } };
+
+export type ColumnOfType = {
+    schemaOid: number,
+    columnOid: number,
+    name: string,
+    referencedSchemaOid: number | null
+};
+
+export type EffectiveColumn = {
+    columnOid: number,
+    name: string,
+    columnType: ColumnType,
+    ordering: number,
+    sourceTableOid: number,
+    sourceTableName: string,
+    isInherited: boolean
 };
 
 
@@ -109,7 +185,9 @@ export function createColumnHeaderHTML(schemaOid: number, column: FullMetadata):
                         openDialogAsync({
                             createColumn: {
                                 schemaOid,
-                                columnOrdering: column.ordering
+                                columnOrdering: null,
+                                referenceColumnOid: column.oid,
+                                position: 'before'
                             }
                         });
                     }
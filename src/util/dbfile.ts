@@ -1,10 +1,17 @@
 import { invoke } from "@tauri-apps/api/core";
 
+/**
+ * How changes are journaled before they are committed.
+ * WAL is faster, but DELETE works on network shares and keeps the file self-contained.
+ */
+export type JournalMode = 'wal' | 'delete';
+
 /**
  * Initializes a new DungeonDB file.
+ * @param journalMode The journal mode of the file. Defaults to WAL.
  */
-export async function newAsync(): Promise<void> {
-    await invoke('init_new', {});
+export async function newAsync(journalMode?: JournalMode): Promise<void> {
+    await invoke('init_new', { journalMode: journalMode ?? null });
 }
 
 /**
@@ -28,6 +35,56 @@ export async function saveAsAsync(): Promise<void> {
     await invoke('save_as', {});
 }
 
+/**
+ * Sets the number of actions after which the file is saved automatically, emitting a 'saved' event each time.
+ * Undoing and redoing an action each count as an action.
+ * @param actionCount The number of actions between saves, or null to disable automatic saving.
+ */
+export async function setAutosaveActionIntervalAsync(actionCount: number | null): Promise<void> {
+    await invoke('set_autosave_action_interval', { actionCount: actionCount });
+}
+
+/**
+ * The rows of a table that were permanently deleted from the trash.
+ */
+export type PurgedRows = {
+    tableOid: number,
+    tableName: string,
+    rowCount: number
+};
+
+/**
+ * Sets how long trashed rows are kept before being permanently deleted.
+ * Expired rows are deleted whenever a file is opened, emitting a 'trash-purged' event with the rows deleted.
+ * @param days The number of days that trashed rows are kept, or null to keep them indefinitely.
+ */
+export async function setTrashRetentionDaysAsync(days: number | null): Promise<void> {
+    await invoke('set_trash_retention_days', { days: days });
+}
+
+/**
+ * Permanently deletes the rows that have been in the trash for longer than the retention period.
+ * The undo history is cleared if any row is deleted.
+ * @returns The number of rows deleted from each table.
+ */
+export async function purgeExpiredTrashAsync(): Promise<PurgedRows[]> {
+    return await invoke('purge_expired_trash', {});
+}
+
+/** Descriptions of the actions on the undo and redo stacks, most recent first. */
+export type UndoHistory = {
+    undo: string[],
+    redo: string[]
+};
+
+/**
+ * Describes the actions that can currently be undone and redone.
+ * @returns The descriptions of the actions on each stack.
+ */
+export async function getUndoHistoryAsync(): Promise<UndoHistory> {
+    return await invoke('get_undo_history', {});
+}
+
 /**
  * Undoes the last action.
  */
@@ -40,4 +97,57 @@ export async function undoAsync(): Promise<void> {
  */
 export async function redoAsync(): Promise<void> {
     await invoke('redo', {});
-}
\ No newline at end of file
+}
+/**
+ * Writes every action in the action log of the open file to a JSON file.
+ * The log records the actions executed, undone, and redone through the undo history.
+ * It does not include changes made outside of actions, such as uploaded files and imported tables, so it cannot be used to rebuild the file.
+ * @param path The path of the JSON file.
+ */
+export async function exportActionLogAsync(path: string): Promise<void> {
+    await invoke('export_action_log', { path: path });
+}
+
+/**
+ * Discards every change made since the file was last saved.
+ */
+export async function discardSessionAsync(): Promise<void> {
+    await invoke('discard_session', {});
+}
+
+/**
+ * A cell whose value does not meet a constraint of its column.
+ */
+export type InvalidCell = {
+    tableOid: number,
+    columnOid: number,
+    rowOid: number,
+    message: string
+};
+
+/**
+ * Enables or disables validation of values written to cells until the database is next opened.
+ * While validation is disabled, cells can be left with duplicate values or malformed JSON.
+ * @param enabled Whether values are validated.
+ * @returns The cells that do not meet the constraints of their column, if validation was enabled again.
+ */
+export async function setValidationEnabledAsync(enabled: boolean): Promise<InvalidCell[]> {
+    return await invoke('set_validation_enabled', { enabled: enabled });
+}
+
+/**
+ * A summary of a DungeonDB file, read without opening it.
+ */
+export type DatabaseProbe = {
+    tableCount: number,
+    schemaVersion: number
+};
+
+/**
+ * Checks that a file is a DungeonDB file without replacing the currently open file.
+ * Throws an error if the file cannot be opened or is not a DungeonDB file.
+ * @param path The path to the file.
+ */
+export async function probeDatabaseAsync(path: string): Promise<DatabaseProbe> {
+    return await invoke('probe_database', { path: path });
+}
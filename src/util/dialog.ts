@@ -16,7 +16,9 @@ export type Dialog = {
 } | { 
     createColumn: {
         schemaOid: number,
-        columnOrdering: number | null
+        columnOrdering: number | null,
+        referenceColumnOid?: number,
+        position?: 'before' | 'after'
     }
 } | {
     editColumn: {
@@ -5,6 +5,7 @@ import { FullMetadata as ColumnFullMetadata, ColumnType } from "./column";
 import { Menu, Submenu } from "@tauri-apps/api/menu";
 import { LogicalPosition } from "@tauri-apps/api/dpi";
 import { openDialogAsync } from "./dialog";
+import { exportRowAsync } from "./query";
 import '@interactjs/auto-start';
 import '@interactjs/actions/resize';
 import interact from "@interactjs/interact";
@@ -39,6 +40,10 @@ class GridRow {
         // Construct the index element
         this.index = cwd.createElement('th');
         this.index.innerText = `${row.index}`;
+        if (row.hasErrors) {
+            this.index.classList.add('row-error');
+            this.index.title = `${row.errorCount} validation error${row.errorCount == 1 ? '' : 's'} in this row`;
+        }
 
         // Set up context menu
         this.#setupContextMenu();
@@ -61,6 +66,7 @@ class GridRow {
                                 createRow: {
                                     tableOid,
                                     rowOid,
+                                    insertMode: 'before',
                                     fixedParentDatasource: this.#row.fixedParentDatasource
                                 }
                             })
@@ -78,7 +84,8 @@ class GridRow {
                             await executeAsync({
                                 createRow: {
                                     tableOid,
-                                    rowOid: rowOid + 1,
+                                    rowOid,
+                                    insertMode: 'after',
                                     fixedParentDatasource: this.#row.fixedParentDatasource
                                 }
                             })
@@ -111,7 +118,20 @@ class GridRow {
                             });
                         }
                     },
-                    insertSubmenu
+                    insertSubmenu,
+                    {
+                        text: 'Copy Row as Text',
+                        action: async () => {
+                            await exportRowAsync(tableOid, rowOid, 'text')
+                            .then((rowText) => navigator.clipboard.writeText(rowText))
+                            .catch(async (e) => {
+                                await message(e, {
+                                    title: 'An error occurred while copying row.',
+                                    kind: 'error'
+                                });
+                            });
+                        }
+                    }
                 ]
             });
 
@@ -244,7 +264,9 @@ class GridColumn {
                                 await openDialogAsync({
                                     createColumn: {
                                         schemaOid: this.#schemaOid,
-                                        columnOrdering: this.metadata.ordering
+                                        columnOrdering: null,
+                                        referenceColumnOid: this.metadata.oid,
+                                        position: 'before'
                                     }
                                 });
                             }
@@ -256,7 +278,9 @@ class GridColumn {
                                 await openDialogAsync({
                                     createColumn: {
                                         schemaOid: this.#schemaOid,
-                                        columnOrdering: this.metadata.ordering + 1
+                                        columnOrdering: null,
+                                        referenceColumnOid: this.metadata.oid,
+                                        position: 'after'
                                     }
                                 });
                             }
@@ -1071,6 +1095,23 @@ export class Grid {
         }
     }
 
+    /**
+     * Applies the styles of conditional formatting rules to a cell in the last row in the grid.
+     * @param columnOid The OID of the column of the cell.
+     * @param styles The CSS styles to apply.
+     */
+    addConditionalStyleToRow(columnOid: number, styles: string[]) {
+        if (this.#rows.length > 0) {
+            const lastRow: GridRow = this.#rows[this.#rows.length - 1];
+            const cell: Cell | undefined = lastRow.cells.find(c => c.cellIdentifier.columnOid == columnOid);
+            if (cell) {
+                for (const style of styles) {
+                    cell.elem.style.cssText += `;${style}`;
+                }
+            }
+        }
+    }
+
 
     /**
      * The HREF to the previous page.
@@ -1,11 +1,12 @@
 import { Channel, invoke } from "@tauri-apps/api/core";
-import { FullMetadata as TableFullMetadata } from "./table";
-import { FullMetadata as ReportFullMetadata } from "./report";
-import { FullMetadata as ColumnFullMetadata } from "./column";
-import { CellContent, File, CellIdentifier, CellStream } from "./cell";
+import { FullMetadata as TableFullMetadata, DegenerateColumn, Group as TableGroup, ObjectNesting, StorageStats, Summary as TableSummary } from "./table";
+import { DependentReport, FullMetadata as ReportFullMetadata } from "./report";
+import { FullMetadata as ColumnFullMetadata, ColumnOfType, ColumnSource, ColumnStyle, ColumnValidations, EffectiveColumn, FormatCondition, FormatRule, ReferenceTarget } from "./column";
+import { CellContent, File, CellIdentifier, CellStream, RowTimestamps, ChangedRows, AttachedFile, SubtypeCount, RetypeOption, ReferenceKeyPart } from "./cell";
 import { message } from "@tauri-apps/plugin-dialog";
 import { Datasource } from "./datasource";
-import { Schema } from "./schema";
+import { Schema, SchemaViewRepair } from "./schema";
+import { InvalidCell } from "./dbfile";
 
 export type FlatListItemMetadata = {
     oid: number,
@@ -13,7 +14,8 @@ export type FlatListItemMetadata = {
 };
 export type HierarchicalListItemMetadata = FlatListItemMetadata & {
     masterOid: number | null,
-    level: number
+    level: number,
+    groupOid?: number | null
 };
 export type SelectedHierarchicalListItemMetadata = HierarchicalListItemMetadata & { selected: boolean };
 export type ToggledHierarchicalListItemMetadata = HierarchicalListItemMetadata & { disabled: boolean };
@@ -39,12 +41,21 @@ export type Limit = {
     }
 } | {
     singleRow: null
+} | {
+    aroundRow: {
+        rowOid: number,
+        radius: number
+    }
 };
 
 export type Query = {
     tables: {
         channel: Channel<HierarchicalListItemMetadata>
     }
+} | {
+    tableGroups: {
+        channel: Channel<TableGroup>
+    }
 } | {
     reports: {
         channel: Channel<HierarchicalListItemMetadata>
@@ -66,6 +77,11 @@ export type Query = {
         schemaOid: number,
         channel: Channel<ColumnFullMetadata>
     }
+} | {
+    effectiveColumns: {
+        tableOid: number,
+        channel: Channel<EffectiveColumn>
+    }
 } | {
     rootDatasources: {
         channel: Channel<DatasourceDropdownValue>
@@ -98,13 +114,15 @@ export type Query = {
         schemaOid: number,
         filters: [string, number][],
         limit: Limit,
+        visibleColumnOids?: number[] | null,
         columnChannel: Channel<ColumnFullMetadata>,
         cellChannel: Channel<CellStream>
     }
 } | {
     tableRowLabels: {
         tableOid: number,
-        processid: number
+        processid: number,
+        columnOid?: number
     }
 };
 
@@ -122,6 +140,30 @@ export async function getTableMetadataAsync(oid: number): Promise<TableFullMetad
     return await invoke('get_table_metadata', { tableOid: oid });
 }
 
+export async function findReportsUsingTableAsync(tableOid: number): Promise<DependentReport[]> {
+    return await invoke('find_reports_using_table', { tableOid: tableOid });
+}
+
+export async function setTablePageSizeAsync(tableOid: number, pageSize: number | null): Promise<void> {
+    return await invoke('set_table_page_size', { tableOid: tableOid, pageSize: pageSize });
+}
+
+export async function getAllTableMetadataAsync(): Promise<TableSummary[]> {
+    return await invoke('get_all_table_metadata', {});
+}
+
+export async function getTableStorageStatsAsync(tableOid: number): Promise<StorageStats> {
+    return await invoke('get_table_storage_stats', { tableOid: tableOid });
+}
+
+export async function findDegenerateColumnsAsync(tableOid: number): Promise<DegenerateColumn[]> {
+    return await invoke('find_degenerate_columns', { tableOid: tableOid });
+}
+
+export async function getTableChecksumAsync(tableOid: number): Promise<string> {
+    return await invoke('get_table_checksum', { tableOid: tableOid });
+}
+
 export async function getReportMetadataAsync(oid: number): Promise<ReportFullMetadata> {
     return await invoke('get_report_metadata', { reportOid: oid });
 }
@@ -134,14 +176,202 @@ export async function getColumnAsync(oid: number): Promise<ColumnFullMetadata> {
     return await invoke('get_column', { columnOid: oid });
 }
 
+export async function getColumnMaxDisplayLengthAsync(tableOid: number, columnOid: number): Promise<number> {
+    return await invoke('get_column_max_display_length', { tableOid: tableOid, columnOid: columnOid });
+}
+
+export async function getColumnDefaultAsync(columnOid: number): Promise<string | null> {
+    return await invoke('get_column_default', { columnOid: columnOid });
+}
+
+export async function getColumnSourcesAsync(schemaOid: number): Promise<ColumnSource[]> {
+    return await invoke('get_column_sources', { schemaOid: schemaOid });
+}
+
+export async function getColumnStylesAsync(schemaOid: number): Promise<ColumnStyle[]> {
+    return await invoke('get_column_styles', { schemaOid: schemaOid });
+}
+
+export async function normalizeColumnOrderingAsync(tableOid: number): Promise<number> {
+    return await invoke('normalize_column_ordering', { tableOid: tableOid });
+}
+
+export async function getColumnFormatRulesAsync(columnOid: number): Promise<FormatRule[]> {
+    return await invoke('get_column_format_rules', { columnOid: columnOid });
+}
+
+export async function addColumnFormatRuleAsync(columnOid: number, condition: FormatCondition, style: string): Promise<number> {
+    return await invoke('add_column_format_rule', { columnOid: columnOid, condition: condition, style: style });
+}
+
+export async function removeColumnFormatRuleAsync(ruleOid: number): Promise<void> {
+    return await invoke('remove_column_format_rule', { ruleOid: ruleOid });
+}
+
+export async function getColumnReferenceTargetAsync(columnOid: number): Promise<ReferenceTarget | null> {
+    return await invoke('get_column_reference_target', { columnOid: columnOid });
+}
+
+export async function getSchemaViewSqlAsync(schemaOid: number): Promise<{ mainViewSql: string | null, labelViewSql: string | null }> {
+    return await invoke('get_schema_view_sql', { schemaOid: schemaOid });
+}
+
+export async function validateFormulaColumnsAsync(formula: string): Promise<string[]> {
+    return await invoke('validate_formula_columns', { formula: formula });
+}
+
+export async function rebuildAllSchemaViewsAsync(): Promise<void> {
+    return await invoke('rebuild_all_schema_views');
+}
+
+export async function beginBulkModeAsync(tableOid: number): Promise<void> {
+    return await invoke('begin_bulk_mode', { tableOid: tableOid });
+}
+
+export async function endBulkModeAsync(tableOid: number): Promise<InvalidCell[]> {
+    return await invoke('end_bulk_mode', { tableOid: tableOid });
+}
+
+export async function repairSchemaViewsAsync(schemaOid: number): Promise<SchemaViewRepair> {
+    return await invoke('repair_schema_views', { schemaOid: schemaOid });
+}
+
+export async function getObjectNestingAsync(tableOid: number): Promise<ObjectNesting> {
+    return await invoke('get_object_nesting', { tableOid: tableOid });
+}
+
+export async function createTableGroupAsync(name: string): Promise<number> {
+    return await invoke('create_table_group', { name: name });
+}
+
+export async function renameTableGroupAsync(groupOid: number, name: string): Promise<void> {
+    await invoke('rename_table_group', { groupOid: groupOid, name: name });
+}
+
+export async function deleteTableGroupAsync(groupOid: number): Promise<void> {
+    await invoke('delete_table_group', { groupOid: groupOid });
+}
+
+export async function assignTableGroupAsync(tableOid: number, groupOid: number | null): Promise<void> {
+    await invoke('assign_table_group', { tableOid: tableOid, groupOid: groupOid });
+}
+
+export async function getColumnValidationsAsync(columnOid: number): Promise<ColumnValidations> {
+    return await invoke('get_column_validations', { columnOid: columnOid });
+}
+
+export async function parseIntegerCellValueAsync(columnOid: number, text: string): Promise<number | null> {
+    return await invoke('parse_integer_cell_value', { columnOid: columnOid, text: text });
+}
+
+export async function countDropdownValueUsageAsync(columnOid: number, valueOid: number): Promise<number> {
+    return await invoke('count_dropdown_value_usage', { columnOid: columnOid, valueOid: valueOid });
+}
+
+export async function renameDropdownValueAsync(columnOid: number, valueOid: number, newText: string): Promise<void> {
+    return await invoke('rename_dropdown_value', { columnOid: columnOid, valueOid: valueOid, newText: newText });
+}
+
+export async function findColumnsOfTypeAsync(mode: string): Promise<ColumnOfType[]> {
+    return await invoke('find_columns_of_type', { mode: mode });
+}
+
 export async function getCellAsync(cellIdentifier: CellIdentifier): Promise<CellContent> {
     return await invoke('get_cell', { cellIdentifier: cellIdentifier });
 }
 
+export async function getCellTrueValueAsync(tableOid: number, columnOid: number, rowOid: number): Promise<string | null> {
+    return await invoke('get_cell_true_value', { tableOid: tableOid, columnOid: columnOid, rowOid: rowOid });
+}
+
+export async function getRowTimestampsAsync(tableOid: number, rowOid: number): Promise<RowTimestamps | null> {
+    return await invoke('get_row_timestamps', { tableOid: tableOid, rowOid: rowOid });
+}
+
+export async function getChangedRowsAsync(tableOid: number, sinceVersion: number): Promise<ChangedRows> {
+    return await invoke('get_changed_rows', { tableOid: tableOid, sinceVersion: sinceVersion });
+}
+
+export async function getSubtypeCountsAsync(baseTableOid: number): Promise<SubtypeCount[]> {
+    return await invoke('get_subtype_counts', { baseTableOid: baseTableOid });
+}
+
+export async function getRetypeOptionsAsync(baseTableOid: number, baseRowOid: number): Promise<RetypeOption[]> {
+    return await invoke('get_retype_options', { baseTableOid: baseTableOid, baseRowOid: baseRowOid });
+}
+
+export async function reorderMultiselectSelectionAsync(tableOid: number, rowOid: number, columnOid: number, valueOid: number, newIndex: number): Promise<void> {
+    return await invoke('reorder_multiselect_selection', { tableOid: tableOid, rowOid: rowOid, columnOid: columnOid, valueOid: valueOid, newIndex: newIndex });
+}
+
+export async function listCellFilesAsync(tableOid: number, columnOid: number, rowOid: number): Promise<AttachedFile[]> {
+    return await invoke('list_cell_files', { tableOid: tableOid, columnOid: columnOid, rowOid: rowOid });
+}
+
+export async function addFileToCellAsync(tableOid: number, columnOid: number, rowOid: number, fileOid: number): Promise<void> {
+    return await invoke('add_file_to_cell', { tableOid: tableOid, columnOid: columnOid, rowOid: rowOid, fileOid: fileOid });
+}
+
+export async function removeFileFromCellAsync(tableOid: number, columnOid: number, rowOid: number, fileOid: number): Promise<void> {
+    return await invoke('remove_file_from_cell', { tableOid: tableOid, columnOid: columnOid, rowOid: rowOid, fileOid: fileOid });
+}
+
+export async function getReferenceDisplayAsync(referencedTableOid: number, referencedRowOid: number): Promise<string | null> {
+    return await invoke('get_reference_display', { referencedTableOid: referencedTableOid, referencedRowOid: referencedRowOid });
+}
+
+export async function getReferenceKeyPartsAsync(referencedTableOid: number, referencedRowOid: number): Promise<ReferenceKeyPart[]> {
+    return await invoke('get_reference_key_parts', { referencedTableOid: referencedTableOid, referencedRowOid: referencedRowOid });
+}
+
+export async function getNextOidAsync(tableOid: number): Promise<number> {
+    return await invoke('get_next_oid', { tableOid: tableOid });
+}
+
+export async function getOidRangeAsync(tableOid: number): Promise<[number, number] | null> {
+    return await invoke('get_oid_range', { tableOid: tableOid });
+}
+
+export async function exportReportAsync(reportOid: number, format: 'csv' | 'json', path: string): Promise<void> {
+    await invoke('export_report', { reportOid: reportOid, format: format, path: path });
+}
+
+export async function exportTableNdjsonAsync(tableOid: number, path: string): Promise<void> {
+    await invoke('export_table_ndjson', { tableOid: tableOid, path: path });
+}
+
+export async function exportRowAsync(tableOid: number, rowOid: number, format: 'json' | 'text'): Promise<string> {
+    return await invoke('export_row', { tableOid: tableOid, rowOid: rowOid, format: format });
+}
+
+export async function cloneTableSchemaAsync(sourceTableOid: number, newName: string): Promise<number> {
+    return await invoke('clone_table_schema', { sourceTableOid: sourceTableOid, newName: newName });
+}
+
+export async function setReferenceByDisplayAsync(tableOid: number, rowOid: number, columnOid: number, displayValue: string): Promise<number> {
+    return await invoke('set_reference_by_display', { tableOid: tableOid, rowOid: rowOid, columnOid: columnOid, displayValue: displayValue });
+}
+
+export async function setColumnNotNullAsync(tableOid: number, columnOid: number, backfillValue: string | null): Promise<number> {
+    return await invoke('set_column_not_null', { tableOid: tableOid, columnOid: columnOid, backfillValue: backfillValue });
+}
+
+export async function reassignReferencesAsync(tableOid: number, fromRowOid: number, toRowOid: number): Promise<number> {
+    return await invoke('reassign_references', { tableOid: tableOid, fromRowOid: fromRowOid, toRowOid: toRowOid });
+}
+
 export async function getProcessidAsync(): Promise<number> {
     return await invoke('get_processid', {});
 }
 
+export async function cancelImportAsync(processid: number): Promise<void> {
+    await invoke('cancel_import', { processid: processid });
+}
+
+export async function getFileSizeAsync(data: { file: File }): Promise<number> {
+    return await invoke('get_file_size', data);
+}
+
 export async function getImageSrcAsync(data: { file: File }): Promise<string> {
     return await invoke('get_image_src', data);
 }
@@ -152,4 +382,7 @@ export async function downloadFileAsync(data: { fileOid: number, filepath: strin
 
 export async function uploadFileAsync(data: { file: File, filepath: string }): Promise<number> {
     return await invoke('upload_file', data);
-}
\ No newline at end of file
+}
+export async function copyDropdownValuesAsync(sourceColumnOid: number, targetColumnOid: number): Promise<void> {
+    await invoke('copy_dropdown_values', { sourceColumnOid: sourceColumnOid, targetColumnOid: targetColumnOid });
+}
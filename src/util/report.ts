@@ -4,4 +4,10 @@ export type FullMetadata = {
     schema: SchemaFullMetadata,
     filterFormula: string | null,
     groupByColumnOids: number[]
-}
\ No newline at end of file
+}
+
+/** A report that draws data from a particular table. */
+export type DependentReport = {
+    reportOid: number,
+    reportName: string
+}
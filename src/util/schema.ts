@@ -12,4 +12,10 @@ export type FullMetadata = {
     name: string,
     masterSchemaOids: number[],
     orderByColumnOids: [number, boolean][]
-};
\ No newline at end of file
+};
+
+export type SchemaViewRepair = {
+    previousError: string | null,
+    unresolvedColumns: string[],
+    repaired: boolean
+};
@@ -1,5 +1,43 @@
 import { FullMetadata as SchemaFullMetadata } from "./schema";
 
 export type FullMetadata = {
-    schema: SchemaFullMetadata
-}
\ No newline at end of file
+    schema: SchemaFullMetadata,
+    trackTimestamps: boolean,
+    defaultPageSize?: number | null
+}
+export type Summary = {
+    metadata: FullMetadata,
+    columnCount: number
+}
+
+export type ColumnStorageStats = {
+    columnOid: number,
+    columnName: string,
+    nonNullCount: number,
+    bytes: number
+}
+
+export type StorageStats = {
+    rowCount: number,
+    totalBytes: number,
+    isApproximate: boolean,
+    columns: ColumnStorageStats[]
+}
+
+export type DegenerateColumn = {
+    columnOid: number,
+    columnName: string,
+    isAllNull: boolean,
+    sharedLabel: string | null
+}
+
+export type ObjectNesting = {
+    depth: number,
+    isCyclic: boolean
+}
+
+export type Group = {
+    oid: number,
+    name: string,
+    ordering: number
+}